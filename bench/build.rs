@@ -7,7 +7,7 @@ use std::path::Path;
 use quote::quote;
 use proc_macro2::{Ident, Span};
 
-use enum_utils_from_str::StrMapFunc;
+use enum_utils_from_str::{Backend, StrMapFunc};
 
 struct Dataset {
     name: String,
@@ -23,18 +23,19 @@ impl Dataset {
         File::create(&dir.join(name)).map(BufWriter::new)
     }
 
-    fn trie(&self) -> io::Result<()> {
-        let mut w = self.file("trie")?;
-        let mut t = StrMapFunc::new("_trie", "usize");
+    fn str_map(&self, method: &str, backend: Backend) -> io::Result<()> {
+        let mut w = self.file(method)?;
+        let mut t = StrMapFunc::new("_lookup", "usize");
         let entries = self.values.iter()
             .enumerate()
             .map(|(i, s)| (&**s, i));
-        t.entries(entries);
+        t.backend(backend).entries(entries);
 
+        let fname = Ident::new(method, Span::call_site());
         let o = quote!{
-            pub fn trie(s: &str) -> Option<usize> {
+            pub fn #fname(s: &str) -> Option<usize> {
                 #t
-                _trie(s.as_bytes())
+                _lookup(s.as_bytes())
             }
         };
         write!(&mut w, "{}", o)
@@ -162,7 +163,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     for data in &datasets {
         data.simple_match()?;
-        data.trie()?;
+        data.str_map("trie", Backend::Trie)?;
+        data.str_map("table", Backend::Table)?;
         data.phf()?;
 
         // gperf fails on large inputs
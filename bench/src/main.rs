@@ -79,8 +79,8 @@ macro_rules! benches {
 }
 
 benches! {
-    english => [control, phf, trie, gperf];
-    rust => [control, phf, trie, gperf];
-    http => [control, phf, trie, gperf];
-    google_1000_english_no_swears => [control, phf, trie, gperf];
+    english => [control, phf, trie, table, gperf];
+    rust => [control, phf, trie, table, gperf];
+    http => [control, phf, trie, table, gperf];
+    google_1000_english_no_swears => [control, phf, trie, table, gperf];
 }
@@ -10,6 +10,9 @@ use proc_macro2::{Literal, Ident, TokenStream, Span};
 
 /// Generates a lookup function for all the key-value pairs contained in the tree.
 ///
+/// The generated code never indexes into its input, so it contains no bounds checks and cannot
+/// panic. Each byte is bound by a slice pattern instead.
+///
 /// # Examples
 ///
 /// ```rust
@@ -32,14 +35,14 @@ use proc_macro2::{Literal, Ident, TokenStream, Span};
 ///
 /// # let generated = quote! {
 /// fn custom_lookup(s: &[u8]) -> Option<bool> {
-///     match s.len() {
-///         2 => if s[0] == b'n' && s[1] == b'o' {
+///     match *s {
+///         [s0, s1] => if s0 == b'n' && s1 == b'o' {
 ///             return Some(false);
 ///         },
-///         3 => if s[0] == b'y' && s[1] == b'e' {
-///             if s[2] == b'p' {
+///         [s0, s1, s2] => if s0 == b'y' && s1 == b'e' {
+///             if s2 == b'p' {
 ///                  return Some(true);
-///             } else if s[2] == b's' {
+///             } else if s2 == b's' {
 ///                 return Some(true);
 ///             }
 ///         },
@@ -59,7 +62,10 @@ pub struct StrMapFunc {
     atoms: Forest<TokenStream>,
     func_name: Ident,
     ret_ty: TokenStream,
+    keys: BTreeMap<Vec<u8>, TokenStream>,
     case: Case,
+    dash_underscore: bool,
+    backend: Backend,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -68,13 +74,41 @@ pub enum Case {
     Insensitive,
 }
 
+/// The number of distinct first bytes among the keys at which [`Backend::Trie`] starts
+/// dispatching on the first byte of the input before its length.
+const FIRST_BYTE_DISPATCH_MIN: usize = 8;
+
+/// The number of keys at which [`Backend::Trie`] emits the trie for each length as a separate
+/// function.
+const CHUNKED_MIN: usize = 256;
+
+/// The shape of the code generated by a [`StrMapFunc`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    /// Dispatches on the length of the input, then compares bytes one at a time using a trie.
+    ///
+    /// If many keys begin with different bytes, the first byte of the input is dispatched on
+    /// before its length.
+    #[default]
+    Trie,
+
+    /// Compares the input against each key in turn with a single `match` expression.
+    Match,
+
+    /// Dispatches on the length of the input, then searches a table of the keys of that length.
+    Table,
+}
+
 impl StrMapFunc {
     pub fn new(func_name: &str, ret_ty: &str) -> Self {
         StrMapFunc {
             atoms: Default::default(),
             func_name: Ident::new(func_name, Span::call_site()),
             ret_ty: ret_ty.parse().unwrap(),
+            keys: Default::default(),
             case: Case::Sensitive,
+            dash_underscore: false,
+            backend: Backend::Trie,
         }
     }
 
@@ -83,13 +117,31 @@ impl StrMapFunc {
         self
     }
 
+    /// If set, `-` and `_` in a key each match either character.
+    pub fn dash_underscore_insensitive(&mut self, yes: bool) -> &mut Self {
+        self.dash_underscore = yes;
+        self
+    }
+
+    pub fn backend(&mut self, backend: Backend) -> &mut Self {
+        self.backend = backend;
+        self
+    }
+
     pub fn entry(&mut self, k: &str, v: impl ToTokens) -> &mut Self {
-        self.atoms.insert(k.as_bytes(), v.into_token_stream());
+        self.bytes_entry(k.as_bytes(), v)
+    }
+
+    /// Adds a key which need not be valid UTF-8.
+    pub fn bytes_entry(&mut self, k: &[u8], v: impl ToTokens) -> &mut Self {
+        let v = v.into_token_stream();
+        self.keys.insert(k.to_vec(), v.clone());
+        self.atoms.insert(k, v);
         self
     }
 
-    pub fn entries<'a, V: 'a>(&mut self, entries: impl IntoIterator<Item = (&'a str, V)>) -> &mut Self
-        where V: ToTokens,
+    pub fn entries<'a, V>(&mut self, entries: impl IntoIterator<Item = (&'a str, V)>) -> &mut Self
+        where V: 'a + ToTokens,
     {
         for (s, v) in entries.into_iter() {
             self.entry(s, v);
@@ -104,30 +156,230 @@ impl StrMapFunc {
     }
 }
 
-impl ToTokens for StrMapFunc {
-    fn to_tokens(&self, tokens: &mut TokenStream) {
-        let StrMapFunc { func_name, ret_ty, atoms, case } = self;
+impl StrMapFunc {
+    /// Returns a function, `_eq`, which compares the input with a key of the same length, taking
+    /// case and dash insensitivity into account. Returns `None` if plain slice equality suffices.
+    fn eq_fn(&self) -> Option<TokenStream> {
+        let ignore_case = self.case == Case::Insensitive;
+        if !ignore_case && !self.dash_underscore {
+            return None;
+        }
+
+        let mut conds = vec![quote!(a == b)];
+        if ignore_case {
+            conds.push(quote!(a.eq_ignore_ascii_case(&b)));
+        }
+
+        if self.dash_underscore {
+            conds.push(quote!(((a == b'-' || a == b'_') && (b == b'-' || b == b'_'))));
+        }
+
+        Some(quote! {
+            fn _eq(s: &[u8], k: &[u8]) -> bool {
+                s.iter().zip(k).all(|(&a, &b)| #( #conds )||*)
+            }
+        })
+    }
+
+    /// Returns a `match` on the length of the input, with a trie for each length.
+    ///
+    /// Each arm binds the bytes of the input with a slice pattern, so the tries need not index it.
+    /// If there are many keys, the trie for each length is emitted as a separate function to keep
+    /// the size of each function (and the time spent compiling it) manageable.
+    fn length_dispatch(&self, forest: &Forest<TokenStream>) -> TokenStream {
+        let chunked = self.keys.len() >= CHUNKED_MIN;
+
+        let mut helpers = vec![];
+        let mut match_arms = vec![];
+        for (&len, trie) in &forest.0 {
+            // The empty key has no bytes to compare, so the trie would be empty.
+            let branch = match self.keys.get(&[][..]) {
+                Some(v) if len == 0 => quote!({ return Some(#v); }),
+                _ => Forest::branch_tokens(trie, self.case == Case::Insensitive, self.dash_underscore),
+            };
+            let bytes = (0..len).map(byte_ident);
+            let arm = quote!([#( #bytes ),*] => #branch);
+
+            if !chunked {
+                match_arms.push(arm);
+                continue;
+            }
+
+            let ret_ty = &self.ret_ty;
+            let helper = Ident::new(&format!("_len_{}", len), Span::call_site());
+            let len = Literal::usize_unsuffixed(len);
+            helpers.push(quote! {
+                fn #helper(s: &[u8]) -> Option<#ret_ty> {
+                    match *s {
+                        #arm,
+                        _ => {}
+                    }
+
+                    None
+                }
+            });
+            match_arms.push(quote! {
+                #len => if let Some(v) = #helper(s) {
+                    return Some(v);
+                }
+            });
+        }
+
+        if !chunked {
+            return quote! {
+                match *s {
+                    #( #match_arms, )*
+                    _ => {}
+                }
+            };
+        }
+
+        quote! {
+            {
+                #( #helpers )*
+
+                match s.len() {
+                    #( #match_arms, )*
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    /// Returns the canonical form of the first byte of a key: the byte after case folding and
+    /// dash normalization, whichever apply.
+    fn fold_byte(&self, b: u8) -> u8 {
+        let b = if self.case == Case::Insensitive { b.to_ascii_lowercase() } else { b };
+        if self.dash_underscore && b == b'-' { b'_' } else { b }
+    }
+
+    fn trie_body(&self) -> TokenStream {
+        let mut by_first: BTreeMap<u8, Forest<TokenStream>> = BTreeMap::new();
+        for (k, v) in &self.keys {
+            if let Some(&b) = k.first() {
+                by_first.entry(self.fold_byte(b)).or_default().insert(k, v.clone());
+            }
+        }
+
+        if by_first.len() < FIRST_BYTE_DISPATCH_MIN {
+            return self.length_dispatch(&self.atoms);
+        }
 
-        let match_arms = atoms.0.iter()
-            .map(|(&len, trie)| {
-                let branch = Forest::branch_tokens(trie, *case == Case::Insensitive);
+        // Many keys differ in their first byte, so most misses can be rejected after a single
+        // comparison by dispatching on it before the length.
+        let match_arms = by_first.iter()
+            .map(|(&b, forest)| {
+                let mut pats = vec![b];
+                if self.case == Case::Insensitive && b.is_ascii_lowercase() {
+                    pats.push(b.to_ascii_uppercase());
+                }
+
+                if self.dash_underscore && b == b'_' {
+                    pats.push(b'-');
+                }
+
+                let pats = pats.into_iter().map(byte_literal);
+                let body = self.length_dispatch(forest);
+                quote!(#( Some(#pats) )|* => #body)
+            });
+
+        let empty = self.keys.get(&[][..])
+            .map(|v| quote!(None => return Some(#v),));
+
+        quote! {
+            match s.first() {
+                #( #match_arms, )*
+                #empty
+                _ => {}
+            }
+        }
+    }
+
+    fn match_body(&self) -> TokenStream {
+        let keys = self.keys.keys().map(|k| Literal::byte_string(k));
+        let values = self.keys.values();
+
+        match self.eq_fn() {
+            None => quote! {
+                match s {
+                    #( #keys => return Some(#values), )*
+                    _ => {}
+                }
+            },
+
+            Some(eq_fn) => {
+                let lens = self.keys.keys().map(|k| Literal::usize_unsuffixed(k.len()));
+                quote! {
+                    #eq_fn
+
+                    match s.len() {
+                        #( #lens if _eq(s, #keys) => return Some(#values), )*
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+
+    fn table_body(&self) -> TokenStream {
+        let mut by_len: BTreeMap<usize, Vec<(&[u8], &TokenStream)>> = BTreeMap::new();
+        for (k, v) in &self.keys {
+            by_len.entry(k.len()).or_default().push((k, v));
+        }
+
+        let eq_fn = self.eq_fn();
+        let eq = if eq_fn.is_some() {
+            quote!(_eq(s, k))
+        } else {
+            quote!(*k == s)
+        };
+
+        let match_arms = by_len.into_iter()
+            .map(|(len, entries)| {
+                let n = entries.len();
+                let keys = entries.iter().map(|(k, _)| Literal::byte_string(k));
+                let arms = entries.iter()
+                    .enumerate()
+                    .map(|(i, (_, v))| quote!(Some(#i) => return Some(#v)));
                 let len = Literal::usize_unsuffixed(len);
 
-                quote!(#len => #branch)
+                quote! {
+                    #len => {
+                        const KEYS: [&[u8]; #n] = [#( #keys ),*];
+                        match KEYS.iter().position(|k| #eq) {
+                            #( #arms, )*
+                            _ => {}
+                        }
+                    }
+                }
             });
 
-        let body = quote! {
+        quote! {
+            #eq_fn
+
             match s.len() {
                 #( #match_arms, )*
                 _ => {}
             }
+        }
+    }
+}
+
+impl ToTokens for StrMapFunc {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let StrMapFunc { func_name, ret_ty, .. } = self;
 
-            None
+        let body = match self.backend {
+            Backend::Trie => self.trie_body(),
+            Backend::Match => self.match_body(),
+            Backend::Table => self.table_body(),
         };
 
         tokens.extend(quote! {
             fn #func_name(s: &[u8]) -> Option<#ret_ty> {
                 #body
+
+                None
             }
         });
     }
@@ -155,9 +407,16 @@ impl<T> Forest<T> {
     }
 }
 
+/// Returns the name of the binding for the byte of the input at index `i`.
+fn byte_ident(i: usize) -> Ident {
+    Ident::new(&format!("s{}", i), Span::call_site())
+}
+
 fn byte_literal(b: u8) -> TokenStream {
+    // `char::escape_default` would escape control characters as `\u{..}`, which is not valid in a
+    // byte literal.
     if b < 128 {
-        let c: String = char::from(b).escape_default().collect();
+        let c: String = std::ascii::escape_default(b).map(char::from).collect();
         format!("b'{}'", c).parse().unwrap()
     } else {
         Literal::u8_unsuffixed(b).into_token_stream()
@@ -167,14 +426,13 @@ fn byte_literal(b: u8) -> TokenStream {
 impl<T> Forest<T>
     where T: ToTokens
 {
-    fn branch_tokens(node: &trie::Node<T>, ignore_case: bool) -> TokenStream {
+    fn branch_tokens(node: &trie::Node<T>, ignore_case: bool, dash_underscore: bool) -> TokenStream {
         use trie::TraversalOrder::*;
 
         let mut tok = vec![TokenStream::new()];
         let mut depth = 0;
         let mut is_first_child = true;
-        let mut dfs = node.dfs();
-        while let Some((order, node)) = dfs.next() {
+        for (order, node) in node.dfs() {
             if node.bytes.is_empty() {
                 continue;
             }
@@ -186,14 +444,24 @@ impl<T> Forest<T>
                         is_first_child = true;
                     }
 
-                    let i = (depth..depth+node.bytes.len()).map(Literal::usize_unsuffixed);
-                    let b = node.bytes.iter().cloned().map(byte_literal);
-
-                    if !ignore_case {
-                        tok.last_mut().unwrap().extend(quote!(if #( s[#i] == #b )&&*));
-                    } else {
-                        tok.last_mut().unwrap().extend(quote!(if #( s[#i].eq_ignore_ascii_case(&#b) )&&*));
-                    }
+                    let conds = node.bytes.iter()
+                        .enumerate()
+                        .map(|(i, &b)| {
+                            let c = byte_ident(depth + i);
+                            match b {
+                                b'-' | b'_' if dash_underscore => quote!((#c == b'-' || #c == b'_')),
+                                _ if ignore_case => {
+                                    let b = byte_literal(b);
+                                    quote!(#c.eq_ignore_ascii_case(&#b))
+                                }
+                                _ => {
+                                    let b = byte_literal(b);
+                                    quote!(#c == #b)
+                                }
+                            }
+                        });
+
+                    tok.last_mut().unwrap().extend(quote!(if #( #conds )&&*));
 
                     tok.push(TokenStream::new());
                     depth += node.bytes.len();
@@ -219,3 +487,24 @@ impl<T> Forest<T>
         ret
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunked() {
+        let keys: Vec<_> = (0..CHUNKED_MIN).map(|i| format!("k{}", i)).collect();
+
+        let mut small = StrMapFunc::new("_lookup", "usize");
+        small.entries(keys.iter().take(10).map(String::as_str).zip(0usize..));
+        assert!(!small.into_token_stream().to_string().contains("_len_"));
+
+        let mut large = StrMapFunc::new("_lookup", "usize");
+        large.entries(keys.iter().map(String::as_str).zip(0usize..));
+        let code = large.into_token_stream().to_string();
+        for len in 2..=4 {
+            assert!(code.contains(&format!("fn _len_{}", len)));
+        }
+    }
+}
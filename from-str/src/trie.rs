@@ -47,7 +47,7 @@ impl<T> Node<T> {
 
         let byte = suffix[0];
         let mut child = Box::new(Node::new(suffix, self.value.take()));
-        child.children = mem::replace(&mut self.children, Default::default());
+        child.children = mem::take(&mut self.children);
         self.children.insert(byte, child);
     }
 
@@ -58,7 +58,7 @@ impl<T> Node<T> {
         // prefix: "abc"
         // "abd"
         let split_idx = differs_at(prefix, &self.bytes)
-            .or_else(|| if l < self.bytes.len() { Some(l) } else { None });
+            .or(if l < self.bytes.len() { Some(l) } else { None });
 
         if let Some(idx) = split_idx {
             self.split_at(idx);
@@ -94,7 +94,7 @@ impl<T> Node<T> {
 
         self.children
             .get(&suffix[0])
-            .map_or(None, |c| c.get(bytes))
+            .and_then(|c| c.get(bytes))
     }
 
     pub fn dfs(&self) -> impl Iterator<Item = (TraversalOrder, NodeRef<'_, T>)> {
@@ -124,7 +124,9 @@ impl<'a, T> From<&'a Node<T>> for NodeRef<'a, T> {
     }
 }
 
-struct DfsIter<'a, T>(Vec<(&'a Node<T>, btree_map::Values<'a, u8, Box<Node<T>>>)>);
+type Children<'a, T> = btree_map::Values<'a, u8, Box<Node<T>>>;
+
+struct DfsIter<'a, T>(Vec<(&'a Node<T>, Children<'a, T>)>);
 
 impl<'a, T> DfsIter<'a, T> {
     fn new(node: &'a Node<T>) -> Self {
@@ -143,7 +145,7 @@ impl<'a, T> Iterator for DfsIter<'a, T> {
             Some((TraversalOrder::Pre, NodeRef::from(&**node)))
         } else {
             let (node, _) = self.0.pop().unwrap();
-            Some((TraversalOrder::Post, NodeRef::from(&*node)))
+            Some((TraversalOrder::Post, NodeRef::from(node)))
         }
     }
 }
@@ -162,7 +164,7 @@ mod tests {
         trie.insert(b"abz", ());
 
         let order: Vec<_> = trie.dfs()
-            .map(|(o, n)| (o, n.bytes.as_ref()))
+            .map(|(o, n)| (o, n.bytes))
             .collect();
 
         let expected: Vec<(_, &[u8])> = vec![
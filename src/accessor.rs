@@ -0,0 +1,98 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+use crate::attr::{Enum, ErrorList, RenameRule};
+
+/// Returns the name of a variant in `snake_case`, for use in method names.
+fn snake_case(v: &syn::Variant) -> String {
+    RenameRule::snake_case().apply_to_variant(&v.ident.to_string())
+}
+
+pub fn derive_is(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let Enum { name, variants, .. } = input;
+
+    let predicates = variants.iter()
+        .map(|(v, _)| {
+            let vident = &v.ident;
+            let method = format_ident!("is_{}", snake_case(v));
+            quote! {
+                pub const fn #method(&self) -> bool {
+                    matches!(*self, #name::#vident { .. })
+                }
+            }
+        });
+
+    Ok(quote! {
+        impl #name {
+            #( #predicates )*
+        }
+    })
+}
+
+pub fn derive_try_as(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let Enum { name, variants, .. } = input;
+
+    let accessors = variants.iter()
+        .filter(|(v, _)| v.fields != syn::Fields::Unit)
+        .map(|(v, _)| {
+            let vident = &v.ident;
+            let snake = snake_case(v);
+            let try_as = format_ident!("try_as_{}", snake);
+            let try_as_mut = format_ident!("try_as_{}_mut", snake);
+            let try_into = format_ident!("try_into_{}", snake);
+
+            // Fields are bound as `_0`, `_1`, ... regardless of whether they are named.
+            let members: Vec<_> = v.fields.iter()
+                .enumerate()
+                .map(|(i, f)| match &f.ident {
+                    Some(ident) => syn::Member::Named(ident.clone()),
+                    None => syn::Member::Unnamed(i.into()),
+                })
+                .collect();
+            let bindings: Vec<_> = (0..members.len()).map(|i| format_ident!("_{}", i)).collect();
+            let tys: Vec<_> = v.fields.iter().map(|f| &f.ty).collect();
+
+            // A single field is returned on its own, several are returned as a tuple.
+            let (val, ty_ref, ty_mut, ty) = if tys.len() == 1 {
+                let (b, t) = (&bindings[0], tys[0]);
+                (quote!(#b), quote!(&#t), quote!(&mut #t), quote!(#t))
+            } else {
+                (quote!((#( #bindings ),*)),
+                 quote!((#( &#tys ),*)),
+                 quote!((#( &mut #tys ),*)),
+                 quote!((#( #tys ),*)))
+            };
+
+            quote! {
+                pub const fn #try_as(&self) -> Option<#ty_ref> {
+                    match *self {
+                        #name::#vident { #( #members: ref #bindings ),* } => Some(#val),
+                        #[allow(unreachable_patterns)]
+                        _ => None,
+                    }
+                }
+
+                pub fn #try_as_mut(&mut self) -> Option<#ty_mut> {
+                    match *self {
+                        #name::#vident { #( #members: ref mut #bindings ),* } => Some(#val),
+                        #[allow(unreachable_patterns)]
+                        _ => None,
+                    }
+                }
+
+                pub fn #try_into(self) -> Result<#ty, Self> {
+                    match self {
+                        #name::#vident { #( #members: #bindings ),* } => Ok(#val),
+                        #[allow(unreachable_patterns)]
+                        v => Err(v),
+                    }
+                }
+            }
+        });
+
+    Ok(quote! {
+        impl #name {
+            #( #accessors )*
+        }
+    })
+}
@@ -0,0 +1,208 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+use crate::attr::{Enum, ErrorList};
+#[cfg(feature = "arbitrary")]
+use crate::iter::{checked_variants, transmute_closure, IterImpl};
+#[cfg(any(feature = "arbitrary", feature = "quickcheck"))]
+use proc_macro2::Literal;
+
+/// Returns the unskipped variants of an enum, erroring if there are none.
+fn selectable<'a>(input: &'a Enum) -> Result<Vec<&'a syn::Variant>, ErrorList> {
+    let variants: Vec<_> = input.variants.iter()
+        .filter(|(_, attrs)| !attrs.skip)
+        .map(|(v, _)| *v)
+        .collect();
+
+    if variants.is_empty() {
+        bail_list!(NoVariants, "At least one variant must not be skipped");
+    }
+
+    Ok(variants)
+}
+
+/// Returns a pattern which binds each field of `fields` to a fresh identifier, along with an
+/// expression which constructs `path` from those identifiers.
+fn bind_fields(path: TokenStream, fields: &syn::Fields) -> (TokenStream, TokenStream) {
+    let bindings: Vec<_> = (0..fields.len()).map(|i| format_ident!("f{}", i)).collect();
+    let ctor = match fields {
+        syn::Fields::Unit => path,
+        syn::Fields::Unnamed(_) => quote!(#path( #( #bindings ),* )),
+        syn::Fields::Named(named) => {
+            let names = named.named.iter().map(|f| &f.ident);
+            quote!(#path { #( #names: #bindings ),* })
+        }
+    };
+
+    (quote!(( #( #bindings, )* )), ctor)
+}
+
+#[cfg(feature = "proptest")]
+pub fn derive_proptest(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let name = input.name;
+
+    let strategies = selectable(input)?
+        .into_iter()
+        .map(|v| {
+            let vident = &v.ident;
+            if v.fields == syn::Fields::Unit {
+                return quote!(::proptest::strategy::Just(#name::#vident));
+            }
+
+            let tys = v.fields.iter().map(|f| &f.ty);
+            let (pat, ctor) = bind_fields(quote!(#name::#vident), &v.fields);
+            quote! {
+                ( #( ::proptest::arbitrary::any::<#tys>(), )* )
+                    .prop_map(|#pat| #ctor)
+            }
+        });
+
+    Ok(quote! {
+        impl ::proptest::arbitrary::Arbitrary for #name {
+            type Parameters = ();
+            type Strategy = ::proptest::strategy::BoxedStrategy<Self>;
+
+            fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+                use ::proptest::strategy::Strategy;
+
+                ::proptest::prop_oneof![
+                    #( #strategies ),*
+                ].boxed()
+            }
+        }
+    })
+}
+
+#[cfg(feature = "arbitrary")]
+pub fn derive_arbitrary(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let name = input.name;
+    let variants = selectable(input)?;
+
+    let is_fieldless = variants.iter().all(|v| v.fields == syn::Fields::Unit);
+    let imp = if is_fieldless { Some(IterImpl::for_enum(input)?) } else { None };
+    let checked = checked_variants(input, false);
+
+    let body = match imp {
+        Some(IterImpl::Range { repr, range }) => {
+            let start = Literal::i128_unsuffixed(range.start);
+            let end = Literal::i128_unsuffixed(range.end - 1);
+            let transmute = transmute_closure(&repr, checked.as_deref(), "Arbitrary");
+            quote! {
+                let start: #repr = #start;
+                let end: #repr = #end;
+                let discrim = u.int_in_range(start..=end)?;
+                Ok((#transmute)(discrim))
+            }
+        }
+
+        Some(IterImpl::RangeInclusive { repr, range }) => {
+            let start = Literal::i128_unsuffixed(*range.start());
+            let end = Literal::i128_unsuffixed(*range.end());
+            let transmute = transmute_closure(&repr, checked.as_deref(), "Arbitrary");
+            quote! {
+                let start: #repr = #start;
+                let end: #repr = #end;
+                let discrim = u.int_in_range(start..=end)?;
+                Ok((#transmute)(discrim))
+            }
+        }
+
+        _ => {
+            let last = Literal::usize_unsuffixed(variants.len() - 1);
+            let arms = variants.iter()
+                .enumerate()
+                .map(|(i, v)| {
+                    let i = Literal::usize_unsuffixed(i);
+                    let vident = &v.ident;
+                    let (pat, ctor) = bind_fields(quote!(#name::#vident), &v.fields);
+                    let fields = v.fields.iter()
+                        .map(|_| quote!(::arbitrary::Arbitrary::arbitrary(u)?));
+
+                    quote! {
+                        #i => {
+                            let #pat = ( #( #fields, )* );
+                            #ctor
+                        }
+                    }
+                });
+
+            quote! {
+                Ok(match u.int_in_range(0..=#last)? {
+                    #( #arms, )*
+                    _ => unreachable!(),
+                })
+            }
+        }
+    };
+
+    Ok(quote! {
+        impl<'a> ::arbitrary::Arbitrary<'a> for #name {
+            fn arbitrary(u: &mut ::arbitrary::Unstructured<'a>) -> ::arbitrary::Result<Self> {
+                #body
+            }
+        }
+    })
+}
+
+#[cfg(feature = "quickcheck")]
+pub fn derive_quickcheck(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let name = input.name;
+    let variants = selectable(input)?;
+
+    let indices: Vec<_> = (0..variants.len()).map(Literal::usize_unsuffixed).collect();
+    let arms = variants.iter()
+        .zip(indices.iter())
+        .map(|(v, i)| {
+            let vident = &v.ident;
+            let (pat, ctor) = bind_fields(quote!(#name::#vident), &v.fields);
+            let fields = v.fields.iter()
+                .map(|_| quote!(::quickcheck::Arbitrary::arbitrary(g)));
+
+            quote! {
+                #i => {
+                    let #pat = ( #( #fields, )* );
+                    #ctor
+                }
+            }
+        });
+
+    // Each variant shrinks to the fieldless variants declared before it.
+    let mut smaller = vec![];
+    let shrink_arms: Vec<_> = variants.iter()
+        .map(|v| {
+            let vident = &v.ident;
+            let arm = quote!(#name::#vident { .. } => vec![#( #smaller ),*]);
+            if v.fields == syn::Fields::Unit {
+                smaller.push(quote!(#name::#vident));
+            }
+
+            arm
+        })
+        .collect();
+
+    let skipped_arm = if variants.len() < input.variants.len() {
+        quote!(_ => vec![],)
+    } else {
+        TokenStream::new()
+    };
+
+    Ok(quote! {
+        impl ::quickcheck::Arbitrary for #name {
+            fn arbitrary(g: &mut ::quickcheck::Gen) -> Self {
+                match *g.choose(&[#( #indices ),*]).unwrap() {
+                    #( #arms, )*
+                    _ => unreachable!(),
+                }
+            }
+
+            fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+                let smaller: Vec<Self> = match *self {
+                    #( #shrink_arms, )*
+                    #skipped_arm
+                };
+
+                Box::new(smaller.into_iter())
+            }
+        }
+    })
+}
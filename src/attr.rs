@@ -1,8 +1,15 @@
-use std::collections::{BTreeSet, LinkedList};
+use std::cell::Cell;
+use std::collections::{BTreeMap, BTreeSet, LinkedList};
+use std::collections::btree_map::Entry;
 use std::convert::{TryFrom, TryInto};
 use std::fmt;
+use std::path::{Path, PathBuf};
 
-use failure::{bail, format_err, Fallible};
+use enum_utils_from_str::Backend;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::error::{Diagnostic, Fallible};
 
 #[derive(Debug, Clone, Copy)]
 pub enum Primitive {
@@ -52,16 +59,16 @@ impl Primitive {
         use self::Primitive::*;
 
         match self {
-            U8 => Some(u8::max_value() as u128),
-            U16 => Some(u16::max_value() as u128),
-            U32 => Some(u32::max_value() as u128),
-            U64 => Some(u64::max_value() as u128),
-            U128 => Some(u128::max_value()),
-            I8 => Some(i8::max_value() as u128),
-            I16 => Some(i16::max_value() as u128),
-            I32 => Some(i32::max_value() as u128),
-            I64 => Some(i64::max_value() as u128),
-            I128 => Some(i128::max_value() as u128),
+            U8 => Some(u8::MAX as u128),
+            U16 => Some(u16::MAX as u128),
+            U32 => Some(u32::MAX as u128),
+            U64 => Some(u64::MAX as u128),
+            U128 => Some(u128::MAX),
+            I8 => Some(i8::MAX as u128),
+            I16 => Some(i16::MAX as u128),
+            I32 => Some(i32::MAX as u128),
+            I64 => Some(i64::MAX as u128),
+            I128 => Some(i128::MAX as u128),
             Usize | Isize => None,
         }
     }
@@ -76,7 +83,10 @@ pub fn parse_primitive_repr<'a>(attrs: impl 'a + Iterator<Item = &'a syn::Attrib
             continue;
         }
 
-        let list = match attr.parse_meta()? {
+        let meta = attr.parse_meta()
+            .map_err(|e| format_diag!(MalformedAttribute, "{}", e).with_span(e.span()))?;
+
+        let list = match meta {
             syn::Meta::List(list) => list,
             _ => continue,
         };
@@ -89,7 +99,7 @@ pub fn parse_primitive_repr<'a>(attrs: impl 'a + Iterator<Item = &'a syn::Attrib
                 syn::NestedMeta::Meta(syn::Meta::Path(path)) => {
                     match path.try_into() {
                         Ok(_) if repr.is_some() =>
-                            bail!("Multiple primitive `#[repr(...)]`s"),
+                            bail_diag!(MultipleRepr, "Multiple primitive `#[repr(...)]`s"),
                         Ok(prim) => repr = Some((prim, path.clone())),
                         Err(_) => continue,
                     }
@@ -102,12 +112,118 @@ pub fn parse_primitive_repr<'a>(attrs: impl 'a + Iterator<Item = &'a syn::Attrib
     Ok(repr)
 }
 
-pub struct RenameRule(serde_derive_internals::attr::RenameRule);
+/// How the names of a variant are matched against a string.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CaseFolding {
+    /// Names must match exactly.
+    #[default]
+    None,
+
+    /// ASCII letters match regardless of case.
+    Ascii,
+
+    /// Names and strings are compared after simple Unicode case folding.
+    Unicode,
+}
+
+/// Whether names must be qualified with the name of the enum (e.g. `"Direction::North"`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Qualified {
+    /// Both `"Direction::North"` and `"North"` are accepted.
+    Optional,
+
+    /// Only `"Direction::North"` is accepted.
+    Only,
+}
+
+/// The inlining hint placed on generated functions with `#[enumeration(inline = "...")]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Inline {
+    /// `#[inline(always)]`
+    Always,
+
+    /// `#[inline(never)]`
+    Never,
+}
+
+#[derive(Clone, Copy)]
+pub struct RenameRule(Rule);
+
+#[derive(Clone, Copy)]
+enum Rule {
+    Serde(serde_derive_internals::attr::RenameRule),
+
+    /// Like the corresponding `serde` rule, except that a run of capitals is a single word.
+    Acronyms { separator: char, uppercase: bool },
+}
 
 impl RenameRule {
+    pub fn snake_case() -> Self {
+        RenameRule(Rule::Serde(serde_derive_internals::attr::RenameRule::SnakeCase))
+    }
+
     pub fn apply_to_variant(&self, s: &str) -> String {
-        self.0.apply_to_variant(s)
+        match self.0 {
+            Rule::Serde(rule) => rule.apply_to_variant(s),
+            Rule::Acronyms { separator, uppercase } => {
+                let mut ret = String::with_capacity(s.len() + 4);
+                for (i, word) in split_words(s).into_iter().enumerate() {
+                    if i > 0 {
+                        ret.push(separator);
+                    }
+
+                    if uppercase {
+                        ret.push_str(&word.to_uppercase());
+                    } else {
+                        ret.push_str(&word.to_lowercase());
+                    }
+                }
+
+                ret
+            }
+        }
+    }
+}
+
+impl std::str::FromStr for RenameRule {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let acronyms = |separator, uppercase| Ok(RenameRule(Rule::Acronyms { separator, uppercase }));
+        match s {
+            "snake_case_acronyms" => acronyms('_', false),
+            "kebab-case-acronyms" => acronyms('-', false),
+            "SCREAMING_SNAKE_CASE_ACRONYMS" => acronyms('_', true),
+            _ => s.parse().map(|rule| RenameRule(Rule::Serde(rule))).map_err(|_| ()),
+        }
+    }
+}
+
+/// Splits a `CamelCase` identifier into words, treating a run of capitals as a single word.
+///
+/// A word begins at a capital which follows a lowercase letter or a digit, or which is followed
+/// by a lowercase letter (e.g. the `R` in `HTTPResponse`). Digits belong to the preceding word.
+fn split_words(s: &str) -> Vec<&str> {
+    let chars: Vec<(usize, char)> = s.char_indices().collect();
+    let mut words = vec![];
+    let mut start = 0;
+    for (i, &(idx, c)) in chars.iter().enumerate().skip(1) {
+        let prev = chars[i - 1].1;
+        let next_is_lower = chars.get(i + 1).is_some_and(|&(_, n)| n.is_lowercase());
+        let boundary = c.is_uppercase()
+            && (prev.is_lowercase() || prev.is_numeric() || (prev.is_uppercase() && next_is_lower));
+
+        if boundary {
+            words.push(&s[start..idx]);
+            start = idx;
+        }
     }
+
+    if start < s.len() {
+        words.push(&s[start..]);
+    }
+
+    words
 }
 
 impl fmt::Debug for RenameRule {
@@ -117,13 +233,13 @@ impl fmt::Debug for RenameRule {
     }
 }
 
-pub type ErrorList = LinkedList<failure::Error>;
+pub type ErrorList = LinkedList<Diagnostic>;
 
 macro_rules! bail_list {
-    ($msg:literal $( , $args:expr )* $(,)?) => {
+    ($code:ident, $msg:literal $( , $args:expr )* $(,)?) => {
         {
             let mut list = ErrorList::new();
-            list.push_back(failure::format_err!($msg, $($args),*));
+            list.push_back(format_diag!($code, $msg, $($args),*));
             return Err(list);
         }
     }
@@ -131,32 +247,116 @@ macro_rules! bail_list {
 
 #[derive(Debug)]
 pub enum Attr {
-    CaseInsensitive,
+    CaseInsensitive(CaseFolding),
+    CaseSensitive,
+    DebugChecks,
+    FromBytes,
+    TryFromStr,
+    CanonicalName,
+    FromName,
+    ParseDetailed,
+    ParseError,
+    Trim,
+    AssumeLowercase,
+    FromPrefix,
+    NumericAliases,
+    NoPanic,
+    Qualified { only: bool },
+    DashUnderscoreInsensitive,
+    Default,
+    Other,
+    ParseTail,
+    NoPrefix,
+    NoSuffix,
     Skip,
+    SkipFor(Vec<String>),
+    Source,
+    TrueVariant,
+    FalseVariant,
+    SqlRepr,
+    Traits,
+    Wrapping,
     Rename(String),
+    Display(String),
     RenameAll(RenameRule),
+    RenameAllSplit { serialize: Option<RenameRule>, deserialize: Option<RenameRule> },
+    AliasAll(RenameRule),
     Alias(String),
+    DeprecatedAlias(String),
+    Deprecated,
+    AliasBytes(Vec<u8>),
+    Message(String),
+    Separator(String),
+    Format(String),
+    Props(Vec<(String, String)>),
+    Locale(Vec<(String, String)>),
+    Weight(u64),
+    Order(u64),
+    Value(syn::Lit),
+    Char(char),
+    OnMiss(syn::Path),
+    AliasesFile(syn::LitStr),
+    Parser(Backend),
+    Inline(Inline),
+    From(syn::Path),
+    TryFrom(syn::Path),
+    FromVariant(syn::Ident),
+    AssertDiscriminants(Vec<(syn::Ident, syn::LitInt)>),
+    KindName(syn::Ident),
+    ParseFn(syn::Visibility, syn::Ident),
+    FromCStrPtr(syn::Visibility, syn::Ident),
+    FromCStr,
+    PgName(String),
+    Expecting(String),
+    Prefix(String),
+    Suffix(String),
+    KindDerive(Vec<syn::Path>),
+    Derive(Vec<syn::Ident>),
 }
 
 impl Attr {
     pub fn parse_attrs(attr: &syn::Attribute) -> impl Iterator<Item = Fallible<Self>> {
         use syn::NestedMeta;
 
-        Self::get_args(attr)
+        let mut attrs: Vec<_> = Self::get_args(attr)
             .map(|arg| {
                 match arg {
                     NestedMeta::Meta(m) => Attr::try_from(&m),
-                    _ => bail!("Argument to attribute cannot be a literal"),
+                    _ => bail_diag!(MalformedAttribute, "Argument to attribute cannot be a literal"),
                 }
             })
+            .collect();
+
+        // `deprecated` applies to the aliases given alongside it, so it must be resolved before the
+        // arguments of different attributes are mixed together.
+        let len = attrs.len();
+        attrs.retain(|attr| !matches!(attr, Ok(Attr::Deprecated)));
+        if attrs.len() < len {
+            let mut any = false;
+            for attr in &mut attrs {
+                if let Ok(Attr::Alias(s)) = attr {
+                    *attr = Ok(Attr::DeprecatedAlias(std::mem::take(s)));
+                    any = true;
+                }
+            }
+
+            if !any {
+                attrs.push(Err(format_diag!(MisplacedAttribute,
+                                            "\"deprecated\" must be given in the same attribute as an \"alias\"")));
+            }
+        }
+
+        attrs.into_iter()
     }
 
     /// Returns an iterator over the items in `...` if this attribute looks like `#[enumeration(...)]`
     fn get_args(attr: &syn::Attribute) -> impl Iterator<Item = syn::NestedMeta> {
         use syn::{token, Meta, MetaList, NestedMeta};
 
-        if let Ok(Meta::List(MetaList { path, nested, .. })) = attr.parse_meta() {
-            if path.is_ident("enumeration") {
+        // Check the path before calling `parse_meta`, since most attributes (e.g. doc comments)
+        // are not ours and parsing them is wasted work on large enums.
+        if attr.path.is_ident("enumeration") {
+            if let Ok(Meta::List(MetaList { nested, .. })) = attr.parse_meta() {
                 return nested.into_iter();
             }
         }
@@ -167,16 +367,17 @@ impl Attr {
 
 /// Parse an attr from the `syn::Meta` inside parens after "enumeration".
 impl TryFrom<&'_ syn::Meta> for Attr {
-    type Error = failure::Error;
+    type Error = Diagnostic;
 
     fn try_from(meta: &syn::Meta) -> Result<Self, Self::Error> {
-        use syn::{Lit, Meta, MetaNameValue};
+        use syn::{Lit, Meta, MetaNameValue, NestedMeta};
+        use syn::spanned::Spanned;
 
         // Extracts a string literal from a MetaNameValue
         let lit_val = |lit: &syn::Lit| {
             match lit {
                 Lit::Str(v) => Ok(v.value()),
-                _ => bail!("Non-string literal"),
+                _ => Err(format_diag!(MalformedAttribute, "Non-string literal").with_span(lit.span())),
             }
         };
 
@@ -185,34 +386,533 @@ impl TryFrom<&'_ syn::Meta> for Attr {
             Meta::Path(path) if path.is_ident("skip") =>
                 Ok(Attr::Skip),
 
+            // #[enumeration(skip(FromStr, Display, ...))]
+            Meta::List(list) if list.path.is_ident("skip") => {
+                let mut derives = vec![];
+                for arg in &list.nested {
+                    let ident = match arg {
+                        NestedMeta::Meta(Meta::Path(path)) => path.get_ident(),
+                        _ => None,
+                    };
+
+                    match ident {
+                        Some(ident) if ident == "Default" || crate::discriminants::DERIVES.iter().any(|d| ident == d) =>
+                            derives.push(ident.to_string()),
+
+                        Some(ident) => return Err(format_diag!(UnknownAttribute, "`{}` is not a derive from this crate", ident)
+                            .with_span(ident.span())),
+
+                        None => return Err(format_diag!(MalformedAttribute, "Arguments to \"skip\" must be the names of derives")
+                            .with_span(arg.span())),
+                    }
+                }
+
+                Ok(Attr::SkipFor(derives))
+            }
+
             // #[enumeration(case_insensitive)]
             Meta::Path(path) if path.is_ident("case_insensitive") =>
-                Ok(Attr::CaseInsensitive),
+                Ok(Attr::CaseInsensitive(CaseFolding::Ascii)),
+
+            // #[enumeration(case_insensitive = "unicode")]
+            Meta::NameValue(MetaNameValue { path, lit, .. }) if path.is_ident("case_insensitive") => {
+                match lit_val(lit)?.as_str() {
+                    "ascii" => Ok(Attr::CaseInsensitive(CaseFolding::Ascii)),
+                    "unicode" => Ok(Attr::CaseInsensitive(CaseFolding::Unicode)),
+                    _ => Err(format_diag!(MalformedAttribute, "\"case_insensitive\" must be \"ascii\" or \"unicode\"")
+                        .with_span(lit.span())),
+                }
+            }
+
+            // #[enumeration(case_sensitive)]
+            Meta::Path(path) if path.is_ident("case_sensitive") =>
+                Ok(Attr::CaseSensitive),
+
+            // #[enumeration(debug_checks)]
+            Meta::Path(path) if path.is_ident("debug_checks") =>
+                Ok(Attr::DebugChecks),
+
+            // #[enumeration(from_bytes)]
+            Meta::Path(path) if path.is_ident("from_bytes") =>
+                Ok(Attr::FromBytes),
+
+            // #[enumeration(try_from_str)]
+            Meta::Path(path) if path.is_ident("try_from_str") =>
+                Ok(Attr::TryFromStr),
+
+            // #[enumeration(canonical_name)]
+            Meta::Path(path) if path.is_ident("canonical_name") =>
+                Ok(Attr::CanonicalName),
+
+            // #[enumeration(from_name)]
+            Meta::Path(path) if path.is_ident("from_name") =>
+                Ok(Attr::FromName),
+
+            // #[enumeration(parse_detailed)]
+            Meta::Path(path) if path.is_ident("parse_detailed") =>
+                Ok(Attr::ParseDetailed),
+
+            // #[enumeration(dash_underscore_insensitive)]
+            Meta::Path(path) if path.is_ident("dash_underscore_insensitive") =>
+                Ok(Attr::DashUnderscoreInsensitive),
+
+            // #[enumeration(numeric_aliases)]
+            Meta::Path(path) if path.is_ident("numeric_aliases") =>
+                Ok(Attr::NumericAliases),
+
+            // #[enumeration(no_panic)]
+            Meta::Path(path) if path.is_ident("no_panic") =>
+                Ok(Attr::NoPanic),
+
+            // #[enumeration(qualified)]
+            Meta::Path(path) if path.is_ident("qualified") =>
+                Ok(Attr::Qualified { only: false }),
+
+            // #[enumeration(qualified = "only")]
+            Meta::NameValue(MetaNameValue { path, lit, .. }) if path.is_ident("qualified") => {
+                match lit_val(lit)?.as_str() {
+                    "only" => Ok(Attr::Qualified { only: true }),
+                    _ => Err(format_diag!(MalformedAttribute, "\"qualified\" must be \"only\"")
+                        .with_span(lit.span())),
+                }
+            }
+
+            // #[enumeration(from_prefix)]
+            Meta::Path(path) if path.is_ident("from_prefix") =>
+                Ok(Attr::FromPrefix),
+
+            // #[enumeration(assume_lowercase)]
+            Meta::Path(path) if path.is_ident("assume_lowercase") =>
+                Ok(Attr::AssumeLowercase),
+
+            // #[enumeration(trim)]
+            Meta::Path(path) if path.is_ident("trim") =>
+                Ok(Attr::Trim),
+
+            // #[enumeration(parse_error)]
+            Meta::Path(path) if path.is_ident("parse_error") =>
+                Ok(Attr::ParseError),
+
+            // #[enumeration(sql_repr)]
+            Meta::Path(path) if path.is_ident("sql_repr") =>
+                Ok(Attr::SqlRepr),
+
+            // #[enumeration(traits)]
+            Meta::Path(path) if path.is_ident("traits") =>
+                Ok(Attr::Traits),
+
+            // #[enumeration(wrapping)]
+            Meta::Path(path) if path.is_ident("wrapping") =>
+                Ok(Attr::Wrapping),
+
+            // #[enumeration(default)]
+            Meta::Path(path) if path.is_ident("default") =>
+                Ok(Attr::Default),
+
+            // #[enumeration(deprecated)]
+            Meta::Path(path) if path.is_ident("deprecated") =>
+                Ok(Attr::Deprecated),
+
+            // #[enumeration(other)]
+            Meta::Path(path) if path.is_ident("other") =>
+                Ok(Attr::Other),
+
+            // #[enumeration(parse_tail)]
+            Meta::Path(path) if path.is_ident("parse_tail") =>
+                Ok(Attr::ParseTail),
+
+            // #[enumeration(true_variant)]
+            Meta::Path(path) if path.is_ident("true_variant") =>
+                Ok(Attr::TrueVariant),
+
+            // #[enumeration(false_variant)]
+            Meta::Path(path) if path.is_ident("false_variant") =>
+                Ok(Attr::FalseVariant),
+
+            // #[enumeration(source)]
+            Meta::Path(path) if path.is_ident("source") =>
+                Ok(Attr::Source),
+
+            // #[enumeration(format = "...")]
+            Meta::NameValue(MetaNameValue { path, lit, .. }) if path.is_ident("format") =>
+                Ok(Attr::Format(lit_val(lit)?)),
+
+            // #[enumeration(separator = "...")]
+            Meta::NameValue(MetaNameValue { path, lit, .. }) if path.is_ident("separator") =>
+                Ok(Attr::Separator(lit_val(lit)?)),
 
             // #[enumeration(rename = "...")]
             Meta::NameValue(MetaNameValue { path, lit, .. }) if path.is_ident("rename") =>
                 Ok(Attr::Rename(lit_val(lit)?)),
 
+            // #[enumeration(display = "...")]
+            Meta::NameValue(MetaNameValue { path, lit, .. }) if path.is_ident("display") =>
+                Ok(Attr::Display(lit_val(lit)?)),
+
             // #[enumeration(rename_all = "...")]
             Meta::NameValue(MetaNameValue { path, lit, .. }) if path.is_ident("rename_all") => {
-                let rule = lit_val(lit)?.parse().map_err(|_| format_err!("Invalid RenameAll rule"))?;
-                Ok(Attr::RenameAll(RenameRule(rule)))
+                let rule = lit_val(lit)?.parse()
+                    .map_err(|_| format_diag!(InvalidRenameRule, "Invalid RenameAll rule").with_span(lit.span()))?;
+                Ok(Attr::RenameAll(rule))
+            }
+
+            // #[enumeration(rename_all(serialize = "...", deserialize = "..."))]
+            Meta::List(list) if list.path.is_ident("rename_all") => {
+                let (mut serialize, mut deserialize) = (None, None);
+                for arg in &list.nested {
+                    let (path, lit) = match arg {
+                        NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit, .. })) => (path, lit),
+                        _ => return Err(format_diag!(MalformedAttribute,
+                                                     "Arguments to \"rename_all\" must look like `serialize = \"...\"`")
+                                            .with_span(arg.span())),
+                    };
+
+                    let slot = if path.is_ident("serialize") {
+                        &mut serialize
+                    } else if path.is_ident("deserialize") {
+                        &mut deserialize
+                    } else {
+                        return Err(format_diag!(MalformedAttribute,
+                                                "Expected \"serialize\" or \"deserialize\"")
+                                       .with_span(path.span()));
+                    };
+
+                    let rule = lit_val(lit)?.parse()
+                        .map_err(|_| format_diag!(InvalidRenameRule, "Invalid RenameAll rule").with_span(lit.span()))?;
+                    if slot.replace(rule).is_some() {
+                        return Err(format_diag!(DuplicateAttribute, "Rename rule specified more than once")
+                                       .with_span(path.span()));
+                    }
+                }
+
+                if serialize.is_none() && deserialize.is_none() {
+                    return Err(format_diag!(MalformedAttribute, "\"rename_all\" requires a rename rule")
+                                   .with_span(list.span()));
+                }
+
+                Ok(Attr::RenameAllSplit { serialize, deserialize })
+            }
+
+            // #[enumeration(alias_all = "...")]
+            Meta::NameValue(MetaNameValue { path, lit, .. }) if path.is_ident("alias_all") => {
+                let rule = lit_val(lit)?.parse()
+                    .map_err(|_| format_diag!(InvalidRenameRule, "Invalid AliasAll rule").with_span(lit.span()))?;
+                Ok(Attr::AliasAll(rule))
             }
 
             // #[enumeration(alias = "...")]
             Meta::NameValue(MetaNameValue { path, lit, .. }) if path.is_ident("alias") =>
                 Ok(Attr::Alias(lit_val(lit)?)),
 
-            _ => bail!("Unknown attribute argument")
+            // #[enumeration(alias_bytes = b"...")]
+            Meta::NameValue(MetaNameValue { path, lit, .. }) if path.is_ident("alias_bytes") => {
+                match lit {
+                    Lit::ByteStr(v) => Ok(Attr::AliasBytes(v.value())),
+                    _ => Err(format_diag!(MalformedAttribute, "\"alias_bytes\" must be a byte string literal")
+                        .with_span(lit.span())),
+                }
+            }
+
+            // #[enumeration(message = "...")]
+            Meta::NameValue(MetaNameValue { path, lit, .. }) if path.is_ident("message") =>
+                Ok(Attr::Message(lit_val(lit)?)),
+
+            // #[enumeration(on_miss = "...")]
+            Meta::NameValue(MetaNameValue { path, lit, .. }) if path.is_ident("on_miss") => {
+                let callback = syn::parse_str(&lit_val(lit)?)
+                    .map_err(|e| format_diag!(MalformedAttribute, "Invalid \"on_miss\" path: {}", e)
+                        .with_span(lit.span()))?;
+                Ok(Attr::OnMiss(callback))
+            }
+
+            // #[enumeration(aliases_file = "...")]
+            Meta::NameValue(MetaNameValue { path, lit, .. }) if path.is_ident("aliases_file") => {
+                match lit {
+                    Lit::Str(file) => Ok(Attr::AliasesFile(file.clone())),
+                    _ => Err(format_diag!(MalformedAttribute, "Non-string literal").with_span(lit.span())),
+                }
+            }
+
+            // #[enumeration(parser = "...")]
+            Meta::NameValue(MetaNameValue { path, lit, .. }) if path.is_ident("parser") => {
+                match lit_val(lit)?.as_str() {
+                    "trie" => Ok(Attr::Parser(Backend::Trie)),
+                    "match" => Ok(Attr::Parser(Backend::Match)),
+                    "table" => Ok(Attr::Parser(Backend::Table)),
+                    _ => Err(format_diag!(MalformedAttribute, "\"parser\" must be \"trie\", \"match\" or \"table\"")
+                        .with_span(lit.span())),
+                }
+            }
+
+            // #[enumeration(inline = "...")]
+            Meta::NameValue(MetaNameValue { path, lit, .. }) if path.is_ident("inline") => {
+                match lit_val(lit)?.as_str() {
+                    "always" => Ok(Attr::Inline(Inline::Always)),
+                    "never" => Ok(Attr::Inline(Inline::Never)),
+                    _ => Err(format_diag!(MalformedAttribute, "\"inline\" must be \"always\" or \"never\"")
+                        .with_span(lit.span())),
+                }
+            }
+
+            // #[enumeration(from = "...")]
+            Meta::NameValue(MetaNameValue { path, lit, .. }) if path.is_ident("from") => {
+                let source = syn::parse_str(&lit_val(lit)?)
+                    .map_err(|e| format_diag!(MalformedAttribute, "Invalid \"from\" path: {}", e)
+                        .with_span(lit.span()))?;
+                Ok(Attr::From(source))
+            }
+
+            // #[enumeration(try_from = "...")]
+            Meta::NameValue(MetaNameValue { path, lit, .. }) if path.is_ident("try_from") => {
+                let source = syn::parse_str(&lit_val(lit)?)
+                    .map_err(|e| format_diag!(MalformedAttribute, "Invalid \"try_from\" path: {}", e)
+                        .with_span(lit.span()))?;
+                Ok(Attr::TryFrom(source))
+            }
+
+            // #[enumeration(from_variant = "...")]
+            Meta::NameValue(MetaNameValue { path, lit, .. }) if path.is_ident("from_variant") => {
+                let ident = syn::parse_str(&lit_val(lit)?)
+                    .map_err(|e| format_diag!(MalformedAttribute, "Invalid \"from_variant\": {}", e)
+                        .with_span(lit.span()))?;
+                Ok(Attr::FromVariant(ident))
+            }
+
+            // #[enumeration(prefix = "...")]
+            Meta::NameValue(MetaNameValue { path, lit, .. }) if path.is_ident("prefix") =>
+                Ok(Attr::Prefix(lit_val(lit)?)),
+
+            // #[enumeration(no_prefix)]
+            Meta::Path(path) if path.is_ident("no_prefix") =>
+                Ok(Attr::NoPrefix),
+
+            // #[enumeration(suffix = "...")]
+            Meta::NameValue(MetaNameValue { path, lit, .. }) if path.is_ident("suffix") =>
+                Ok(Attr::Suffix(lit_val(lit)?)),
+
+            // #[enumeration(no_suffix)]
+            Meta::Path(path) if path.is_ident("no_suffix") =>
+                Ok(Attr::NoSuffix),
+
+            // #[enumeration(expecting = "...")]
+            Meta::NameValue(MetaNameValue { path, lit, .. }) if path.is_ident("expecting") =>
+                Ok(Attr::Expecting(lit_val(lit)?)),
+
+            // #[enumeration(pg_name = "...")]
+            Meta::NameValue(MetaNameValue { path, lit, .. }) if path.is_ident("pg_name") =>
+                Ok(Attr::PgName(lit_val(lit)?)),
+
+            // #[enumeration(kind_name = "...")]
+            Meta::NameValue(MetaNameValue { path, lit, .. }) if path.is_ident("kind_name") => {
+                let ident = syn::parse_str(&lit_val(lit)?)
+                    .map_err(|e| format_diag!(MalformedAttribute, "Invalid \"kind_name\": {}", e)
+                        .with_span(lit.span()))?;
+                Ok(Attr::KindName(ident))
+            }
+
+            // #[enumeration(parse_fn = "pub parse_foo")]
+            Meta::NameValue(MetaNameValue { path, lit, .. }) if path.is_ident("parse_fn") => {
+                let parser = |input: syn::parse::ParseStream| Ok((input.parse()?, input.parse()?));
+                let (vis, ident) = syn::parse::Parser::parse_str(parser, &lit_val(lit)?)
+                    .map_err(|e| format_diag!(MalformedAttribute, "Invalid \"parse_fn\": {}", e)
+                        .with_span(lit.span()))?;
+                Ok(Attr::ParseFn(vis, ident))
+            }
+
+            // #[enumeration(from_cstr)]
+            Meta::Path(path) if path.is_ident("from_cstr") =>
+                Ok(Attr::FromCStr),
+
+            // #[enumeration(from_cstr_ptr = "pub from_ptr")]
+            Meta::NameValue(MetaNameValue { path, lit, .. }) if path.is_ident("from_cstr_ptr") => {
+                let parser = |input: syn::parse::ParseStream| Ok((input.parse()?, input.parse()?));
+                let (vis, ident) = syn::parse::Parser::parse_str(parser, &lit_val(lit)?)
+                    .map_err(|e| format_diag!(MalformedAttribute, "Invalid \"from_cstr_ptr\": {}", e)
+                        .with_span(lit.span()))?;
+                Ok(Attr::FromCStrPtr(vis, ident))
+            }
+
+            // #[enumeration(kind_derive(Debug, Clone, ...))]
+            Meta::List(list) if list.path.is_ident("kind_derive") => {
+                let mut paths = vec![];
+                for arg in &list.nested {
+                    match arg {
+                        NestedMeta::Meta(Meta::Path(path)) => paths.push(path.clone()),
+                        _ => return Err(format_diag!(MalformedAttribute,
+                                                     "Arguments to \"kind_derive\" must be paths")
+                                            .with_span(arg.span())),
+                    }
+                }
+
+                Ok(Attr::KindDerive(paths))
+            }
+
+            // #[enumeration(derive(FromStr, Display, ...))]
+            Meta::List(list) if list.path.is_ident("derive") => {
+                let mut idents = vec![];
+                for arg in &list.nested {
+                    match arg {
+                        NestedMeta::Meta(Meta::Path(path)) if path.get_ident().is_some() =>
+                            idents.extend(path.get_ident().cloned()),
+                        _ => return Err(format_diag!(MalformedAttribute,
+                                                     "Arguments to \"derive\" must be the names of derives")
+                                            .with_span(arg.span())),
+                    }
+                }
+
+                Ok(Attr::Derive(idents))
+            }
+
+            // #[enumeration(weight = N)]
+            Meta::NameValue(MetaNameValue { path, lit, .. }) if path.is_ident("weight") => {
+                let weight = match lit {
+                    Lit::Int(i) => i.base10_parse().ok(),
+                    _ => None,
+                };
+
+                weight.map(Attr::Weight)
+                    .ok_or_else(|| format_diag!(MalformedAttribute, "\"weight\" must be a non-negative integer")
+                        .with_span(lit.span()))
+            }
+
+            // #[enumeration(order = N)]
+            Meta::NameValue(MetaNameValue { path, lit, .. }) if path.is_ident("order") => {
+                let order = match lit {
+                    Lit::Int(i) => i.base10_parse().ok(),
+                    _ => None,
+                };
+
+                order.map(Attr::Order)
+                    .ok_or_else(|| format_diag!(MalformedAttribute, "\"order\" must be a non-negative integer")
+                        .with_span(lit.span()))
+            }
+
+            // #[enumeration(value = 404u16)]
+            Meta::NameValue(MetaNameValue { path, lit, .. }) if path.is_ident("value") =>
+                Ok(Attr::Value(lit.clone())),
+
+            // #[enumeration(char = 'A')]
+            Meta::NameValue(MetaNameValue { path, lit, .. }) if path.is_ident("char") => match lit {
+                Lit::Char(c) => Ok(Attr::Char(c.value())),
+                _ => Err(format_diag!(MalformedAttribute, "\"char\" must be a character literal")
+                    .with_span(lit.span())),
+            }
+
+            // #[enumeration(props(key = "value", ...))]
+            Meta::List(list) if list.path.is_ident("props") => {
+                let mut props = vec![];
+                for arg in &list.nested {
+                    match arg {
+                        NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit: Lit::Str(s), .. })) => {
+                            let key = path.get_ident()
+                                .ok_or_else(|| format_diag!(MalformedAttribute, "Expected a property name")
+                                    .with_span(path.span()))?;
+                            props.push((key.to_string(), s.value()));
+                        }
+
+                        _ => return Err(format_diag!(MalformedAttribute,
+                                                     "Arguments to \"props\" must look like `key = \"value\"`")
+                                            .with_span(arg.span())),
+                    }
+                }
+
+                Ok(Attr::Props(props))
+            }
+
+            // #[enumeration(locale(en = "...", de = "...", ...))]
+            Meta::List(list) if list.path.is_ident("locale") => {
+                let mut names = vec![];
+                for arg in &list.nested {
+                    match arg {
+                        NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit: Lit::Str(s), .. })) => {
+                            let locale = path.get_ident()
+                                .ok_or_else(|| format_diag!(MalformedAttribute, "Expected a locale")
+                                    .with_span(path.span()))?;
+                            names.push((locale.to_string(), s.value()));
+                        }
+
+                        _ => return Err(format_diag!(MalformedAttribute,
+                                                     "Arguments to \"locale\" must look like `en = \"name\"`")
+                                            .with_span(arg.span())),
+                    }
+                }
+
+                Ok(Attr::Locale(names))
+            }
+
+            // #[enumeration(assert_discriminants(A = 1, B = 2, ...))]
+            Meta::List(list) if list.path.is_ident("assert_discriminants") => {
+                let mut pairs = vec![];
+                for arg in &list.nested {
+                    match arg {
+                        NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit: Lit::Int(i), .. })) => {
+                            let ident = path.get_ident()
+                                .ok_or_else(|| format_diag!(MalformedAttribute, "Expected a variant name")
+                                    .with_span(path.span()))?;
+                            pairs.push((ident.clone(), i.clone()));
+                        }
+
+                        _ => return Err(format_diag!(MalformedAttribute,
+                                                     "Arguments to \"assert_discriminants\" must look like `Variant = 1`")
+                                            .with_span(arg.span())),
+                    }
+                }
+
+                Ok(Attr::AssertDiscriminants(pairs))
+            }
+
+            _ => Err(format_diag!(UnknownAttribute, "Unknown attribute argument").with_span(meta.span()))
         }
     }
 }
 
 #[derive(Debug, Default)]
 pub struct VariantAttrs {
+    /// Whether the variant is skipped by the derive being expanded. See `Enum::for_derive`.
     pub skip: bool,
+
+    /// Whether the variant is skipped by every derive, regardless of `skip_for`.
+    skip_all: bool,
+
+    /// The derives named by `skip(...)`, which skip the variant in addition to a plain `skip`.
+    pub skip_for: BTreeSet<String>,
+
+    /// Overrides the enum-wide `case_insensitive` setting for this variant's names.
+    pub case: Option<CaseFolding>,
+    pub default: bool,
+    pub other: bool,
     pub rename: Option<String>,
+
+    /// The name which is written out instead of the canonical one.
+    pub display: Option<String>,
     pub aliases: BTreeSet<String>,
+
+    /// The subset of `aliases` marked with `deprecated`.
+    pub deprecated_aliases: BTreeSet<String>,
+
+    /// Aliases given with `alias_bytes` which are not valid UTF-8.
+    pub alias_bytes: BTreeSet<Vec<u8>>,
+    pub message: Option<String>,
+    pub props: BTreeMap<String, String>,
+
+    /// The name of the variant in each locale given with `locale(...)`.
+    pub locales: BTreeMap<String, String>,
+    pub weight: Option<u64>,
+    pub order: Option<u64>,
+    pub value: Option<syn::Lit>,
+    pub char: Option<char>,
+    pub from_variant: Option<syn::Ident>,
+    pub parse_tail: bool,
+    pub no_prefix: bool,
+    pub no_suffix: bool,
+    pub separator: Option<String>,
+    pub format: Option<String>,
+
+    /// The `bool` this variant corresponds to, set by `true_variant` or `false_variant`.
+    pub bool_value: Option<bool>,
+
+    /// The field marked with `#[enumeration(source)]`, if any.
+    pub source: Option<syn::Member>,
 }
 
 impl VariantAttrs {
@@ -223,25 +923,150 @@ impl VariantAttrs {
         let mut errors = ErrorList::default();
         for attr in attrs {
             match attr {
-                Ok(Attr::Skip) => ret.skip = true,
+                Ok(Attr::Skip) => {
+                    ret.skip = true;
+                    ret.skip_all = true;
+                }
+
+                Ok(Attr::SkipFor(derives)) => ret.skip_for.extend(derives),
+                Ok(Attr::Default) => ret.default = true,
+
+                // The catch-all variant has no name of its own, so it is implicitly skipped.
+                Ok(Attr::Other) => {
+                    ret.other = true;
+                    ret.skip = true;
+                    ret.skip_all = true;
+                }
+
+                Ok(Attr::ParseTail) => ret.parse_tail = true,
+                Ok(Attr::NoPrefix) => ret.no_prefix = true,
+                Ok(Attr::NoSuffix) => ret.no_suffix = true,
+
+                Ok(Attr::CaseInsensitive(case)) => if ret.case.is_none() {
+                    ret.case = Some(case);
+                } else {
+                    errors.push_back(format_diag!(DuplicateAttribute, "Variant cannot have multiple \"case_insensitive\" or \"case_sensitive\" attributes"));
+                },
+
+                Ok(Attr::CaseSensitive) => if ret.case.is_none() {
+                    ret.case = Some(CaseFolding::None);
+                } else {
+                    errors.push_back(format_diag!(DuplicateAttribute, "Variant cannot have multiple \"case_insensitive\" or \"case_sensitive\" attributes"));
+                },
+
+                Ok(attr @ Attr::TrueVariant) | Ok(attr @ Attr::FalseVariant) => if ret.bool_value.is_none() {
+                    ret.bool_value = Some(matches!(attr, Attr::TrueVariant));
+                } else {
+                    errors.push_back(format_diag!(DuplicateAttribute, "Variant cannot have multiple \"true_variant\" or \"false_variant\" attributes"));
+                },
+
+                Ok(Attr::Separator(s)) => if ret.separator.is_none() {
+                    ret.separator = Some(s);
+                } else {
+                    errors.push_back(format_diag!(DuplicateAttribute, "Variant cannot have multiple separators"));
+                },
+
+                Ok(Attr::Format(s)) => if ret.format.is_none() {
+                    ret.format = Some(s);
+                } else {
+                    errors.push_back(format_diag!(DuplicateAttribute, "Variant cannot have multiple formats"));
+                },
 
                 Ok(Attr::Rename(s)) => if ret.rename.is_none() {
                     ret.rename = Some(s);
                 } else {
-                    errors.push_back(format_err!("Variant cannot be renamed multiple times"));
+                    errors.push_back(format_diag!(DuplicateAttribute, "Variant cannot be renamed multiple times"));
+                },
+
+                Ok(Attr::Display(s)) => if ret.display.is_none() {
+                    ret.display = Some(s);
+                } else {
+                    errors.push_back(format_diag!(DuplicateAttribute, "Variant can only have a single \"display\" attribute"));
                 },
 
                 Ok(Attr::Alias(s)) => {
                     ret.aliases.insert(s);
                 },
 
+                Ok(Attr::DeprecatedAlias(s)) => {
+                    ret.aliases.insert(s.clone());
+                    ret.deprecated_aliases.insert(s);
+                },
+
+                // Byte strings which are valid UTF-8 are no different from a string alias.
+                Ok(Attr::AliasBytes(b)) => match String::from_utf8(b) {
+                    Ok(s) => {
+                        ret.aliases.insert(s);
+                    }
+
+                    Err(e) => {
+                        ret.alias_bytes.insert(e.into_bytes());
+                    }
+                },
+
+                Ok(Attr::Message(s)) => if ret.message.is_none() {
+                    ret.message = Some(s);
+                } else {
+                    errors.push_back(format_diag!(DuplicateAttribute, "Variant cannot have multiple messages"));
+                },
+
+                Ok(Attr::Weight(w)) => if ret.weight.is_none() {
+                    ret.weight = Some(w);
+                } else {
+                    errors.push_back(format_diag!(DuplicateAttribute, "Variant cannot have multiple weights"));
+                },
+
+                Ok(Attr::Order(n)) => if ret.order.is_none() {
+                    ret.order = Some(n);
+                } else {
+                    errors.push_back(format_diag!(DuplicateAttribute, "Variant cannot have multiple orders"));
+                },
+
+                Ok(Attr::Value(lit)) => if ret.value.is_none() {
+                    ret.value = Some(lit);
+                } else {
+                    errors.push_back(format_diag!(DuplicateAttribute, "Variant cannot have multiple values"));
+                },
+
+                Ok(Attr::Char(c)) => if ret.char.is_none() {
+                    ret.char = Some(c);
+                } else {
+                    errors.push_back(format_diag!(DuplicateAttribute, "Variant cannot have multiple characters"));
+                },
+
+                Ok(Attr::FromVariant(ident)) => if ret.from_variant.is_none() {
+                    ret.from_variant = Some(ident);
+                } else {
+                    errors.push_back(format_diag!(DuplicateAttribute, "Variant cannot have multiple \"from_variant\" attributes"));
+                },
+
+                Ok(Attr::Props(props)) => for (key, value) in props {
+                    match ret.props.entry(key) {
+                        Entry::Vacant(e) => { e.insert(value); }
+                        Entry::Occupied(e) =>
+                            errors.push_back(format_diag!(DuplicateAttribute, "Property \"{}\" is specified multiple times", e.key())),
+                    }
+                },
+
+                Ok(Attr::Locale(names)) => for (locale, name) in names {
+                    match ret.locales.entry(locale) {
+                        Entry::Vacant(e) => { e.insert(name); }
+                        Entry::Occupied(e) =>
+                            errors.push_back(format_diag!(DuplicateAttribute, "Locale \"{}\" is specified multiple times", e.key())),
+                    }
+                },
+
                 Ok(attr) =>
-                    errors.push_back(format_err!("Attribute \"{:?}\" is not valid for a variant", attr)),
+                    errors.push_back(format_diag!(MisplacedAttribute, "Attribute \"{:?}\" is not valid for a variant", attr)),
 
                 Err(e) => errors.push_back(e),
             }
         }
 
+        if ret.separator.is_some() && !ret.parse_tail {
+            errors.push_back(format_diag!(MisplacedAttribute, "\"separator\" is only valid with \"parse_tail\""));
+        }
+
         if errors.is_empty() {
             Ok(ret)
         } else {
@@ -250,13 +1075,177 @@ impl VariantAttrs {
     }
 }
 
+/// Returns the field of a variant marked with `#[enumeration(source)]`, if any.
+///
+/// `source` is the only attribute which is valid on a field.
+fn source_field(v: &syn::Variant) -> Result<Option<syn::Member>, ErrorList> {
+    let mut errors = ErrorList::default();
+    let mut source = None;
+    for (i, field) in v.fields.iter().enumerate() {
+        for attr in field.attrs.iter().flat_map(Attr::parse_attrs) {
+            match attr {
+                Ok(Attr::Source) => if source.is_none() {
+                    source = Some(match &field.ident {
+                        Some(ident) => syn::Member::Named(ident.clone()),
+                        None => syn::Member::Unnamed(i.into()),
+                    });
+                } else {
+                    errors.push_back(format_diag!(DuplicateAttribute, "Variant cannot have multiple \"source\" fields")
+                        .with_span(v.ident.span()));
+                },
+
+                Ok(attr) =>
+                    errors.push_back(format_diag!(MisplacedAttribute, "Attribute \"{:?}\" is not valid for a field", attr)),
+
+                Err(e) => errors.push_back(e),
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(source)
+    } else {
+        Err(errors)
+    }
+}
+
 #[derive(Default)]
 pub struct EnumAttrs {
-    pub nocase: bool,
+    pub case: CaseFolding,
+    pub debug_checks: bool,
+    pub wrapping: bool,
+    pub sql_repr: bool,
+    pub from_bytes: bool,
+    pub from_cstr: bool,
+    pub try_from_str: bool,
+    pub canonical_name: bool,
+    pub from_name: bool,
+    pub parse_detailed: bool,
+    pub parse_error: bool,
+    pub trim: bool,
+    pub assume_lowercase: bool,
+    pub from_prefix: bool,
+    pub numeric_aliases: bool,
+    pub no_panic: bool,
+    pub qualified: Option<Qualified>,
+    pub dash_underscore: bool,
+    pub traits: bool,
     pub rename_rule: Option<RenameRule>,
+    pub serialize_rename_rule: Option<RenameRule>,
+    rename_all_seen: bool,
+    pub alias_rules: Vec<RenameRule>,
+    pub prefix: Option<String>,
+    pub suffix: Option<String>,
+    pub on_miss: Option<syn::Path>,
+    pub aliases_file: Option<syn::LitStr>,
+
+    /// The path of the `aliases_file`, once `Enum::parse` has loaded it.
+    aliases_path: Option<PathBuf>,
+
+    /// Set once the aliases of any variant have been read, so that only the derives which use the
+    /// `aliases_file` depend on it.
+    aliases_read: Cell<bool>,
+    pub parser: Option<Backend>,
+    pub inline: Option<Inline>,
+    pub from: Vec<syn::Path>,
+    pub try_from: Vec<syn::Path>,
+    pub assert_discriminants: Option<Vec<(syn::Ident, syn::LitInt)>>,
+    pub kind_name: Option<syn::Ident>,
+    pub parse_fn: Option<(syn::Visibility, syn::Ident)>,
+    pub from_cstr_ptr: Option<(syn::Visibility, syn::Ident)>,
+    pub kind_derive: Vec<syn::Path>,
+    pub derives: Vec<syn::Ident>,
+    pub pg_name: Option<String>,
+    pub expecting: Option<String>,
 }
 
 impl EnumAttrs {
+    /// Returns how the names of a variant should be matched, taking `case_insensitive` and
+    /// `case_sensitive` into account.
+    pub fn variant_case(&self, attrs: &VariantAttrs) -> CaseFolding {
+        attrs.case.unwrap_or(self.case)
+    }
+
+    /// Returns the `#[inline(...)]` attribute to place on generated functions, if any.
+    pub fn inline_attr(&self) -> TokenStream {
+        match self.inline {
+            Some(Inline::Always) => quote!(#[inline(always)]),
+            Some(Inline::Never) => quote!(#[inline(never)]),
+            None => TokenStream::new(),
+        }
+    }
+
+    /// Returns the canonical name of a variant, taking `rename`, `rename_all`, `prefix` and
+    /// `suffix` into account.
+    ///
+    /// This is the name which is parsed. If `rename_all` has separate `serialize` and
+    /// `deserialize` rules, it follows `deserialize`.
+    pub fn variant_name(&self, v: &syn::Variant, attrs: &VariantAttrs) -> String {
+        self.renamed(v, attrs, self.rename_rule)
+    }
+
+    /// Like `variant_name`, but returns the name which is written out (e.g. by `Display`),
+    /// following the `serialize` rule of `rename_all`. A `display` attribute overrides this
+    /// entirely.
+    pub fn display_name(&self, v: &syn::Variant, attrs: &VariantAttrs) -> String {
+        match &attrs.display {
+            Some(display) => display.clone(),
+            None => self.renamed(v, attrs, self.serialize_rename_rule),
+        }
+    }
+
+    fn renamed(&self, v: &syn::Variant, attrs: &VariantAttrs, rule: Option<RenameRule>) -> String {
+        let name = if let Some(name) = &attrs.rename {
+            name.clone()
+        } else if let Some(rename_rule) = rule {
+            rename_rule.apply_to_variant(&v.ident.to_string())
+        } else {
+            v.ident.to_string()
+        };
+
+        self.with_affixes(name, attrs)
+    }
+
+    /// Adds `prefix` and `suffix` to a name, unless the variant opts out of them.
+    fn with_affixes(&self, name: String, attrs: &VariantAttrs) -> String {
+        let prefix = self.prefix.as_deref().filter(|_| !attrs.no_prefix).unwrap_or("");
+        let suffix = self.suffix.as_deref().filter(|_| !attrs.no_suffix).unwrap_or("");
+        format!("{}{}{}", prefix, name, suffix)
+    }
+
+    /// Returns the aliases of a variant: those given with `alias`, and its name under each
+    /// `alias_all` rule.
+    ///
+    /// `alias_all` is not applied to variants with an explicit `rename`. The canonical name is
+    /// never an alias.
+    pub fn variant_aliases(&self, v: &syn::Variant, attrs: &VariantAttrs) -> BTreeSet<String> {
+        self.aliases_read.set(true);
+
+        let mut aliases = attrs.aliases.clone();
+        if attrs.rename.is_none() {
+            for rule in &self.alias_rules {
+                let alias = rule.apply_to_variant(&v.ident.to_string());
+                aliases.insert(self.with_affixes(alias, attrs));
+            }
+        }
+
+        aliases.remove(&self.variant_name(v, attrs));
+        aliases
+    }
+
+    /// Returns an item which causes the crate to be recompiled when the `aliases_file` changes, or
+    /// nothing if no aliases have been read since the enum was parsed.
+    pub fn aliases_file_dependency(&self) -> TokenStream {
+        match &self.aliases_path {
+            Some(path) if self.aliases_read.get() => {
+                let path = path.to_string_lossy().into_owned();
+                quote!(const _: &[u8] = include_bytes!(#path);)
+            }
+
+            _ => TokenStream::new(),
+        }
+    }
+
     pub fn from_attrs<T>(attrs: T) -> Result<Self, ErrorList>
         where T: IntoIterator<Item = Fallible<Attr>>,
     {
@@ -264,16 +1253,127 @@ impl EnumAttrs {
         let mut errors = ErrorList::default();
         for attr in attrs {
             match attr {
-                Ok(Attr::CaseInsensitive) => ret.nocase = true,
+                Ok(Attr::CaseInsensitive(case)) => ret.case = case,
+                Ok(Attr::DebugChecks) => ret.debug_checks = true,
+                Ok(Attr::Wrapping) => ret.wrapping = true,
+                Ok(Attr::SqlRepr) => ret.sql_repr = true,
+                Ok(Attr::FromBytes) => ret.from_bytes = true,
+                Ok(Attr::FromCStr) => ret.from_cstr = true,
+                Ok(Attr::TryFromStr) => ret.try_from_str = true,
+                Ok(Attr::CanonicalName) => ret.canonical_name = true,
+                Ok(Attr::FromName) => ret.from_name = true,
+                Ok(Attr::ParseDetailed) => ret.parse_detailed = true,
+                Ok(Attr::ParseError) => ret.parse_error = true,
+                Ok(Attr::Trim) => ret.trim = true,
+                Ok(Attr::AssumeLowercase) => ret.assume_lowercase = true,
+                Ok(Attr::FromPrefix) => ret.from_prefix = true,
+                Ok(Attr::NumericAliases) => ret.numeric_aliases = true,
+                Ok(Attr::NoPanic) => ret.no_panic = true,
+                Ok(Attr::Qualified { only }) => ret.qualified = Some(if only {
+                    Qualified::Only
+                } else {
+                    Qualified::Optional
+                }),
+                Ok(Attr::DashUnderscoreInsensitive) => ret.dash_underscore = true,
+                Ok(Attr::Traits) => ret.traits = true,
+
+                Ok(Attr::AliasAll(r)) => ret.alias_rules.push(r),
 
-                Ok(Attr::RenameAll(r)) => if ret.rename_rule.is_none() {
+                Ok(Attr::RenameAll(r)) => if !ret.rename_all_seen {
+                    ret.rename_all_seen = true;
                     ret.rename_rule = Some(r);
+                    ret.serialize_rename_rule = Some(r);
+                } else {
+                    errors.push_back(format_diag!(DuplicateAttribute, "Enum can only have a single \"rename_all\" attribute"));
+                },
+
+                Ok(Attr::RenameAllSplit { serialize, deserialize }) => if !ret.rename_all_seen {
+                    ret.rename_all_seen = true;
+                    ret.rename_rule = deserialize;
+                    ret.serialize_rename_rule = serialize;
+                } else {
+                    errors.push_back(format_diag!(DuplicateAttribute, "Enum can only have a single \"rename_all\" attribute"));
+                },
+
+                Ok(Attr::FromCStrPtr(vis, ident)) => if ret.from_cstr_ptr.is_none() {
+                    ret.from_cstr_ptr = Some((vis, ident));
+                } else {
+                    errors.push_back(format_diag!(DuplicateAttribute, "Enum can only have a single \"from_cstr_ptr\" attribute"));
+                },
+
+                Ok(Attr::ParseFn(vis, ident)) => if ret.parse_fn.is_none() {
+                    ret.parse_fn = Some((vis, ident));
+                } else {
+                    errors.push_back(format_diag!(DuplicateAttribute, "Enum can only have a single \"parse_fn\" attribute"));
+                },
+
+                Ok(Attr::KindName(ident)) => if ret.kind_name.is_none() {
+                    ret.kind_name = Some(ident);
+                } else {
+                    errors.push_back(format_diag!(DuplicateAttribute, "Enum can only have a single \"kind_name\" attribute"));
+                },
+
+                Ok(Attr::KindDerive(paths)) => ret.kind_derive.extend(paths),
+                Ok(Attr::Derive(idents)) => ret.derives.extend(idents),
+                Ok(Attr::From(path)) => ret.from.push(path),
+                Ok(Attr::TryFrom(path)) => ret.try_from.push(path),
+
+                Ok(Attr::Prefix(s)) => if ret.prefix.is_none() {
+                    ret.prefix = Some(s);
+                } else {
+                    errors.push_back(format_diag!(DuplicateAttribute, "Enum can only have a single \"prefix\" attribute"));
+                },
+
+                Ok(Attr::Suffix(s)) => if ret.suffix.is_none() {
+                    ret.suffix = Some(s);
+                } else {
+                    errors.push_back(format_diag!(DuplicateAttribute, "Enum can only have a single \"suffix\" attribute"));
+                },
+
+                Ok(Attr::PgName(s)) => if ret.pg_name.is_none() {
+                    ret.pg_name = Some(s);
+                } else {
+                    errors.push_back(format_diag!(DuplicateAttribute, "Enum can only have a single \"pg_name\" attribute"));
+                },
+
+                Ok(Attr::Expecting(s)) => if ret.expecting.is_none() {
+                    ret.expecting = Some(s);
+                } else {
+                    errors.push_back(format_diag!(DuplicateAttribute, "Enum can only have a single \"expecting\" attribute"));
+                },
+
+                Ok(Attr::OnMiss(p)) => if ret.on_miss.is_none() {
+                    ret.on_miss = Some(p);
+                } else {
+                    errors.push_back(format_diag!(DuplicateAttribute, "Enum can only have a single \"on_miss\" attribute"));
+                },
+
+                Ok(Attr::AliasesFile(file)) => if ret.aliases_file.is_none() {
+                    ret.aliases_file = Some(file);
+                } else {
+                    errors.push_back(format_diag!(DuplicateAttribute, "Enum can only have a single \"aliases_file\" attribute"));
+                },
+
+                Ok(Attr::Parser(backend)) => if ret.parser.is_none() {
+                    ret.parser = Some(backend);
+                } else {
+                    errors.push_back(format_diag!(DuplicateAttribute, "Enum can only have a single \"parser\" attribute"));
+                },
+
+                Ok(Attr::Inline(inline)) => if ret.inline.is_none() {
+                    ret.inline = Some(inline);
+                } else {
+                    errors.push_back(format_diag!(DuplicateAttribute, "Enum can only have a single \"inline\" attribute"));
+                },
+
+                Ok(Attr::AssertDiscriminants(pairs)) => if ret.assert_discriminants.is_none() {
+                    ret.assert_discriminants = Some(pairs);
                 } else {
-                    errors.push_back(format_err!("Enum can only have a single \"rename_all\" attribute"));
+                    errors.push_back(format_diag!(DuplicateAttribute, "Enum can only have a single \"assert_discriminants\" attribute"));
                 },
 
                 Ok(attr) =>
-                    errors.push_back(format_err!("Attribute \"{:?}\" is not valid for an enum", attr)),
+                    errors.push_back(format_diag!(MisplacedAttribute, "Attribute \"{:?}\" is not valid for an enum", attr)),
 
                 Err(e) => errors.push_back(e),
             }
@@ -287,10 +1387,69 @@ impl EnumAttrs {
     }
 }
 
+/// Returns the path of the file given with `aliases_file`, which is relative to the directory
+/// containing the manifest of the crate being compiled.
+fn aliases_file_path(file: &syn::LitStr) -> PathBuf {
+    match std::env::var_os("CARGO_MANIFEST_DIR") {
+        Some(dir) => Path::new(&dir).join(file.value()),
+        None => PathBuf::from(file.value()),
+    }
+}
+
+/// Adds the aliases listed in the file given with `aliases_file` to each variant, and returns the
+/// path of the file.
+///
+/// Each line of the file is the name of a variant followed by any number of aliases, separated by
+/// whitespace. A `#` begins a comment which runs to the end of the line, and blank lines are
+/// ignored.
+fn load_aliases_file(file: &syn::LitStr, variants: &mut [(&syn::Variant, VariantAttrs)]) -> Fallible<PathBuf> {
+    let path = aliases_file_path(file);
+    let contents = std::fs::read_to_string(&path)
+        .map_err(|e| format_diag!(InvalidAliasesFile, "Failed to read `{}`: {}", path.display(), e)
+            .with_span(file.span()))?;
+
+    for (i, line) in contents.lines().enumerate() {
+        let line = line.find('#').map_or(line, |comment| &line[..comment]);
+        let mut words = line.split_whitespace();
+        let name = match words.next() {
+            Some(name) => name,
+            None => continue,
+        };
+
+        match variants.iter_mut().find(|(v, _)| v.ident == name) {
+            Some((_, attrs)) => attrs.aliases.extend(words.map(String::from)),
+            None => return Err(format_diag!(InvalidAliasesFile, "`{}` is not a variant (line {} of `{}`)",
+                                            name, i + 1, path.display())
+                .with_span(file.span())),
+        }
+    }
+
+    Ok(path)
+}
+
 pub type Discriminant = i128;
 
+/// Returns the value of `expr` if it is an integer literal or a negated integer literal.
+fn int_literal(expr: &syn::Expr) -> Option<Discriminant> {
+    use syn::{Expr, ExprLit, ExprUnary, Lit, UnOp};
+
+    match expr {
+        Expr::Lit(ExprLit { lit: Lit::Int(i), .. }) =>
+            Some(i.base10_parse::<Discriminant>().expect("Variant overflowed i128")),
+
+        Expr::Unary(ExprUnary { op: UnOp::Neg(_), expr, .. }) =>
+            int_literal(expr).map(|d| -d),
+
+        _ => None,
+    }
+}
+
 pub struct Enum<'a> {
     pub name: &'a syn::Ident,
+    pub vis: &'a syn::Visibility,
+
+    /// Every attribute on the enum, including those which do not belong to this crate.
+    pub item_attrs: &'a [syn::Attribute],
     pub attrs: EnumAttrs,
 
     /// This will be `None` if no `#[repr]` was specified, or an error if parsing failed or
@@ -305,11 +1464,11 @@ pub struct Enum<'a> {
 
 impl<'a> Enum<'a> {
     pub fn parse(input: &'a syn::DeriveInput) -> Result<Self, ErrorList> {
-        use syn::{Data, DataEnum, Expr, ExprLit, Lit};
+        use syn::{Data, DataEnum};
 
         let DataEnum { variants, .. } = match &input.data {
             Data::Enum(e) => e,
-            _ => bail_list!("Input must be an enum"),
+            _ => bail_list!(NotAnEnum, "Input must be an enum"),
         };
 
         let mut errors = ErrorList::default();
@@ -317,7 +1476,7 @@ impl<'a> Enum<'a> {
             .iter()
             .flat_map(Attr::parse_attrs));
 
-        let enum_attrs = match enum_attrs {
+        let mut enum_attrs = match enum_attrs {
             Ok(attrs) => attrs,
             Err(e) => {
                 errors = e;
@@ -325,14 +1484,14 @@ impl<'a> Enum<'a> {
             }
         };
 
-        let mut discriminants = Some(vec![]);
-        let mut parsed_variants = vec![];
+        let mut discriminants = Some(Vec::with_capacity(variants.len()));
+        let mut parsed_variants = Vec::with_capacity(variants.len());
         for v in variants.iter() {
             let attrs = VariantAttrs::from_attrs(v.attrs
                 .iter()
                 .flat_map(Attr::parse_attrs));
 
-            let attrs = match attrs {
+            let mut attrs = match attrs {
                 Ok(a) => a,
                 Err(mut e) => {
                     errors.append(&mut e);
@@ -340,6 +1499,11 @@ impl<'a> Enum<'a> {
                 }
             };
 
+            match source_field(v) {
+                Ok(source) => attrs.source = source,
+                Err(mut e) => errors.append(&mut e),
+            }
+
             parsed_variants.push((v, attrs));
 
             if v.fields != syn::Fields::Unit {
@@ -348,14 +1512,14 @@ impl<'a> Enum<'a> {
             }
 
             if let Some(ds) = discriminants.as_mut() {
-                match &v.discriminant {
-                    // An integer literal
-                    Some((_, Expr::Lit(ExprLit { lit: Lit::Int(i), .. }))) => {
-                        ds.push(i.base10_parse::<i128>().expect("Variant overflowed i128"));
+                match v.discriminant.as_ref().map(|(_, expr)| int_literal(expr)) {
+                    // An integer literal, possibly negated
+                    Some(Some(d)) => {
+                        ds.push(d);
                     }
 
                     // An expr with an unknown value (e.g. a const defined elsewhere)
-                    Some(_) => {
+                    Some(None) => {
                         discriminants = None;
                     }
 
@@ -370,12 +1534,21 @@ impl<'a> Enum<'a> {
 
         let primitive_repr = parse_primitive_repr(input.attrs.iter());
 
+        if let (true, Some(file)) = (errors.is_empty(), &enum_attrs.aliases_file) {
+            match load_aliases_file(file, &mut parsed_variants) {
+                Ok(path) => enum_attrs.aliases_path = Some(path),
+                Err(e) => errors.push_back(e),
+            }
+        }
+
         if !errors.is_empty() {
             return Err(errors);
         }
 
         Ok(Enum {
             name: &input.ident,
+            vis: &input.vis,
+            item_attrs: &input.attrs,
             attrs: enum_attrs,
             variants: parsed_variants,
             primitive_repr,
@@ -383,6 +1556,46 @@ impl<'a> Enum<'a> {
         })
     }
 
+    /// Resolves `skip(...)` for the given derives, so that `skip` is set on exactly those variants
+    /// which are skipped by any of them.
+    ///
+    /// This only updates a flag on each variant, so the same `Enum` can be used for several
+    /// derives in turn.
+    pub fn for_derive(&mut self, derives: &[&str]) -> &Self {
+        for (_, attrs) in &mut self.variants {
+            attrs.skip = attrs.skip_all || derives.iter().any(|d| attrs.skip_for.contains(*d));
+        }
+
+        self
+    }
+
+    /// Returns the identifiers of all variants which are not skipped, in declaration order.
+    ///
+    /// Unskipped variants must not have fields.
+    pub fn unskipped_unit_variants(&self) -> Result<Vec<&'a syn::Ident>, ErrorList> {
+        let mut errors = ErrorList::new();
+        let mut unskipped = vec![];
+        for (v, attrs) in self.variants.iter() {
+            if attrs.skip {
+                continue;
+            }
+
+            if v.fields != syn::Fields::Unit {
+                errors.push_back(format_diag!(VariantHasFields, "An (unskipped) variant cannot have fields")
+                    .with_span(v.ident.span()));
+                continue;
+            }
+
+            unskipped.push(&v.ident);
+        }
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        Ok(unskipped)
+    }
+
     /*
     pub fn is_c_like(&self) -> bool {
         self.discriminants.is_some()
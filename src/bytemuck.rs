@@ -0,0 +1,27 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::attr::{Enum, ErrorList};
+use crate::conv::FromRepr;
+
+pub fn derive(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let FromRepr { name, repr, assertions, lookup } = FromRepr::for_enum(input)?;
+
+    // A C-like enum with a primitive `#[repr(...)]` has the same layout as that primitive, so it
+    // has no padding, and any of its discriminants is a valid bit pattern.
+    Ok(quote! {
+        #assertions
+
+        unsafe impl ::bytemuck::NoUninit for #name {}
+
+        unsafe impl ::bytemuck::CheckedBitPattern for #name {
+            type Bits = #repr;
+
+            fn is_valid_bit_pattern(bits: &#repr) -> bool {
+                let d = *bits;
+                let v: Option<Self> = #lookup;
+                v.is_some()
+            }
+        }
+    })
+}
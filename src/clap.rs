@@ -0,0 +1,57 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::attr::{Enum, ErrorList};
+use crate::name::{doc_summary, name_table};
+
+pub fn derive(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let name = input.name;
+
+    let table = name_table(input)?;
+
+    let unskipped: Vec<_> = table.iter()
+        .filter(|(_, s)| s.is_some())
+        .map(|(v, _)| {
+            let vident = &v.ident;
+            quote!(#name::#vident)
+        })
+        .collect();
+
+    let arms = table.iter()
+        .zip(input.variants.iter())
+        .map(|((v, s), (_, attrs))| {
+            let vident = &v.ident;
+            let s = match s {
+                Some(s) => s,
+                None => return quote!(#name::#vident { .. } => None),
+            };
+
+            let aliases = input.attrs.variant_aliases(v, attrs);
+            let help = attrs.message.clone()
+                .or_else(|| doc_summary(v))
+                .map(|help| quote!(.help(#help)));
+
+            quote! {
+                #name::#vident => Some(
+                    ::clap::builder::PossibleValue::new(#s)
+                        #( .alias(#aliases) )*
+                        #help
+                )
+            }
+        });
+
+    Ok(quote! {
+        impl ::clap::ValueEnum for #name {
+            fn value_variants<'a>() -> &'a [Self] {
+                const VARIANTS: &[#name] = &[#( #unskipped ),*];
+                VARIANTS
+            }
+
+            fn to_possible_value(&self) -> Option<::clap::builder::PossibleValue> {
+                match *self {
+                    #( #arms, )*
+                }
+            }
+        }
+    })
+}
@@ -1,27 +1,33 @@
-use failure::format_err;
-use proc_macro2::{TokenStream, Span};
-use quote::quote;
-
-use crate::attr::{Enum, ErrorList};
+use std::collections::BTreeMap;
 
-pub fn derive_try_from_repr(input: &syn::DeriveInput) -> Result<TokenStream, ErrorList> {
-    let Enum { name, variants, primitive_repr, .. } = Enum::parse(input)?;
+use proc_macro2::{Literal, TokenStream, Span};
+use quote::quote;
 
-    let mut errors = ErrorList::new();
-    let repr = match primitive_repr {
-        Ok(Some((_, repr))) => repr,
+use crate::attr::{Enum, ErrorList, VariantAttrs};
 
-        Ok(None) => bail_list!("`#[repr(...)]` must be specified to derive `TryFrom`"),
-        Err(e) => {
-            errors.push_back(e);
-            return Err(errors);
-        }
+/// Emits compile-time assertions for each discriminant listed in
+/// `#[enumeration(assert_discriminants(...))]`.
+fn discriminant_assertions(name: &syn::Ident,
+                           variants: &[(&syn::Variant, VariantAttrs)],
+                           pairs: Option<&[(syn::Ident, syn::LitInt)]>,
+                           repr: &syn::Path)
+    -> Result<TokenStream, ErrorList>
+{
+    let pairs = match pairs {
+        Some(pairs) => pairs,
+        None => return Ok(TokenStream::new()),
     };
 
+    let mut errors = ErrorList::new();
     for (v, _) in variants.iter() {
-        if v.fields != syn::Fields::Unit {
-            errors.push_back(format_err!("Variant cannot have fields"));
-            continue;
+        match pairs.iter().filter(|(ident, _)| *ident == v.ident).count() {
+            1 => {}
+            0 => errors.push_back(format_diag!(UnassertedDiscriminant,
+                                               "Variant `{}` is missing from \"assert_discriminants\"",
+                                               v.ident)),
+            _ => errors.push_back(format_diag!(UnassertedDiscriminant,
+                                               "Variant `{}` appears more than once in \"assert_discriminants\"",
+                                               v.ident)),
         }
     }
 
@@ -29,59 +35,165 @@ pub fn derive_try_from_repr(input: &syn::DeriveInput) -> Result<TokenStream, Err
         return Err(errors);
     }
 
-    let consts = variants.iter()
-        .map(|(v, _)| {
-            let s = "DISCRIMINANT_".to_owned() + &v.ident.to_string();
-            syn::Ident::new(s.as_str(), Span::call_site())
+    let assertions = pairs.iter()
+        .map(|(v, lit)| {
+            let msg = format!("The discriminant of `{}::{}` must be {}", name, v, lit);
+            quote!(assert!(#name::#v as #repr == #lit, #msg);)
         });
 
-    let ctors = variants.iter()
-        .map(|(v, _)| {
-            let v = &v.ident;
-            quote!(#name::#v)
-        });
+    Ok(quote! {
+        const _: () = {
+            #( #assertions )*
+        };
+    })
+}
+
+/// The pieces needed to convert a value of the primitive representation of a C-like enum back
+/// into the enum.
+pub struct FromRepr<'a> {
+    pub name: &'a syn::Ident,
+    pub repr: syn::Path,
+
+    /// Compile-time assertions requested with `assert_discriminants`.
+    pub assertions: TokenStream,
+
+    /// An expression of type `Option<Self>` which returns the variant whose discriminant is `d`.
+    pub lookup: TokenStream,
+}
+
+impl<'a> FromRepr<'a> {
+    pub fn for_enum(Enum { name, attrs, variants, primitive_repr, discriminants, .. }: &Enum<'a>)
+        -> Result<Self, ErrorList>
+    {
+        let mut errors = ErrorList::new();
+        let repr = match primitive_repr {
+            Ok(Some((_, repr))) => repr.clone(),
+
+            Ok(None) => bail_list!(MissingRepr, "`#[repr(...)]` must be specified to derive `TryFrom`"),
+            Err(e) => {
+                errors.push_back(e.clone());
+                return Err(errors);
+            }
+        };
+
+        for (v, _) in variants.iter() {
+            if v.fields != syn::Fields::Unit {
+                errors.push_back(format_diag!(VariantHasFields, "Variant cannot have fields")
+                    .with_span(v.ident.span()));
+                continue;
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        let assertions = discriminant_assertions(name, variants,
+                                                 attrs.assert_discriminants.as_deref(), &repr)?;
+
+        let ctors = variants.iter()
+            .map(|(v, _)| {
+                let v = &v.ident;
+                quote!(#name::#v)
+            });
+
+        // If every discriminant is an integer literal, we can match on them directly. This avoids
+        // emitting a `const` item per variant, which is slow to compile for very large enums.
+        if let Some(discriminants) = discriminants {
+            let discriminants = discriminants.iter().cloned().map(Literal::i128_unsuffixed);
+
+            let lookup = quote! {
+                match d {
+                    #( #discriminants => Some(#ctors), )*
+                    _ => None
+                }
+            };
+
+            return Ok(FromRepr { name, repr, assertions, lookup });
+        }
+
+        let consts = variants.iter()
+            .map(|(v, _)| {
+                let s = "DISCRIMINANT_".to_owned() + &v.ident.to_string();
+                syn::Ident::new(s.as_str(), Span::call_site())
+            });
+
+        // `as` casts are not valid as part of a pattern, so we need to do define new `consts` to hold
+        // them.
+        let const_defs = consts.clone()
+            .zip(ctors.clone())
+            .map(|(v, ctor)|  quote!(const #v: #repr = #ctor as #repr));
+
+        let lookup = quote! {
+            {
+                #( #[allow(non_upper_case_globals)] #const_defs; )*
+
+                match d {
+                    #( #consts => Some(#ctors), )*
+                    _ => None
+                }
+            }
+        };
+
+        Ok(FromRepr { name, repr, assertions, lookup })
+    }
+}
+
+pub fn derive_try_from_repr(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let FromRepr { name, repr, assertions, lookup } = FromRepr::for_enum(input)?;
+    let inline = input.attrs.inline_attr();
+
+    let trait_impl = if input.attrs.traits {
+        quote! {
+            impl ::enum_utils_traits::FromRepr for #name {
+                type Repr = #repr;
 
-    // `as` casts are not valid as part of a pattern, so we need to do define new `consts` to hold
-    // them.
-    let const_defs = consts.clone()
-        .zip(ctors.clone())
-        .map(|(v, ctor)|  quote!(const #v: #repr = #ctor as #repr));
+                #inline
+                fn from_repr(d: #repr) -> Option<Self> {
+                    #lookup
+                }
+            }
+        }
+    } else {
+        TokenStream::new()
+    };
 
     Ok(quote! {
+        #assertions
+        #trait_impl
+
         impl ::std::convert::TryFrom<#repr> for #name {
             type Error = ();
 
-            #[allow(non_upper_case_globals)]
+            #inline
             fn try_from(d: #repr) -> Result<Self, Self::Error> {
-
-                #( #const_defs; )*
-
-                match d {
-                    #( #consts => Ok(#ctors), )*
-                    _ => Err(())
+                match #lookup {
+                    Some(v) => Ok(v),
+                    None => Err(()),
                 }
             }
         }
     })
 }
 
-pub fn derive_repr_from(input: &syn::DeriveInput) -> Result<TokenStream, ErrorList> {
-    let Enum { name, variants, primitive_repr, .. } = Enum::parse(input)?;
-
+pub fn derive_repr_from(Enum { name, attrs, variants, primitive_repr, .. }: &Enum)
+    -> Result<TokenStream, ErrorList>
+{
     let mut errors = ErrorList::new();
     let repr = match primitive_repr {
         Ok(Some((_, repr))) => repr,
 
-        Ok(None) => bail_list!("`#[repr(...)]` must be specified to derive `TryFrom`"),
+        Ok(None) => bail_list!(MissingRepr, "`#[repr(...)]` must be specified to derive `TryFrom`"),
         Err(e) => {
-            errors.push_back(e);
+            errors.push_back(e.clone());
             return Err(errors);
         }
     };
 
     for (v, _) in variants.iter() {
         if v.fields != syn::Fields::Unit {
-            errors.push_back(format_err!("Variant cannot have fields"));
+            errors.push_back(format_diag!(VariantHasFields, "Variant cannot have fields")
+                .with_span(v.ident.span()));
             continue;
         }
     }
@@ -90,11 +202,324 @@ pub fn derive_repr_from(input: &syn::DeriveInput) -> Result<TokenStream, ErrorLi
         return Err(errors);
     }
 
+    let assertions = discriminant_assertions(name, variants,
+                                             attrs.assert_discriminants.as_deref(), repr)?;
+    let inline = attrs.inline_attr();
+
     Ok(quote! {
+        #assertions
+
         impl ::std::convert::From<#name> for #repr {
+            #inline
             fn from(d: #name) -> Self {
                 d as #repr
             }
         }
     })
 }
+
+pub fn derive_try_from_float(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let Enum { name, variants, discriminants, .. } = input;
+
+    let mut errors = ErrorList::new();
+    for (v, _) in variants.iter() {
+        if v.fields != syn::Fields::Unit {
+            errors.push_back(format_diag!(VariantHasFields, "Variant cannot have fields")
+                .with_span(v.ident.span()));
+            continue;
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    let discriminants = match discriminants {
+        Some(ds) => ds,
+        None => bail_list!(UnknownDiscriminant,
+                           "All discriminants must be integer literals to derive `TryFromFloat`"),
+    };
+
+    // Variants whose discriminant cannot be represented exactly by a given float type are never
+    // returned, since no float of that type is exactly equal to their discriminant.
+    let float_impl = |ty: TokenStream, to_lit: fn(i128) -> Option<Literal>| {
+        let (lits, ctors): (Vec<_>, Vec<_>) = discriminants.iter()
+            .zip(variants.iter())
+            .filter_map(|(&d, (v, _))| {
+                let v = &v.ident;
+                to_lit(d).map(|lit| (lit, quote!(#name::#v)))
+            })
+            .unzip();
+
+        quote! {
+            impl ::std::convert::TryFrom<#ty> for #name {
+                type Error = ();
+
+                #[allow(clippy::float_cmp)]
+                fn try_from(f: #ty) -> Result<Self, Self::Error> {
+                    #( if f == #lits { return Ok(#ctors); } )*
+                    Err(())
+                }
+            }
+        }
+    };
+
+    let f64_impl = float_impl(quote!(f64), |d| {
+        let f = d as f64;
+        if d != i128::MAX && f as i128 == d {
+            Some(Literal::f64_unsuffixed(f))
+        } else {
+            None
+        }
+    });
+
+    let f32_impl = float_impl(quote!(f32), |d| {
+        let f = d as f32;
+        if d != i128::MAX && f as i128 == d {
+            Some(Literal::f32_unsuffixed(f))
+        } else {
+            None
+        }
+    });
+
+    Ok(quote! {
+        #f64_impl
+        #f32_impl
+    })
+}
+
+pub fn derive_try_from_char(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let Enum { name, variants, .. } = input;
+
+    let mut errors = ErrorList::new();
+    let mut seen = BTreeMap::new();
+    let mut from_arms = vec![];
+    let mut into_arms = vec![];
+    for (v, attrs) in variants.iter() {
+        let vident = &v.ident;
+        if attrs.skip {
+            let msg = format!("`{}::{}` is skipped and has no character", name, vident);
+            into_arms.push(quote!(#name::#vident { .. } => panic!(#msg)));
+            continue;
+        }
+
+        if v.fields != syn::Fields::Unit {
+            errors.push_back(format_diag!(VariantHasFields, "An (unskipped) variant cannot have fields")
+                .with_span(vident.span()));
+            continue;
+        }
+
+        let c = match attrs.char {
+            Some(c) => c,
+            None => {
+                errors.push_back(format_diag!(MissingChar, "Variant `{}` has no \"char\" attribute", vident)
+                    .with_span(vident.span()));
+                continue;
+            }
+        };
+
+        if let Some(prev) = seen.insert(c, vident) {
+            errors.push_back(format_diag!(DuplicateChar, "Variants `{}` and `{}` have the same character {:?}",
+                                          prev, vident, c)
+                .with_span(vident.span()));
+            continue;
+        }
+
+        from_arms.push(quote!(#c => Ok(#name::#vident)));
+        into_arms.push(quote!(#name::#vident => #c));
+    }
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    Ok(quote! {
+        impl ::std::convert::TryFrom<char> for #name {
+            type Error = ();
+
+            fn try_from(c: char) -> Result<Self, Self::Error> {
+                match c {
+                    #( #from_arms, )*
+                    _ => Err(()),
+                }
+            }
+        }
+
+        impl ::std::convert::From<#name> for char {
+            fn from(v: #name) -> Self {
+                match v {
+                    #( #into_arms, )*
+                }
+            }
+        }
+    })
+}
+
+/// Returns a pair for each unskipped variant containing the name of the corresponding variant in
+/// another enum and its own name.
+///
+/// Variants are paired by identifier unless `from_variant` is given.
+fn variant_pairs<'a>(variants: &'a [(&'a syn::Variant, VariantAttrs)])
+    -> Result<Vec<(&'a syn::Ident, &'a syn::Ident)>, ErrorList>
+{
+    let mut errors = ErrorList::new();
+    let pairs = variants.iter()
+        .filter(|(_, attrs)| !attrs.skip)
+        .filter_map(|(v, attrs)| {
+            if v.fields != syn::Fields::Unit {
+                errors.push_back(format_diag!(VariantHasFields, "An (unskipped) variant cannot have fields")
+                    .with_span(v.ident.span()));
+                return None;
+            }
+
+            Some((attrs.from_variant.as_ref().unwrap_or(&v.ident), &v.ident))
+        })
+        .collect();
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    Ok(pairs)
+}
+
+pub fn derive_from_enum(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let Enum { name, attrs, variants, .. } = input;
+    let pairs = variant_pairs(variants)?;
+
+    // Every variant of the source enum must have a counterpart, so no wildcard arm is emitted.
+    // `rustc` reports any which are missing as a non-exhaustive `match`.
+    let from_impls = attrs.from.iter()
+        .map(|source| {
+            let arms = pairs.iter().map(|(src, dst)| quote!(#source::#src => #name::#dst));
+            quote! {
+                impl ::std::convert::From<#source> for #name {
+                    fn from(v: #source) -> Self {
+                        match v {
+                            #( #arms, )*
+                        }
+                    }
+                }
+            }
+        });
+
+    // The source enum may have variants which are not in this one. The wildcard arm is
+    // unreachable if it does not.
+    let try_from_impls = attrs.try_from.iter()
+        .map(|source| {
+            let arms = pairs.iter().map(|(src, dst)| quote!(#source::#src => Ok(#name::#dst)));
+            quote! {
+                impl ::std::convert::TryFrom<#source> for #name {
+                    type Error = #source;
+
+                    fn try_from(v: #source) -> Result<Self, Self::Error> {
+                        match v {
+                            #( #arms, )*
+                            #[allow(unreachable_patterns)]
+                            v => Err(v),
+                        }
+                    }
+                }
+            }
+        });
+
+    Ok(quote! {
+        #( #from_impls )*
+        #( #try_from_impls )*
+    })
+}
+
+pub fn derive_proto_enum(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let FromRepr { name, repr, assertions, lookup } = FromRepr::for_enum(input)?;
+
+    // Protobuf enums are always `i32` on the wire, so every discriminant must fit in one. Going
+    // through `i128` makes the check valid for any primitive representation.
+    let range_checks = input.variants.iter()
+        .map(|(v, _)| {
+            let vident = &v.ident;
+            let msg = format!("The discriminant of `{}::{}` does not fit in an `i32`", name, vident);
+            quote! {
+                assert!(#name::#vident as #repr as i128 >= i32::MIN as i128
+                        && #name::#vident as #repr as i128 <= i32::MAX as i128, #msg);
+            }
+        });
+
+    let arms: Vec<_> = input.variants.iter()
+        .map(|(v, _)| {
+            let vident = &v.ident;
+            quote!(#name::#vident => #name::#vident as #repr as i32)
+        })
+        .collect();
+
+    Ok(quote! {
+        #assertions
+
+        const _: () = {
+            #( #range_checks )*
+        };
+
+        impl #name {
+            fn from_i32(value: i32) -> Option<Self> {
+                let d = <#repr as ::std::convert::TryFrom<i32>>::try_from(value).ok()?;
+                #lookup
+            }
+
+            const fn as_i32(&self) -> i32 {
+                match *self {
+                    #( #arms, )*
+                }
+            }
+        }
+    })
+}
+
+pub fn derive_from_bool(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let Enum { name, variants, .. } = input;
+
+    let (a, b) = match variants.as_slice() {
+        [a, b] => (a, b),
+        _ => bail_list!(InvalidBoolEnum, "`FromBool` can only be derived for an enum with two variants"),
+    };
+
+    let mut errors = ErrorList::new();
+    for (v, _) in [a, b] {
+        if v.fields != syn::Fields::Unit {
+            errors.push_back(format_diag!(VariantHasFields, "Variant cannot have fields")
+                .with_span(v.ident.span()));
+        }
+    }
+
+    // Without `true_variant` or `false_variant`, the first variant is `false` like it is for
+    // `bool`. If only one variant is marked, the other takes the opposite value.
+    let (falsy, truthy) = match (a.1.bool_value, b.1.bool_value) {
+        (None, None) | (Some(false), None) | (None, Some(true)) | (Some(false), Some(true)) =>
+            (&a.0.ident, &b.0.ident),
+        (Some(true), None) | (None, Some(false)) | (Some(true), Some(false)) =>
+            (&b.0.ident, &a.0.ident),
+        (Some(x), Some(_)) => {
+            errors.push_back(format_diag!(InvalidBoolEnum, "Both variants are marked as `{}`", x));
+            return Err(errors);
+        }
+    };
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    Ok(quote! {
+        impl ::std::convert::From<bool> for #name {
+            fn from(b: bool) -> Self {
+                if b { #name::#truthy } else { #name::#falsy }
+            }
+        }
+
+        impl ::std::convert::From<#name> for bool {
+            fn from(v: #name) -> Self {
+                match v {
+                    #name::#falsy => false,
+                    #name::#truthy => true,
+                }
+            }
+        }
+    })
+}
@@ -0,0 +1,42 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::attr::{Enum, ErrorList};
+
+pub fn derive(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let Enum { name, variants, .. } = input;
+
+    let mut defaults = variants.iter()
+        .filter(|(_, attrs)| attrs.default)
+        .map(|(v, _)| v);
+
+    let default = match defaults.next() {
+        Some(v) => v,
+        None => bail_list!(MissingDefault, "One variant must be marked with `#[enumeration(default)]`"),
+    };
+
+    let mut errors: ErrorList = defaults
+        .map(|v| {
+            format_diag!(DuplicateAttribute, "Only one variant can be marked with `#[enumeration(default)]`")
+                .with_span(v.ident.span())
+        })
+        .collect();
+
+    if default.fields != syn::Fields::Unit {
+        errors.push_back(format_diag!(VariantHasFields, "The default variant cannot have fields")
+            .with_span(default.ident.span()));
+    }
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    let vident = &default.ident;
+    Ok(quote! {
+        impl ::std::default::Default for #name {
+            fn default() -> Self {
+                #name::#vident
+            }
+        }
+    })
+}
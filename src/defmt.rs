@@ -0,0 +1,32 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::attr::{Enum, ErrorList};
+use crate::name::name_table_ignoring_fields;
+
+pub fn derive(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let name = input.name;
+
+    let arms = name_table_ignoring_fields(input)?
+        .into_iter()
+        .map(|(v, s)| {
+            let vident = &v.ident;
+
+            // Skipped variants have no canonical name, so we fall back to the identifier.
+            let s = s.unwrap_or_else(|| vident.to_string());
+
+            // The name is used as a `defmt` format string, which is interned.
+            let s = s.replace('{', "{{").replace('}', "}}");
+            quote!(#name::#vident { .. } => ::defmt::write!(f, #s))
+        });
+
+    Ok(quote! {
+        impl ::defmt::Format for #name {
+            fn format(&self, f: ::defmt::Formatter<'_>) {
+                match *self {
+                    #( #arms, )*
+                }
+            }
+        }
+    })
+}
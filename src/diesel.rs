@@ -0,0 +1,132 @@
+use std::convert::TryFrom;
+
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::attr::{Enum, ErrorList, Primitive};
+use crate::conv::FromRepr;
+use crate::from_str::FromStrImpl;
+use crate::name::name_table;
+
+/// Stores each variant as its canonical name in a `Text` column.
+fn derive_text(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let imp = FromStrImpl::for_enum(input)?;
+    let lookup = imp.lookup();
+    let name = input.name;
+
+    let arms: Vec<_> = name_table(input)?
+        .into_iter()
+        .zip(input.variants.iter())
+        .map(|((v, s), (_, attrs))| {
+            let vident = &v.ident;
+            match s {
+                Some(s) => quote!(#name::#vident => #s),
+
+                // The catch-all variant is stored as the string it was parsed from.
+                None if attrs.other && v.fields.len() == 1 =>
+                    quote!(#name::#vident(ref s) => ::std::convert::AsRef::<str>::as_ref(s)),
+
+                None => {
+                    let msg = format!("`{}::{}` is skipped and cannot be stored", name, vident);
+                    quote!(#name::#vident { .. } => return Err(#msg.into()))
+                }
+            }
+        })
+        .collect();
+
+    let msg = format!("invalid value {{:?}} for `{}`", name);
+
+    Ok(quote! {
+        impl<DB> ::diesel::serialize::ToSql<::diesel::sql_types::Text, DB> for #name
+            where DB: ::diesel::backend::Backend,
+                  str: ::diesel::serialize::ToSql<::diesel::sql_types::Text, DB>,
+        {
+            fn to_sql<'b>(&'b self, out: &mut ::diesel::serialize::Output<'b, '_, DB>)
+                -> ::diesel::serialize::Result
+            {
+                let s: &'b str = match *self {
+                    #( #arms, )*
+                };
+
+                <str as ::diesel::serialize::ToSql<::diesel::sql_types::Text, DB>>::to_sql(s, out)
+            }
+        }
+
+        impl<DB> ::diesel::deserialize::FromSql<::diesel::sql_types::Text, DB> for #name
+            where DB: ::diesel::backend::Backend,
+                  ::std::string::String: ::diesel::deserialize::FromSql<::diesel::sql_types::Text, DB>,
+        {
+            fn from_sql(bytes: DB::RawValue<'_>) -> ::diesel::deserialize::Result<Self> {
+                let s = <::std::string::String as ::diesel::deserialize::FromSql<::diesel::sql_types::Text, DB>>::from_sql(bytes)?;
+                let s = s.as_str();
+                match #lookup {
+                    Some(v) => Ok(v),
+                    None => Err(format!(#msg, s).into()),
+                }
+            }
+        }
+    })
+}
+
+/// Stores each variant as its discriminant in an integer column.
+fn derive_repr(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let FromRepr { name, repr, assertions, lookup } = FromRepr::for_enum(input)?;
+    let variants = &input.variants;
+
+    let sql_ty = match Primitive::try_from(&repr) {
+        Ok(Primitive::I16) => quote!(::diesel::sql_types::SmallInt),
+        Ok(Primitive::I32) => quote!(::diesel::sql_types::Integer),
+        Ok(Primitive::I64) => quote!(::diesel::sql_types::BigInt),
+        _ => bail_list!(MissingRepr, "`sql_repr` requires `#[repr(i16)]`, `#[repr(i32)]` or `#[repr(i64)]`"),
+    };
+
+    let arms = variants.iter()
+        .map(|(v, _)| {
+            let vident = &v.ident;
+            quote!(#name::#vident => &(#name::#vident as #repr))
+        });
+
+    let msg = format!("invalid discriminant `{{}}` for `{}`", name);
+
+    Ok(quote! {
+        #assertions
+
+        impl<DB> ::diesel::serialize::ToSql<#sql_ty, DB> for #name
+            where DB: ::diesel::backend::Backend,
+                  #repr: ::diesel::serialize::ToSql<#sql_ty, DB>,
+        {
+            fn to_sql<'b>(&'b self, out: &mut ::diesel::serialize::Output<'b, '_, DB>)
+                -> ::diesel::serialize::Result
+            {
+                // `to_sql` requires a reference which outlives `out`, so each discriminant is
+                // promoted to a `static`.
+                let d: &'static #repr = match *self {
+                    #( #arms, )*
+                };
+
+                <#repr as ::diesel::serialize::ToSql<#sql_ty, DB>>::to_sql(d, out)
+            }
+        }
+
+        impl<DB> ::diesel::deserialize::FromSql<#sql_ty, DB> for #name
+            where DB: ::diesel::backend::Backend,
+                  #repr: ::diesel::deserialize::FromSql<#sql_ty, DB>,
+        {
+            fn from_sql(bytes: DB::RawValue<'_>) -> ::diesel::deserialize::Result<Self> {
+                let d = <#repr as ::diesel::deserialize::FromSql<#sql_ty, DB>>::from_sql(bytes)?;
+                match #lookup {
+                    Some(v) => Ok(v),
+                    None => Err(format!(#msg, d).into()),
+                }
+            }
+        }
+    })
+}
+
+pub fn derive(input: &Enum) -> Result<TokenStream, ErrorList> {
+    if input.attrs.sql_repr {
+        derive_repr(input)
+    } else {
+        derive_text(input)
+    }
+}
@@ -0,0 +1,108 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+use crate::attr::{Enum, ErrorList};
+
+/// Derives from this crate which accept `#[enumeration(...)]` attributes.
+///
+/// `Default` is omitted since it collides with the derive in the standard library.
+pub const DERIVES: &[&str] = &[
+    "FromStr", "Display", "AsStr", "VariantNames", "IterVariants", "EnumCount", "EnumIs",
+    "TryFromRepr", "ReprFrom", "TryFromFloat", "EnumDiscriminants", "EnumMessage",
+    "EnumProperty", "Ordinal", "Successor", "EnumMap", "EnumSet",
+    "EnumIndex", "Random", "ProptestArbitrary", "Arbitrary", "QuickcheckArbitrary",
+    "Deserialize", "Serialize", "SerializeRepr", "DeserializeRepr", "ValueEnum",
+    "JsonSchema", "SqlxType", "DieselSql", "PostgresEnum",
+    "RusqliteSql", "Format", "FromPrimitive", "ToPrimitive", "TryFromChar",
+    "PartialEqStr", "AsRefStr", "FromEnum", "Enum", "VariantId", "ForEachVariant",
+    "GraphQLEnum", "ProtoEnum", "ToSchema", "Error", "EnumBounds",
+    "EnumTryAs", "NameOf", "Visitor", "CheckedBitPattern",
+    "FromBool", "Step", "EnumOrd", "EnumValue", "Matcher",
+];
+
+/// Returns `true` if `path` names one of the derives in this crate, i.e. it begins with
+/// `enum_utils`.
+///
+/// Names such as `Serialize` or `Error` are shared with derives from other crates, which do not
+/// accept `#[enumeration(...)]`, so a bare name is never assumed to be ours.
+fn is_enum_utils_derive(path: &syn::Path) -> bool {
+    path.segments.first().is_some_and(|seg| seg.ident == "enum_utils")
+}
+
+/// Returns the `#[enumeration(...)]` attributes on the original enum with any `kind_*` arguments
+/// removed, since they configure the mirror itself.
+fn enum_level_attrs(attrs: &[syn::Attribute]) -> Vec<TokenStream> {
+    use syn::{Meta, NestedMeta};
+
+    attrs.iter()
+        .filter(|attr| attr.path.is_ident("enumeration"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(Meta::List(list)) => Some(list.nested),
+            _ => None,
+        })
+        .filter_map(|nested| {
+            let kept: Vec<_> = nested.into_iter()
+                .filter(|arg| match arg {
+                    NestedMeta::Meta(m) => !m.path().is_ident("kind_name")
+                        && !m.path().is_ident("kind_derive"),
+                    _ => true,
+                })
+                .collect();
+
+            if kept.is_empty() {
+                None
+            } else {
+                Some(quote!(#[enumeration(#( #kept ),*)]))
+            }
+        })
+        .collect()
+}
+
+pub fn derive(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let Enum { name, attrs, variants, .. } = input;
+
+    let vis = input.vis;
+    let kind = attrs.kind_name.clone().unwrap_or_else(|| format_ident!("{}Kind", name));
+    let derives = &attrs.kind_derive;
+
+    // `#[enumeration(...)]` is only a valid attribute if one of our derives is applied to the
+    // mirror, so the original attributes are copied over only in that case.
+    let copy_attrs = derives.iter().any(is_enum_utils_derive);
+    let enum_attrs = if copy_attrs { enum_level_attrs(input.item_attrs) } else { vec![] };
+
+    let kind_variants = variants.iter()
+        .map(|(v, _)| {
+            let vident = &v.ident;
+            let vattrs = v.attrs.iter()
+                .filter(|attr| attr.path.is_ident("doc")
+                        || (copy_attrs && attr.path.is_ident("enumeration")));
+
+            quote!(#( #vattrs )* #vident)
+        });
+
+    let arms = variants.iter()
+        .map(|(v, _)| {
+            let vident = &v.ident;
+            quote!(#name::#vident { .. } => #kind::#vident)
+        });
+
+    let doc = format!("The discriminant of [`{}`], without any of its fields.", name);
+
+    Ok(quote! {
+        #[doc = #doc]
+        #[derive(#( #derives ),*)]
+        #( #enum_attrs )*
+        #vis enum #kind {
+            #( #kind_variants, )*
+        }
+
+        impl #name {
+            /// Returns the discriminant of this value.
+            pub const fn kind(&self) -> #kind {
+                match *self {
+                    #( #arms, )*
+                }
+            }
+        }
+    })
+}
@@ -0,0 +1,206 @@
+use std::fmt;
+
+use proc_macro2::{Span, TokenStream};
+
+/// A stable identifier for each kind of error reported by the derives.
+///
+/// Codes are printed alongside the error message. Once assigned, a code is never reused for a
+/// different kind of error, so the text of a message can change without breaking tooling which
+/// matches on the code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCode {
+    /// The derive was applied to a `struct` or `union`.
+    NotAnEnum,
+
+    /// An `#[enumeration(...)]` or `#[repr(...)]` attribute could not be parsed.
+    MalformedAttribute,
+
+    /// A variant which is not skipped has fields.
+    VariantHasFields,
+
+    /// An argument to `#[enumeration(...)]` is not recognized.
+    UnknownAttribute,
+
+    /// An argument to `#[enumeration(...)]` was used on an enum when it is only valid on a variant
+    /// (or vice versa).
+    MisplacedAttribute,
+
+    /// An argument which may only appear once was specified multiple times.
+    DuplicateAttribute,
+
+    /// The argument to `rename_all` is not a known rename rule.
+    InvalidRenameRule,
+
+    /// The derive requires a primitive `#[repr(...)]`, but none was specified.
+    MissingRepr,
+
+    /// More than one primitive `#[repr(...)]` was specified.
+    MultipleRepr,
+
+    /// The derive needs the value of each discriminant, but one was not an integer literal.
+    UnknownDiscriminant,
+
+    /// A variant is missing from `assert_discriminants`, or is listed more than once.
+    UnassertedDiscriminant,
+
+    /// No variant is marked with `#[enumeration(default)]`.
+    MissingDefault,
+
+    /// The variant marked with `#[enumeration(other)]` has more than one field, or named fields.
+    InvalidOtherVariant,
+
+    /// The enum has too many variants to fit in the largest supported integer type.
+    TooManyVariants,
+
+    /// The weights given with `#[enumeration(weight = N)]` sum to zero or overflow a `u64`.
+    #[cfg_attr(not(feature = "rand"), allow(dead_code))]
+    InvalidTotalWeight,
+
+    /// Every variant is skipped (or there are none), but the derive needs to be able to construct
+    /// one.
+    NoVariants,
+
+    /// An unskipped variant has no `#[enumeration(char = '...')]`.
+    MissingChar,
+
+    /// Two variants have the same `#[enumeration(char = '...')]`.
+    DuplicateChar,
+
+    /// Two variants have names which hash to the same ID.
+    IdCollision,
+
+    /// The enum does not have exactly two variants, one for `true` and one for `false`.
+    InvalidBoolEnum,
+
+    /// The discriminants of the enum do not form a single, increasing run.
+    NonContiguousDiscriminants,
+
+    /// A variant has no `#[enumeration(order = N)]`.
+    MissingOrder,
+
+    /// Two variants have the same `#[enumeration(order = N)]`.
+    DuplicateOrder,
+
+    /// A variant has no `#[enumeration(value = ...)]`.
+    MissingValue,
+
+    /// Two variants have `#[enumeration(value = ...)]` literals of different types.
+    MismatchedValueTypes,
+
+    /// Two variants have the same name or alias, after `rename`, `rename_all` and the like are
+    /// applied.
+    DuplicateName,
+
+    /// The enum is marked with `#[enumeration(no_panic)]`, but an attribute requires the parser to
+    /// call code which may panic.
+    MayPanic,
+
+    /// The file given with `#[enumeration(aliases_file = "...")]` could not be read, or names a
+    /// variant which does not exist.
+    InvalidAliasesFile,
+}
+
+impl ErrorCode {
+    pub fn as_str(&self) -> &'static str {
+        use self::ErrorCode::*;
+
+        match self {
+            NotAnEnum => "EU0001",
+            MalformedAttribute => "EU0002",
+            VariantHasFields => "EU0003",
+            UnknownAttribute => "EU0004",
+            MisplacedAttribute => "EU0005",
+            DuplicateAttribute => "EU0006",
+            InvalidRenameRule => "EU0007",
+            MissingRepr => "EU0008",
+            MultipleRepr => "EU0009",
+            UnknownDiscriminant => "EU0010",
+            UnassertedDiscriminant => "EU0011",
+            MissingDefault => "EU0012",
+            InvalidOtherVariant => "EU0013",
+            TooManyVariants => "EU0014",
+            InvalidTotalWeight => "EU0015",
+            NoVariants => "EU0016",
+            MissingChar => "EU0017",
+            DuplicateChar => "EU0018",
+            IdCollision => "EU0019",
+            InvalidBoolEnum => "EU0020",
+            NonContiguousDiscriminants => "EU0021",
+            MissingOrder => "EU0022",
+            DuplicateOrder => "EU0023",
+            MissingValue => "EU0024",
+            MismatchedValueTypes => "EU0025",
+            DuplicateName => "EU0026",
+            MayPanic => "EU0027",
+            InvalidAliasesFile => "EU0028",
+        }
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// An error message tagged with a stable [`ErrorCode`].
+///
+/// If a `span` is given, the error will point to it. Otherwise it will point to the derive
+/// attribute.
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub code: ErrorCode,
+    pub message: String,
+    pub span: Option<Span>,
+}
+
+pub type Fallible<T> = Result<T, Diagnostic>;
+
+impl Diagnostic {
+    pub fn new(code: ErrorCode, message: String) -> Self {
+        Diagnostic { code, message, span: None }
+    }
+
+    pub fn with_span(mut self, span: Span) -> Self {
+        self.span = Some(span);
+        self
+    }
+
+    /// Returns a `compile_error!` invocation which reports this diagnostic.
+    pub fn to_compile_error(&self) -> TokenStream {
+        let span = self.span.unwrap_or_else(Span::call_site);
+        syn::Error::new(span, self).to_compile_error()
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.code, self.message)
+    }
+}
+
+/// Constructs a `Diagnostic` from an `ErrorCode` variant and a format string.
+macro_rules! format_diag {
+    ($code:ident, $msg:literal $( , $args:expr )* $(,)?) => {
+        $crate::error::Diagnostic::new(
+            $crate::error::ErrorCode::$code,
+            format!($msg, $($args),*),
+        )
+    }
+}
+
+/// Returns a `Diagnostic` from the current function.
+macro_rules! bail_diag {
+    ($code:ident, $msg:literal $( , $args:expr )* $(,)?) => {
+        return Err(format_diag!($code, $msg, $($args),*))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn display() {
+        let err = format_diag!(VariantHasFields, "Variant {} cannot have fields", "Foo");
+        assert_eq!(err.to_string(), "EU0003: Variant Foo cannot have fields");
+    }
+}
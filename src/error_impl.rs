@@ -0,0 +1,54 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::attr::{Enum, ErrorList};
+use crate::name::{format_arm, message};
+
+pub fn derive(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let name = input.name;
+
+    let mut display_arms = vec![];
+    let mut source_arms = vec![];
+    for (v, attrs) in input.variants.iter() {
+        let vident = &v.ident;
+        match &attrs.format {
+            Some(fmt) => display_arms.push(format_arm(name, v, fmt)),
+            None => {
+                let message = message(input, v, attrs);
+                display_arms.push(quote!(#name::#vident { .. } => f.pad(#message)));
+            }
+        }
+
+        if let Some(member) = &attrs.source {
+            source_arms.push(quote! {
+                #name::#vident { #member: ref source, .. } => Some(source)
+            });
+        }
+    }
+
+    // Avoid an unreachable pattern when every variant has a source.
+    let no_source = if source_arms.len() < input.variants.len() {
+        quote!(_ => None,)
+    } else {
+        TokenStream::new()
+    };
+
+    Ok(quote! {
+        impl ::std::fmt::Display for #name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                match *self {
+                    #( #display_arms, )*
+                }
+            }
+        }
+
+        impl ::std::error::Error for #name {
+            fn source(&self) -> Option<&(dyn ::std::error::Error + 'static)> {
+                match *self {
+                    #( #source_arms, )*
+                    #no_source
+                }
+            }
+        }
+    })
+}
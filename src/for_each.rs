@@ -0,0 +1,28 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+use crate::attr::{Enum, ErrorList, RenameRule};
+
+pub fn derive(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let name = input.name;
+
+    let snake = RenameRule::snake_case().apply_to_variant(&name.to_string());
+    let macro_name = format_ident!("for_each_{}", snake);
+
+    let calls = input.variants.iter()
+        .filter(|(_, attrs)| !attrs.skip)
+        .map(|(v, attrs)| {
+            let vident = &v.ident;
+            let s = input.attrs.display_name(v, attrs);
+            quote!($callback!(#vident, #s);)
+        });
+
+    Ok(quote! {
+        #[allow(unused_macros)]
+        macro_rules! #macro_name {
+            ($callback:ident) => {
+                #( #calls )*
+            };
+        }
+    })
+}
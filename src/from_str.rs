@@ -1,45 +1,291 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
+use std::collections::btree_map::Entry;
+use std::iter;
 
-use failure::format_err;
 use proc_macro2::TokenStream;
-use quote::quote;
+use quote::{quote, ToTokens};
 
-use crate::attr::{Enum, ErrorList};
-use enum_utils_from_str::{Case, StrMapFunc};
+use crate::attr::{CaseFolding, Enum, ErrorList, Qualified};
+use enum_utils_from_str::{Backend, Case, StrMapFunc};
 
-struct FromStrImpl {
-    nocase: bool,
-    enum_name: syn::Ident,
-    variants: BTreeMap<String, syn::Ident>,
+/// The names of the variants in a single locale, keyed by how they are matched and their
+/// normalized form, along with the variant they name.
+type LocalizedNames = BTreeMap<(CaseFolding, String), (String, syn::Ident)>;
+
+pub struct FromStrImpl {
+    from_bytes: bool,
+    from_cstr: bool,
+    try_from_str: bool,
+    canonical_name: bool,
+    from_name: bool,
+    parse_detailed: bool,
+    parse_error: bool,
+    trim: bool,
+    assume_lowercase: bool,
+    pub dash_underscore: bool,
+    from_prefix: bool,
+    parse_fn: Option<(syn::Visibility, syn::Ident)>,
+    from_cstr_ptr: Option<(syn::Visibility, syn::Ident)>,
+    parser: Backend,
+    on_miss: Option<syn::Path>,
+
+    /// An expression constructing the catch-all variant from the input string `s`.
+    other: Option<TokenStream>,
+    pub enum_name: syn::Ident,
+
+    /// The variant matched by each name or alias, and whether it is matched case-insensitively.
+    variants: BTreeMap<String, (syn::Ident, CaseFolding)>,
+
+    /// The canonical name of the variant matched by each name or alias.
+    canonical: BTreeMap<String, (String, CaseFolding)>,
+
+    /// Each alias which is marked `deprecated`.
+    deprecated: BTreeSet<String>,
+
+    /// The variant matched by each alias given with `alias_bytes` which is not valid UTF-8.
+    byte_aliases: BTreeMap<Vec<u8>, syn::Ident>,
+
+    /// The names given with `locale(...)` in each locale.
+    localized: BTreeMap<String, LocalizedNames>,
+
+    /// The prefix of each `parse_tail` variant, which is followed by the string representation of
+    /// its field.
+    tails: Vec<(String, syn::Ident, CaseFolding)>,
+
+    /// A description of the expected input, used in error messages instead of listing every name.
+    pub expecting: Option<String>,
+
+    /// The canonical name of each unskipped variant, in declaration order.
+    #[cfg_attr(not(any(feature = "postgres-types", feature = "schemars", feature = "serde",
+                       feature = "utoipa")),
+               allow(dead_code))]
+    pub names: Vec<String>,
 }
 
 impl FromStrImpl {
-    pub fn parse(input: &syn::DeriveInput) -> Result<Self, ErrorList> {
-        let Enum { name, attrs: enum_attrs, variants, .. } = Enum::parse(input)?;
+    pub fn for_enum(input: &Enum) -> Result<Self, ErrorList> {
+        let Enum { name, attrs: enum_attrs, variants, discriminants, .. } = input;
+        let discriminants = match discriminants {
+            Some(ds) if enum_attrs.numeric_aliases => Some(ds),
+            None if enum_attrs.numeric_aliases => bail_list!(UnknownDiscriminant,
+                "All discriminants must be integer literals to use \"numeric_aliases\""),
+            _ => None,
+        };
 
         let mut errors = ErrorList::default();
         let mut name_map = BTreeMap::default();
-        for (v, attrs) in variants.iter() {
+
+        // Each name (or `parse_tail` prefix) after case folding and dash normalization, and the
+        // variant and name it came from. Exact matches take precedence over case-insensitive ones,
+        // so only names which are matched the same way can be ambiguous.
+        let mut folded = BTreeMap::new();
+        let mut check_folded = |is_tail: bool, s: &str, case, v: &syn::Ident| {
+            let key = (is_tail, case, normalize(s, case, enum_attrs.dash_underscore));
+            match folded.entry(key) {
+                Entry::Vacant(e) => {
+                    e.insert((v.clone(), s.to_owned()));
+                    None
+                }
+
+                Entry::Occupied(e) => {
+                    let (prev, prev_s): &(syn::Ident, String) = e.get();
+                    if prev == v {
+                        return None;
+                    }
+
+                    Some(format_diag!(DuplicateName, "Variants `{}` and `{}` have ambiguous names \"{}\" and \"{}\"",
+                                      prev, v, prev_s, s)
+                        .with_span(v.span()))
+                }
+            }
+        };
+        // Returns each form of `s` which is accepted: `s` itself unless `qualified = "only"` is set,
+        // then `s` qualified with the name of the enum if `qualified` is set. The first is canonical.
+        let qualify = |s: String| match enum_attrs.qualified {
+            None => vec![s],
+            Some(Qualified::Only) => vec![format!("{}::{}", name, s)],
+            Some(Qualified::Optional) => {
+                let qualified = format!("{}::{}", name, s);
+                vec![s, qualified]
+            }
+        };
+
+        let mut canonical = BTreeMap::default();
+        let mut byte_aliases = BTreeMap::new();
+        let mut deprecated = BTreeSet::new();
+        let mut localized = BTreeMap::new();
+        let mut other = None;
+        let mut names = vec![];
+        let mut tails = vec![];
+        for (i, (v, attrs)) in variants.iter().enumerate() {
+            if !attrs.locales.is_empty() && v.fields != syn::Fields::Unit {
+                errors.push_back(format_diag!(VariantHasFields, "A variant with localized names cannot have fields")
+                    .with_span(v.ident.span()));
+            }
+
+            if attrs.other {
+                let vident = &v.ident;
+                let ctor = match &v.fields {
+                    syn::Fields::Unit => Some(quote!(#name::#vident)),
+                    syn::Fields::Unnamed(fields) if fields.unnamed.len() == 1 =>
+                        Some(quote!(#name::#vident(::std::convert::From::from(s)))),
+                    _ => {
+                        errors.push_back(format_diag!(InvalidOtherVariant,
+                                                      "The `other` variant must have zero fields or a single unnamed field")
+                            .with_span(vident.span()));
+                        None
+                    }
+                };
+
+                if other.is_some() {
+                    errors.push_back(format_diag!(DuplicateAttribute, "Only one variant can be marked with `#[enumeration(other)]`")
+                        .with_span(vident.span()));
+                }
+
+                other = ctor;
+            }
+
             if attrs.skip {
                 continue;
             }
 
+            let case = enum_attrs.variant_case(attrs);
+            if attrs.parse_tail {
+                match &v.fields {
+                    syn::Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                        let separator = attrs.separator.as_deref().unwrap_or("");
+                        let prefix = enum_attrs.variant_name(v, attrs) + separator;
+                        for prefix in qualify(prefix) {
+                            match tails.iter().find(|(p, _, _)| *p == prefix) {
+                                Some((_, prev, _)) => {
+                                    errors.push_back(format_diag!(DuplicateName, "Variants `{}` and `{}` both have the prefix \"{}\"",
+                                                                  prev, v.ident, prefix)
+                                        .with_span(v.ident.span()));
+                                }
+
+                                None => {
+                                    if let Some(e) = check_folded(true, &prefix, case, &v.ident) {
+                                        errors.push_back(e);
+                                    }
+
+                                    tails.push((prefix, v.ident.clone(), case));
+                                }
+                            }
+                        }
+                    }
+
+                    _ => errors.push_back(format_diag!(VariantHasFields,
+                                                       "A `parse_tail` variant must have a single unnamed field")
+                        .with_span(v.ident.span())),
+                }
+
+                continue;
+            }
+
             if v.fields != syn::Fields::Unit {
-                errors.push_back(format_err!("An (unskipped) variant cannot have fields"));
+                errors.push_back(format_diag!(VariantHasFields, "An (unskipped) variant cannot have fields")
+                    .with_span(v.ident.span()));
             }
 
-            if let Some(name) = &attrs.rename {
-                name_map.insert(name.clone(), v.ident.clone());
-            } else if let Some(rename_rule) = &enum_attrs.rename_rule {
-                let s = v.ident.to_string();
-                name_map.insert(rename_rule.apply_to_variant(&*s), v.ident.clone());
-            } else {
-                let s = v.ident.to_string();
-                name_map.insert(s, v.ident.clone());
+            let mut name = enum_attrs.variant_name(v, attrs);
+            let mut aliases = enum_attrs.variant_aliases(v, attrs);
+            let variant_deprecated: BTreeSet<_> = attrs.deprecated_aliases.iter()
+                .cloned()
+                .flat_map(&qualify)
+                .collect();
+
+            if enum_attrs.qualified.is_some() {
+                let mut qualified = qualify(name).into_iter();
+                name = qualified.next().unwrap();
+                aliases = qualified.chain(aliases.into_iter().flat_map(&qualify)).collect();
+            }
+
+            if let Some(ds) = discriminants {
+                aliases.insert(ds[i].to_string());
             }
 
-            for alias in &attrs.aliases {
-                name_map.insert(alias.clone(), v.ident.clone());
+            for s in iter::once(name.clone()).chain(aliases) {
+                match name_map.entry(s) {
+                    Entry::Vacant(e) => {
+                        if let Some(e) = check_folded(false, e.key(), case, &v.ident) {
+                            errors.push_back(e);
+                        }
+
+                        if variant_deprecated.contains(e.key()) {
+                            deprecated.insert(e.key().clone());
+                        }
+
+                        canonical.insert(e.key().clone(), (name.clone(), case));
+                        e.insert((v.ident.clone(), case));
+                    }
+
+                    Entry::Occupied(e) => {
+                        let (prev, _): &(syn::Ident, _) = e.get();
+                        errors.push_back(format_diag!(DuplicateName, "Variants `{}` and `{}` can both be parsed from \"{}\"",
+                                                      prev, v.ident, e.key())
+                            .with_span(v.ident.span()));
+                    }
+                }
+            }
+
+            for b in &attrs.alias_bytes {
+                match byte_aliases.entry(b.clone()) {
+                    Entry::Vacant(e) => {
+                        e.insert(v.ident.clone());
+                    }
+
+                    Entry::Occupied(e) => {
+                        errors.push_back(format_diag!(DuplicateName, "Variants `{}` and `{}` can both be parsed from b\"{}\"",
+                                                      e.get(), v.ident, b.escape_ascii())
+                            .with_span(v.ident.span()));
+                    }
+                }
+            }
+
+            for (locale, s) in &attrs.locales {
+                let names: &mut LocalizedNames = localized.entry(locale.clone()).or_default();
+                match names.entry((case, normalize(s, case, enum_attrs.dash_underscore))) {
+                    Entry::Vacant(e) => {
+                        e.insert((s.clone(), v.ident.clone()));
+                    }
+
+                    Entry::Occupied(e) => {
+                        let (prev_s, prev) = e.get();
+                        errors.push_back(format_diag!(DuplicateName, "Variants `{}` and `{}` have ambiguous names \"{}\" and \"{}\" in locale \"{}\"",
+                                                      prev, v.ident, prev_s, s, locale)
+                            .with_span(v.ident.span()));
+                    }
+                }
+            }
+
+            names.push(name);
+        }
+
+        if enum_attrs.no_panic {
+            let mut may_panic = |attr: &str| errors.push_back(format_diag!(MayPanic,
+                "{} calls code which may panic, so it cannot be used with \"no_panic\"", attr));
+
+            if enum_attrs.on_miss.is_some() {
+                may_panic("\"on_miss\"");
+            }
+
+            if !tails.is_empty() {
+                may_panic("\"parse_tail\"");
+            }
+
+            if variants.iter().any(|(v, attrs)| attrs.other && v.fields != syn::Fields::Unit) {
+                may_panic("\"other\"");
+            }
+
+            // `ParseError::new` and Unicode case folding both allocate.
+            if enum_attrs.parse_error {
+                may_panic("\"parse_error\"");
+            }
+
+            let unicode = variants.iter()
+                .any(|(_, attrs)| !attrs.skip && enum_attrs.variant_case(attrs) == CaseFolding::Unicode);
+            if unicode {
+                may_panic("Unicode case folding");
             }
         }
 
@@ -48,32 +294,727 @@ impl FromStrImpl {
         }
 
         Ok(FromStrImpl {
-            nocase: enum_attrs.nocase,
-            enum_name: name.clone(),
+            from_bytes: enum_attrs.from_bytes,
+            from_cstr: enum_attrs.from_cstr,
+            try_from_str: enum_attrs.try_from_str,
+            canonical_name: enum_attrs.canonical_name,
+            from_name: enum_attrs.from_name,
+            parse_detailed: enum_attrs.parse_detailed,
+            parse_error: enum_attrs.parse_error,
+            trim: enum_attrs.trim,
+            assume_lowercase: enum_attrs.assume_lowercase,
+            dash_underscore: enum_attrs.dash_underscore,
+            from_prefix: enum_attrs.from_prefix,
+            parse_fn: enum_attrs.parse_fn.clone(),
+            from_cstr_ptr: enum_attrs.from_cstr_ptr.clone(),
+            parser: enum_attrs.parser.unwrap_or_default(),
+            expecting: enum_attrs.expecting.clone(),
+            on_miss: enum_attrs.on_miss.clone(),
+            other,
+            enum_name: (*name).clone(),
             variants: name_map,
+            canonical,
+            byte_aliases,
+            localized,
+            deprecated,
+            tails,
+            names,
         })
     }
 }
 
-pub fn derive(ast: &syn::DeriveInput) -> Result<TokenStream, ErrorList> {
-    let FromStrImpl { nocase, enum_name, variants } = FromStrImpl::parse(ast)?;
+impl FromStrImpl {
+    /// Returns every string accepted by the parser: the canonical names in declaration order,
+    /// followed by any aliases.
+    #[cfg(any(feature = "schemars", feature = "utoipa"))]
+    pub fn accepted(&self) -> Vec<&str> {
+        self.names.iter()
+            .map(String::as_str)
+            .chain(self.aliases())
+            .collect()
+    }
+
+    /// Returns every alias accepted by the parser which is not also a canonical name.
+    #[cfg(any(feature = "schemars", feature = "utoipa"))]
+    pub fn aliases(&self) -> impl Iterator<Item = &str> {
+        self.variants.keys()
+            .filter(move |s| !self.names.contains(s))
+            .map(String::as_str)
+    }
+
+    /// Returns each name or alias accepted by the parser, along with the variant it names and how
+    /// it is matched.
+    pub fn entries(&self) -> impl Iterator<Item = (&str, &syn::Ident, CaseFolding)> {
+        self.variants.iter().map(|(s, (v, case))| (s.as_str(), v, *case))
+    }
+
+    /// Returns `true` if there is a catch-all variant which accepts any string.
+    #[cfg(any(feature = "postgres-types", feature = "schemars", feature = "utoipa"))]
+    pub fn has_other(&self) -> bool {
+        self.other.is_some()
+    }
+
+    /// Returns an expression of type `Option<Self>` which looks up the variant named by `s: &str`.
+    ///
+    /// If there is no such variant, the `on_miss` callback is invoked, and the catch-all variant
+    /// is constructed if there is one. If `trim` is set, whitespace is stripped from `s` first.
+    pub fn lookup(&self) -> TokenStream {
+        let FromStrImpl { on_miss, other, .. } = self;
+
+        let trie = self.trie();
+
+        let on_miss = on_miss.as_ref().map(|callback| quote!(#callback(s);));
+        let miss = match other {
+            Some(ctor) => quote!(Some(#ctor)),
+            None => quote!(None),
+        };
+
+        let (tail_fn, miss) = match self.tail_fn() {
+            Some(tail_fn) => (tail_fn, quote! {
+                match _parse_tail(s) {
+                    Some(v) => Some(v),
+                    None => {
+                        #on_miss
+                        #miss
+                    }
+                }
+            }),
+
+            None => (TokenStream::new(), quote! {
+                #on_miss
+                #miss
+            }),
+        };
+
+        let trim = self.trim_str();
+        quote! {
+            {
+                #trie
+                #tail_fn
+                #trim
+                match _parse(s.as_bytes()) {
+                    Some(v) => Some(v),
+                    None => { #miss }
+                }
+            }
+        }
+    }
+
+    /// Returns an expression of type `Option<Self>` which looks up the variant named by
+    /// `b: &[u8]`.
+    ///
+    /// Names are matched without checking that `b` is valid UTF-8. Only if there is no match and
+    /// the input is needed as a `&str` (by `parse_tail`, `on_miss` or the catch-all variant) is
+    /// it converted and passed to [`lookup`](Self::lookup).
+    pub fn bytes_lookup(&self) -> TokenStream {
+        let trie = self.trie();
+        let fallback = if self.tails.is_empty() && self.on_miss.is_none() && self.other.is_none() {
+            quote!(None)
+        } else {
+            let lookup = self.lookup();
+            quote! {
+                match ::std::str::from_utf8(b) {
+                    Ok(s) => #lookup,
+                    Err(_) => None,
+                }
+            }
+        };
+
+        let trim = if self.trim {
+            quote! {
+                let start = b.iter().position(|c| !c.is_ascii_whitespace()).unwrap_or(b.len());
+                let end = b.iter().rposition(|c| !c.is_ascii_whitespace()).map_or(start, |i| i + 1);
+                let b = b.get(start..end).unwrap_or_default();
+            }
+        } else {
+            TokenStream::new()
+        };
+
+        quote! {
+            {
+                #trie
+                #trim
+                match _parse(b) {
+                    Some(v) => Some(v),
+                    None => #fallback,
+                }
+            }
+        }
+    }
+
+    /// Returns a function, `from_prefix`, which finds the longest name or alias which is a prefix
+    /// of `s: &[u8]`, and returns its variant along with the length of the match.
+    fn prefix_fn(&self) -> TokenStream {
+        let trie = self.trie();
 
-    let mut trie = StrMapFunc::new("_parse", &enum_name.to_string());
-    let case = if nocase { Case::Insensitive } else { Case::Sensitive };
-    trie.case(case);
+        // Case folding can change the length of a name, so every length must be tried.
+        let lens = if self.variants.values().any(|(_, case)| *case == CaseFolding::Unicode) {
+            quote!((0..=s.len()).rev())
+        } else {
+            let mut lens: Vec<_> = self.variants.keys().map(String::len)
+                .chain(self.byte_aliases.keys().map(Vec::len))
+                .collect();
+            lens.sort_unstable_by(|a, b| b.cmp(a));
+            lens.dedup();
+            quote!([#( #lens ),*])
+        };
 
-    for (alias, variant) in variants {
-        let path = quote!(#enum_name::#variant);
-        trie.entry(alias.as_str(), path);
+        quote! {
+            fn from_prefix(s: &[u8]) -> Option<(Self, usize)> {
+                #trie
+
+                for len in #lens {
+                    if let Some(v) = s.get(..len).and_then(_parse) {
+                        return Some((v, len));
+                    }
+                }
+
+                None
+            }
+        }
     }
 
+    /// Returns a statement which strips leading and trailing ASCII whitespace from `s: &str` if
+    /// `trim` is set.
+    fn trim_str(&self) -> TokenStream {
+        if self.trim {
+            quote!(let s = s.trim_matches(|c: char| c.is_ascii_whitespace());)
+        } else {
+            TokenStream::new()
+        }
+    }
+
+    /// Returns a function, `_parse`, which matches the name or alias of each variant with a trie.
+    fn trie(&self) -> TokenStream {
+        let FromStrImpl { enum_name, variants, byte_aliases, .. } = self;
+
+        // Byte aliases are not valid UTF-8, so they are always matched exactly.
+        let entries = variants.iter()
+            .map(|(alias, (variant, case))| (alias.as_bytes(), *case, variant))
+            .chain(byte_aliases.iter().map(|(alias, variant)| (alias.as_slice(), CaseFolding::None, variant)))
+            .map(|(alias, case, variant)| (alias, case, quote!(#enum_name::#variant)));
+
+        self.trie_fn("_parse", &enum_name.to_string(), entries)
+    }
+
+    /// Returns a function, `_parse_tail`, which matches the prefix of each `parse_tail` variant
+    /// with a trie and parses the remainder of the string as its field.
+    ///
+    /// Longer prefixes are tried first, so a prefix is never shadowed by a shorter one.
+    fn tail_fn(&self) -> Option<TokenStream> {
+        if self.tails.is_empty() {
+            return None;
+        }
+
+        let enum_name = &self.enum_name;
+        let trie = self.trie_fn("_parse_prefix", "usize", self.tails.iter()
+            .enumerate()
+            .map(|(i, (prefix, _, case))| (prefix.as_bytes(), *case, i)));
+
+        let mut lens = vec![];
+        let mut arms = vec![];
+        for (i, (prefix, variant, _)) in self.tails.iter().enumerate() {
+            lens.push(prefix.len());
+            arms.push(quote! {
+                Some(#i) => if let Ok(v) = tail.parse() {
+                    return Some(#enum_name::#variant(v));
+                }
+            });
+        }
+
+        lens.sort_unstable_by(|a, b| b.cmp(a));
+        lens.dedup();
+
+        // Case folding can change the length of a prefix, so every split must be tried.
+        let lens = if self.tails.iter().any(|(_, _, case)| *case == CaseFolding::Unicode) {
+            quote! {
+                s.char_indices().map(|(i, _)| i).chain(::std::iter::once(s.len())).rev()
+            }
+        } else {
+            quote!([#( #lens ),*])
+        };
+
+        Some(quote! {
+            fn _parse_tail(s: &str) -> Option<#enum_name> {
+                #trie
+
+                for len in #lens {
+                    let (head, tail) = match (s.get(..len), s.get(len..)) {
+                        (Some(head), Some(tail)) => (head, tail),
+                        _ => continue,
+                    };
+
+                    match _parse_prefix(head.as_bytes()) {
+                        #( #arms, )*
+                        _ => {}
+                    }
+                }
+
+                None
+            }
+        })
+    }
+
+    /// Returns a function, `fn_name`, which maps each key to its value with a trie.
+    ///
+    /// Keys are stored in a separate trie for each kind of case folding. If more than one is
+    /// needed, they are tried in order, so an exact match takes precedence over a case-insensitive
+    /// one.
+    ///
+    /// If `dash_underscore_insensitive` is set, `-` and `_` are interchangeable in every trie. If
+    /// `assume_lowercase` is set, ASCII case-insensitive keys are lowercased and compared exactly.
+    /// Despite the name, the code generated for each group follows the `parser` attribute.
+    fn trie_fn<'a, V>(&self,
+                      fn_name: &str,
+                      ret_ty: &str,
+                      entries: impl IntoIterator<Item = (&'a [u8], CaseFolding, V)>) -> TokenStream
+        where V: ToTokens,
+    {
+        let mut groups: BTreeMap<CaseFolding, Vec<(Vec<u8>, V)>> = BTreeMap::new();
+        for (k, case, v) in entries {
+            let k = match case {
+                CaseFolding::Ascii if self.assume_lowercase => k.to_ascii_lowercase(),
+                CaseFolding::Unicode => {
+                    let k = std::str::from_utf8(k).expect("Only names and string aliases are case folded");
+                    fold_case(k).into_bytes()
+                }
+                _ => k.to_owned(),
+            };
+
+            groups.entry(case).or_default().push((k, v));
+        }
+
+        let trie = |name: &str, case, entries: Vec<(Vec<u8>, V)>| {
+            let mut trie = StrMapFunc::new(name, ret_ty);
+            trie.case(case);
+            trie.dash_underscore_insensitive(self.dash_underscore);
+            trie.backend(self.parser);
+            for (k, v) in entries {
+                trie.bytes_entry(&k, v);
+            }
+
+            trie.into_token_stream()
+        };
+
+        let ret_ty: TokenStream = ret_ty.parse().unwrap();
+        let single = groups.len() <= 1;
+        let mut names = vec![];
+        let mut fns = vec![];
+        for (case, entries) in groups {
+            let name = match case {
+                _ if single => fn_name,
+                CaseFolding::None => "_case_sensitive",
+                CaseFolding::Ascii => "_case_insensitive",
+                CaseFolding::Unicode => "_case_folded",
+            };
+
+            let func = match case {
+                CaseFolding::None => trie(name, Case::Sensitive, entries),
+                // The input is already lowercase, so it can be compared exactly with the lowercased
+                // keys.
+                CaseFolding::Ascii if self.assume_lowercase => trie(name, Case::Sensitive, entries),
+                CaseFolding::Ascii => trie(name, Case::Insensitive, entries),
+
+                // Folded keys are all lowercase, so ASCII input can be matched without folding it.
+                CaseFolding::Unicode => {
+                    let ident = syn::Ident::new(name, proc_macro2::Span::call_site());
+                    let folded = trie("_folded", Case::Insensitive, entries);
+                    let fold_case_fn = fold_case_fn();
+                    quote! {
+                        fn #ident(s: &[u8]) -> Option<#ret_ty> {
+                            #folded
+                            #fold_case_fn
+
+                            if s.is_ascii() {
+                                return _folded(s);
+                            }
+
+                            let s = ::std::str::from_utf8(s).ok()?;
+                            let s: ::std::string::String = s.chars().map(_fold_case).collect();
+                            _folded(s.as_bytes())
+                        }
+                    }
+                }
+            };
+
+            names.push(syn::Ident::new(name, proc_macro2::Span::call_site()));
+            fns.push(func);
+        }
+
+        match names.split_first() {
+            None => trie(fn_name, Case::Sensitive, vec![]),
+            Some(_) if single => fns.remove(0),
+            Some((first, rest)) => {
+                let fn_name = syn::Ident::new(fn_name, proc_macro2::Span::call_site());
+                quote! {
+                    fn #fn_name(s: &[u8]) -> Option<#ret_ty> {
+                        #( #fns )*
+                        #first(s) #( .or_else(|| #rest(s)) )*
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Returns the string which `s` is compared as, under the given case folding and with `-`
+/// replaced by `_` if `dash_underscore` is set.
+fn normalize(s: &str, case: CaseFolding, dash_underscore: bool) -> String {
+    let s = match case {
+        CaseFolding::None => s.to_owned(),
+        CaseFolding::Ascii => s.to_ascii_lowercase(),
+        CaseFolding::Unicode => fold_case(s),
+    };
+
+    if dash_underscore {
+        s.replace('-', "_")
+    } else {
+        s
+    }
+}
+
+/// Returns the simple case folding of a character.
+///
+/// This maps each character to the lowercase form of its uppercase form, so that characters like
+/// `'ς'` and `'σ'` are equivalent. Characters whose uppercase or lowercase form is more than one
+/// character (e.g. `'ß'`) are left unchanged. This must agree with [`fold_case_fn`].
+fn fold_char(c: char) -> char {
+    fn single(mut it: impl Iterator<Item = char>) -> Option<char> {
+        match (it.next(), it.next()) {
+            (Some(c), None) => Some(c),
+            _ => None,
+        }
+    }
+
+    let upper = single(c.to_uppercase()).unwrap_or(c);
+    single(upper.to_lowercase()).unwrap_or(upper)
+}
+
+fn fold_case(s: &str) -> String {
+    s.chars().map(fold_char).collect()
+}
+
+/// Returns a function, `_fold_case`, which computes the simple case folding of a character at
+/// runtime in the same way as [`fold_char`].
+pub fn fold_case_fn() -> TokenStream {
+    quote! {
+        fn _fold_case(c: char) -> char {
+            fn single(mut it: impl ::std::iter::Iterator<Item = char>) -> Option<char> {
+                match (it.next(), it.next()) {
+                    (Some(c), None) => Some(c),
+                    _ => None,
+                }
+            }
+
+            let upper = single(c.to_uppercase()).unwrap_or(c);
+            single(upper.to_lowercase()).unwrap_or(upper)
+        }
+    }
+}
+
+pub fn derive(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let imp = FromStrImpl::for_enum(input)?;
+    let enum_name = &imp.enum_name;
+    let inline = input.attrs.inline_attr();
+    // If there is a `from_name` method, `FromStr` forwards to it so the trie is only emitted once.
+    let (from_name, lookup) = if imp.from_name {
+        let lookup = imp.lookup();
+        (quote! {
+            impl #enum_name {
+                #inline
+                fn from_name(s: &str) -> Option<Self> {
+                    #lookup
+                }
+            }
+        }, quote!(Self::from_name(s)))
+    } else {
+        (TokenStream::new(), imp.lookup())
+    };
+
+    let try_from_bytes = if imp.from_bytes {
+        // Reuse the public parse function if there is one, so the trie is only emitted once.
+        let bytes_lookup = match &imp.parse_fn {
+            Some((_, ident)) => quote!(Self::#ident(b)),
+            None => imp.bytes_lookup(),
+        };
+
+        quote! {
+            impl<'a> ::std::convert::TryFrom<&'a [u8]> for #enum_name {
+                type Error = ();
+
+                #inline
+                fn try_from(b: &'a [u8]) -> Result<Self, Self::Error> {
+                    match #bytes_lookup {
+                        Some(v) => Ok(v),
+                        None => Err(()),
+                    }
+                }
+            }
+        }
+    } else {
+        TokenStream::new()
+    };
+
+    // Forward to another function which takes bytes if there is one, so the trie is only emitted
+    // once.
+    let try_from_cstr = if imp.from_cstr || imp.from_cstr_ptr.is_some() {
+        let bytes_lookup = match &imp.parse_fn {
+            Some((_, ident)) => quote!(Self::#ident(b)),
+            None if imp.from_bytes => quote!(<Self as ::std::convert::TryFrom<&[u8]>>::try_from(b).ok()),
+            None => imp.bytes_lookup(),
+        };
+
+        quote! {
+            impl<'a> ::std::convert::TryFrom<&'a ::std::ffi::CStr> for #enum_name {
+                type Error = ();
+
+                #inline
+                fn try_from(s: &'a ::std::ffi::CStr) -> Result<Self, Self::Error> {
+                    let b = s.to_bytes();
+                    match #bytes_lookup {
+                        Some(v) => Ok(v),
+                        None => Err(()),
+                    }
+                }
+            }
+        }
+    } else {
+        TokenStream::new()
+    };
+
+    let from_cstr_ptr = if let Some((vis, ident)) = &imp.from_cstr_ptr {
+        quote! {
+            impl #enum_name {
+                /// Parses a nul-terminated string, returning `None` if `ptr` is null or the string
+                /// does not name a variant.
+                ///
+                /// # Safety
+                ///
+                /// If `ptr` is not null, it must satisfy the requirements of
+                /// `std::ffi::CStr::from_ptr`.
+                #inline
+                #vis unsafe fn #ident(ptr: *const ::std::os::raw::c_char) -> Option<Self> {
+                    if ptr.is_null() {
+                        return None;
+                    }
+
+                    let s = ::std::ffi::CStr::from_ptr(ptr);
+                    <Self as ::std::convert::TryFrom<&::std::ffi::CStr>>::try_from(s).ok()
+                }
+            }
+        }
+    } else {
+        TokenStream::new()
+    };
+
+    let (err_ty, err) = if imp.parse_error {
+        let names = &imp.names;
+        (quote!(::enum_utils_traits::ParseError),
+         match &imp.expecting {
+             Some(e) => quote!(::enum_utils_traits::ParseError::new(s, &[#( #names ),*]).with_expecting(#e)),
+             None => quote!(::enum_utils_traits::ParseError::new(s, &[#( #names ),*])),
+         })
+    } else {
+        (quote!(()), quote!(()))
+    };
+
+    let from_prefix = if imp.from_prefix {
+        let prefix_fn = imp.prefix_fn();
+        quote! {
+            impl #enum_name {
+                #inline
+                #prefix_fn
+            }
+        }
+    } else {
+        TokenStream::new()
+    };
+
+    let parse_fn = if let Some((vis, ident)) = &imp.parse_fn {
+        let bytes_lookup = imp.bytes_lookup();
+        quote! {
+            impl #enum_name {
+                #inline
+                #vis fn #ident(b: &[u8]) -> Option<Self> {
+                    #bytes_lookup
+                }
+            }
+        }
+    } else {
+        TokenStream::new()
+    };
+
+    // These forward to `FromStr` so that the trie is only emitted once.
+    let try_from_str = if imp.try_from_str {
+        quote! {
+            impl<'a> ::std::convert::TryFrom<&'a str> for #enum_name {
+                type Error = #err_ty;
+
+                #inline
+                fn try_from(s: &'a str) -> Result<Self, Self::Error> {
+                    <Self as ::std::str::FromStr>::from_str(s)
+                }
+            }
+
+            impl ::std::convert::TryFrom<::std::string::String> for #enum_name {
+                type Error = #err_ty;
+
+                #inline
+                fn try_from(s: ::std::string::String) -> Result<Self, Self::Error> {
+                    <Self as ::std::str::FromStr>::from_str(&s)
+                }
+            }
+        }
+    } else {
+        TokenStream::new()
+    };
+
+    let canonical_name = if imp.canonical_name {
+        let trie = imp.trie_fn("_canonical", "&'static str", imp.canonical.iter()
+            .map(|(alias, (name, case))| (alias.as_bytes(), *case, name)));
+
+        let trim = imp.trim_str();
+        quote! {
+            impl #enum_name {
+                #inline
+                fn canonical_name(s: &str) -> Option<&'static str> {
+                    #trie
+                    #trim
+                    _canonical(s.as_bytes())
+                }
+            }
+        }
+    } else {
+        TokenStream::new()
+    };
+
+    let parse_detailed = if imp.parse_detailed {
+        let ret_ty = format!("({}, &'static str, bool)", enum_name);
+        let trie = imp.trie_fn("_parse_detailed", &ret_ty, imp.entries()
+            .map(|(alias, variant, case)| {
+                let deprecated = imp.deprecated.contains(alias);
+                (alias.as_bytes(), case, quote!((#enum_name::#variant, #alias, #deprecated)))
+            }));
+
+        let trim = imp.trim_str();
+        quote! {
+            impl #enum_name {
+                #inline
+                fn parse_detailed(s: &str) -> Option<(Self, &'static str, bool)> {
+                    #trie
+                    #trim
+                    _parse_detailed(s.as_bytes())
+                }
+            }
+        }
+    } else {
+        TokenStream::new()
+    };
+
+    // Every name is included so that one which is matched exactly takes precedence over a
+    // deprecated alias which is matched case-insensitively.
+    let is_deprecated_name = if !imp.deprecated.is_empty() {
+        let trie = imp.trie_fn("_is_deprecated", "bool", imp.entries()
+            .map(|(alias, _, case)| (alias.as_bytes(), case, imp.deprecated.contains(alias))));
+
+        let trim = imp.trim_str();
+        quote! {
+            impl #enum_name {
+                #[allow(dead_code)]
+                #inline
+                fn is_deprecated_name(s: &str) -> bool {
+                    #trie
+                    #trim
+                    _is_deprecated(s.as_bytes()).unwrap_or(false)
+                }
+            }
+        }
+    } else {
+        TokenStream::new()
+    };
+
+    let localized = if input.variants.iter().any(|(_, attrs)| !attrs.locales.is_empty()) {
+        let name_arms = input.variants.iter().map(|(v, attrs)| {
+            let vident = &v.ident;
+            let default = if attrs.skip {
+                vident.to_string()
+            } else {
+                input.attrs.display_name(v, attrs)
+            };
+
+            let locales = attrs.locales.keys();
+            let names = attrs.locales.values();
+            quote! {
+                #enum_name::#vident { .. } => match locale {
+                    #( #locales => #names, )*
+                    _ => #default,
+                }
+            }
+        });
+
+        let ret_ty = enum_name.to_string();
+        let parse_arms = imp.localized.iter().enumerate().map(|(i, (locale, names))| {
+            let fn_name = format!("_parse_{}", i);
+            let trie = imp.trie_fn(&fn_name, &ret_ty, names.iter()
+                .map(|((case, _), (s, variant))| (s.as_bytes(), *case, quote!(#enum_name::#variant))));
+
+            let fn_ident = syn::Ident::new(&fn_name, proc_macro2::Span::call_site());
+            quote! {
+                #locale => {
+                    #trie
+                    #fn_ident(s.as_bytes())
+                }
+            }
+        });
+
+        let trim = imp.trim_str();
+        quote! {
+            impl #enum_name {
+                #[allow(dead_code)]
+                #inline
+                fn name_in(&self, locale: &str) -> &'static str {
+                    match *self {
+                        #( #name_arms, )*
+                    }
+                }
+
+                #[allow(dead_code)]
+                #inline
+                fn parse_in(locale: &str, s: &str) -> Option<Self> {
+                    #trim
+                    match locale {
+                        #( #parse_arms, )*
+                        _ => None,
+                    }
+                }
+            }
+        }
+    } else {
+        TokenStream::new()
+    };
+
     Ok(quote!{
+        #try_from_bytes
+        #try_from_cstr
+        #from_cstr_ptr
+        #from_name
+        #parse_fn
+        #from_prefix
+        #try_from_str
+        #canonical_name
+        #parse_detailed
+        #is_deprecated_name
+        #localized
+
         impl ::std::str::FromStr for #enum_name {
-            type Err = ();
+            type Err = #err_ty;
 
+            #inline
             fn from_str(s: &str) -> Result<Self, Self::Err> {
-                #trie
-                _parse(s.as_bytes()).ok_or(())
+                match #lookup {
+                    Some(v) => Ok(v),
+                    None => Err(#err),
+                }
             }
         }
     })
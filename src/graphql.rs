@@ -0,0 +1,134 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::attr::{Enum, ErrorList};
+use crate::name::{doc_summary, name_table};
+
+pub fn derive(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let name = input.name;
+    let type_name = name.to_string();
+
+    let mut items = vec![];
+    let mut meta_values = vec![];
+    let mut value_arms = vec![];
+    for (v, s) in name_table(input)? {
+        let vident = &v.ident;
+        let s = match s {
+            Some(s) => s,
+            None => {
+                let msg = format!("`{}::{}` is skipped and cannot be a GraphQL value", name, vident);
+                value_arms.push(quote!(#name::#vident { .. } => panic!(#msg)));
+                continue;
+            }
+        };
+
+        let description = match doc_summary(v) {
+            Some(doc) => quote!(Some(::std::string::String::from(#doc))),
+            None => quote!(None),
+        };
+
+        items.push(quote! {
+            ::async_graphql::resolver_utils::EnumItem { name: #s, value: #name::#vident }
+        });
+
+        meta_values.push(quote! {
+            values.insert(::std::string::String::from(#s), ::async_graphql::registry::MetaEnumValue {
+                name: ::std::string::String::from(#s),
+                description: #description,
+                deprecation: ::async_graphql::registry::Deprecation::NoDeprecated,
+                visible: None,
+                inaccessible: false,
+                tags: ::std::vec::Vec::new(),
+                directive_invocations: ::std::vec::Vec::new(),
+            });
+        });
+
+        value_arms.push(quote!(#name::#vident => ::async_graphql::Value::Enum(::async_graphql::Name::new(#s))));
+    }
+
+    Ok(quote! {
+        impl ::async_graphql::resolver_utils::EnumType for #name {
+            fn items() -> &'static [::async_graphql::resolver_utils::EnumItem<Self>] {
+                &[#( #items ),*]
+            }
+        }
+
+        impl #name {
+            fn __enum_utils_graphql_type_info(
+                registry: &mut ::async_graphql::registry::Registry,
+            ) -> ::std::string::String {
+                registry.create_input_type::<Self, _>(::async_graphql::registry::MetaTypeId::Enum, |_| {
+                    ::async_graphql::registry::MetaType::Enum {
+                        name: ::std::string::String::from(#type_name),
+                        description: None,
+                        enum_values: {
+                            let mut values = ::async_graphql::indexmap::IndexMap::new();
+                            #( #meta_values )*
+                            values
+                        },
+                        visible: None,
+                        inaccessible: false,
+                        tags: ::std::vec::Vec::new(),
+                        rust_typename: Some(::std::any::type_name::<Self>()),
+                        directive_invocations: ::std::vec::Vec::new(),
+                        requires_scopes: ::std::vec::Vec::new(),
+                    }
+                })
+            }
+
+            fn __enum_utils_graphql_value(&self) -> ::async_graphql::Value {
+                match *self {
+                    #( #value_arms, )*
+                }
+            }
+        }
+
+        impl ::async_graphql::InputType for #name {
+            type RawValueType = Self;
+
+            fn type_name() -> ::std::borrow::Cow<'static, str> {
+                ::std::borrow::Cow::Borrowed(#type_name)
+            }
+
+            fn create_type_info(registry: &mut ::async_graphql::registry::Registry) -> ::std::string::String {
+                Self::__enum_utils_graphql_type_info(registry)
+            }
+
+            fn parse(value: Option<::async_graphql::Value>) -> ::async_graphql::InputValueResult<Self> {
+                ::async_graphql::resolver_utils::parse_enum(value.unwrap_or_default())
+            }
+
+            fn to_value(&self) -> ::async_graphql::Value {
+                self.__enum_utils_graphql_value()
+            }
+
+            fn as_raw_value(&self) -> Option<&Self::RawValueType> {
+                Some(self)
+            }
+        }
+
+        impl ::async_graphql::OutputType for #name {
+            fn type_name() -> ::std::borrow::Cow<'static, str> {
+                ::std::borrow::Cow::Borrowed(#type_name)
+            }
+
+            fn create_type_info(registry: &mut ::async_graphql::registry::Registry) -> ::std::string::String {
+                Self::__enum_utils_graphql_type_info(registry)
+            }
+
+            async fn resolve(
+                &self,
+                _: &::async_graphql::ContextSelectionSet<'_>,
+                _: &::async_graphql::Positioned<::async_graphql::parser::types::Field>,
+            ) -> ::async_graphql::ServerResult<::async_graphql::Value> {
+                Ok(self.__enum_utils_graphql_value())
+            }
+        }
+
+        impl ::std::convert::From<#name> for ::async_graphql::Value {
+            fn from(v: #name) -> Self {
+                v.__enum_utils_graphql_value()
+            }
+        }
+    })
+}
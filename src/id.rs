@@ -0,0 +1,69 @@
+use std::collections::BTreeMap;
+
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::attr::{Enum, ErrorList};
+use crate::name::name_table;
+
+/// The 32-bit FNV-1a hash of `s`.
+///
+/// This must never change, since IDs are persisted outside of the program.
+fn fnv1a(s: &str) -> u32 {
+    const OFFSET_BASIS: u32 = 0x811c_9dc5;
+    const PRIME: u32 = 0x0100_0193;
+
+    s.bytes().fold(OFFSET_BASIS, |hash, b| (hash ^ u32::from(b)).wrapping_mul(PRIME))
+}
+
+pub fn derive(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let name = input.name;
+
+    let mut errors = ErrorList::new();
+    let mut seen = BTreeMap::new();
+    let mut id_arms = vec![];
+    let mut from_id_arms = vec![];
+    for (v, s) in name_table(input)? {
+        let vident = &v.ident;
+        let s = match s {
+            Some(s) => s,
+            None => {
+                let msg = format!("`{}::{}` is skipped and has no ID", name, vident);
+                id_arms.push(quote!(#name::#vident { .. } => panic!(#msg)));
+                continue;
+            }
+        };
+
+        let id = fnv1a(&s);
+        if let Some(prev) = seen.insert(id, s.clone()) {
+            errors.push_back(format_diag!(IdCollision, "The names {:?} and {:?} have the same ID ({:#010x})",
+                                          prev, s, id)
+                .with_span(vident.span()));
+            continue;
+        }
+
+        id_arms.push(quote!(#name::#vident => #id));
+        from_id_arms.push(quote!(#id => Some(#name::#vident)));
+    }
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    Ok(quote! {
+        impl #name {
+            const fn id(&self) -> u32 {
+                match *self {
+                    #( #id_arms, )*
+                }
+            }
+
+            const fn from_id(id: u32) -> Option<Self> {
+                match id {
+                    #( #from_id_arms, )*
+                    _ => None,
+                }
+            }
+        }
+    })
+}
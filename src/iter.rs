@@ -1,12 +1,12 @@
 use std::ops::{Range, RangeInclusive};
 
-use failure::format_err;
 use proc_macro2::{Literal, TokenStream};
-use quote::quote;
+use quote::{format_ident, quote};
 
 use crate::attr::{Discriminant, Enum, ErrorList};
+use crate::conv::FromRepr;
 
-enum IterImpl {
+pub enum IterImpl {
     Empty,
     Range {
         repr: syn::Path,
@@ -25,7 +25,7 @@ impl IterImpl {
     /// If the discriminants form a single, contiguous, increasing run, we will create a
     /// `Range` (or `RangeInclusive`) containing the discriminants as the `#[repr(...)]` of the
     /// enum.
-    fn for_enum(Enum { name, variants, discriminants, primitive_repr, .. }: &Enum) -> Result<Self, ErrorList> {
+    pub fn for_enum(Enum { name, variants, discriminants, primitive_repr, .. }: &Enum) -> Result<Self, ErrorList> {
         // See if we can generate a fast, transmute-based iterator.
         if let Some(discriminants) = discriminants {
             let is_zst = discriminants.len() <= 1;
@@ -48,7 +48,7 @@ impl IterImpl {
                         // If range.end() is less than the maximum value of the primitive repr, we can
                         // use the (faster) non-inclusive `Range`
                         let end = *range.end();
-                        if end < 0 || repr.max_value().map_or(false, |max| (end as u128) < max) {
+                        if end < 0 || repr.max_value().is_some_and(|max| (end as u128) < max) {
                             return Ok(IterImpl::Range {
                                 repr: repr_path.clone(),
                                 range: *range.start()..(end + 1),
@@ -74,7 +74,8 @@ impl IterImpl {
                 }
 
                 if v.fields != syn::Fields::Unit {
-                    errors.push_back(format_err!("An (unskipped) variant cannot have fields"));
+                    errors.push_back(format_diag!(VariantHasFields, "An (unskipped) variant cannot have fields")
+                        .with_span(v.ident.span()));
                     return None;
                 }
 
@@ -94,59 +95,174 @@ impl IterImpl {
         Ok(IterImpl::Slice(unskipped_variants))
     }
 
-    fn tokens(&self, ty: &syn::Ident) -> TokenStream {
-        let body = match self {
-            IterImpl::Empty => quote! {
-                ::std::iter::empty()
-            },
+    /// If `checked` contains the unskipped variants of the enum, the transmute-based iterators
+    /// validate each discriminant against them in debug builds, and a test is emitted which
+    /// compares the fast iterator against the list of variants.
+    ///
+    /// If `traits` is set, `IntoEnumIterator` is implemented instead of the inherent methods.
+    fn tokens(
+        &self,
+        ty: &syn::Ident,
+        checked: Option<&[TokenStream]>,
+        traits: bool,
+        inline: TokenStream,
+    ) -> TokenStream {
+        // The concrete type of the iterator, which must be named to implement `IntoEnumIterator`.
+        let (iter_ty, body) = match self {
+            IterImpl::Empty => (
+                quote!(::std::iter::Empty<#ty>),
+                quote!(::std::iter::empty()),
+            ),
 
             IterImpl::Range { range, repr } => {
                 let start = Literal::i128_unsuffixed(range.start);
                 let end = Literal::i128_unsuffixed(range.end);
 
-                quote! {
+                let transmute = transmute_closure(repr, checked, "iter");
+                let body = quote! {
                     let start: #repr = #start;
                     let end: #repr = #end;
-                    (start .. end).map(|discrim| unsafe { ::std::mem::transmute(discrim) })
-                }
+                    (start .. end).map((#transmute) as fn(#repr) -> #ty)
+                };
+
+                (quote!(::std::iter::Map<::std::ops::Range<#repr>, fn(#repr) -> #ty>), body)
             },
 
             IterImpl::RangeInclusive { range, repr } => {
                 let start = Literal::i128_unsuffixed(*range.start());
                 let end = Literal::i128_unsuffixed(*range.end());
-                quote! {
+                let transmute = transmute_closure(repr, checked, "iter");
+                let body = quote! {
                     let start: #repr = #start;
                     let end: #repr = #end;
-                    (start ..= end).map(|discrim| unsafe { ::std::mem::transmute(discrim) })
-                }
+                    (start ..= end).map((#transmute) as fn(#repr) -> #ty)
+                };
+
+                (quote!(::std::iter::Map<::std::ops::RangeInclusive<#repr>, fn(#repr) -> #ty>), body)
             },
 
-            IterImpl::Slice(variants) => quote! {
-                const VARIANTS: &[#ty] = &[#( #variants ),*];
+            IterImpl::Slice(variants) => {
+                let body = quote! {
+                    const VARIANTS: &[#ty] = &[#( #variants ),*];
+
+                    VARIANTS.iter().cloned()
+                };
 
-                VARIANTS.iter().cloned()
+                (quote!(::std::iter::Cloned<::std::slice::Iter<'static, #ty>>), body)
             },
         };
 
+        let iter_fn = if traits {
+            quote!(<#ty as ::enum_utils_traits::IntoEnumIterator>::iter)
+        } else {
+            quote!(#ty::iter)
+        };
+
+        let check_test = match (self, checked) {
+            (IterImpl::Range { repr, .. }, Some(variants))
+            | (IterImpl::RangeInclusive { repr, .. }, Some(variants)) => {
+                let test_name = format_ident!("__enum_utils_check_iter_{}", ty);
+                quote! {
+                    #[cfg(test)]
+                    #[test]
+                    #[allow(non_snake_case)]
+                    fn #test_name() {
+                        let expected: &[#repr] = &[#( #variants as #repr ),*];
+                        let actual: Vec<#repr> = #iter_fn().map(|v| v as #repr).collect();
+                        assert_eq!(&actual[..], expected);
+                    }
+                }
+            }
+
+            _ => TokenStream::new(),
+        };
+
+        if traits {
+            return quote! {
+                impl ::enum_utils_traits::IntoEnumIterator for #ty {
+                    type Iter = #iter_ty;
+
+                    #inline
+                    fn iter() -> Self::Iter {
+                        #body
+                    }
+                }
+
+                #check_test
+            };
+        }
+
         quote! {
             impl #ty {
+                #inline
                 fn iter() -> impl Iterator<Item = #ty> + Clone {
                     #body
                 }
+
+                #inline
+                fn iter_adjacent() -> impl Iterator<Item = (#ty, #ty)> + Clone {
+                    Self::iter().zip(Self::iter().skip(1))
+                }
             }
+
+            #check_test
         }
     }
 }
 
+/// Returns a closure which transmutes a discriminant into a variant of the enum.
+///
+/// If `checked` is `Some`, the closure asserts that the discriminant belongs to one of the given
+/// variants before transmuting it. `what` names the generated code in the assertion message.
+pub fn transmute_closure(repr: &syn::Path, checked: Option<&[TokenStream]>, what: &str) -> TokenStream {
+    match checked {
+        None => quote! {
+            |discrim: #repr| unsafe { ::std::mem::transmute(discrim) }
+        },
+
+        Some(variants) => {
+            let msg = format!("invalid discriminant {{}} produced by `{}`", what);
+            quote! {
+                |discrim: #repr| {
+                    debug_assert!([#( #variants as #repr ),*].contains(&discrim), #msg, discrim);
+                    unsafe { ::std::mem::transmute(discrim) }
+                }
+            }
+        }
+    }
+}
+
+/// Returns the variants which a transmuted discriminant is checked against, or `None` if
+/// `#[enumeration(debug_checks)]` was not given.
+///
+/// Skipped variants are only included if `skipped` is set.
+pub fn checked_variants(input: &Enum, skipped: bool) -> Option<Vec<TokenStream>> {
+    if !input.attrs.debug_checks {
+        return None;
+    }
+
+    let name = input.name;
+    let variants = input.variants
+        .iter()
+        .filter(|(_, attrs)| skipped || !attrs.skip)
+        .map(|(v, _)| {
+            let vident = &v.ident;
+            quote!(#name::#vident)
+        })
+        .collect();
+
+    Some(variants)
+}
+
 /// Returns a range containing the discriminants of this enum if they comprise a single, contiguous
 /// run. Returns `None` if there were no discriminants or they were not contiguous.
-fn detect_contiguous_run(mut discriminants: impl Iterator<Item = Discriminant>)
+pub fn detect_contiguous_run(mut discriminants: impl Iterator<Item = Discriminant>)
     -> Option<RangeInclusive<Discriminant>>
 {
     let first = discriminants.next()?;
 
     let mut last = first;
-    while let Some(next) = discriminants.next() {
+    for next in discriminants {
         if last.checked_add(1)? != next {
             return None;
         }
@@ -157,8 +273,209 @@ fn detect_contiguous_run(mut discriminants: impl Iterator<Item = Discriminant>)
     Some(first..=last)
 }
 
-pub fn derive(input: &syn::DeriveInput) -> Result<TokenStream, ErrorList> {
-    let input = Enum::parse(input)?;
-    let imp = IterImpl::for_enum(&input)?;
-    Ok(imp.tokens(&input.name))
+pub fn derive(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let imp = IterImpl::for_enum(input)?;
+
+    let checked = checked_variants(input, false);
+    Ok(imp.tokens(input.name, checked.as_deref(), input.attrs.traits, input.attrs.inline_attr()))
+}
+
+pub fn derive_count(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let Enum { name, attrs, variants, .. } = input;
+
+    let count = variants.iter()
+        .filter(|(_, attr)| !attr.skip)
+        .count();
+
+    if attrs.traits {
+        return Ok(quote! {
+            impl ::enum_utils_traits::EnumCount for #name {
+                const COUNT: usize = #count;
+            }
+        });
+    }
+
+    Ok(quote! {
+        impl #name {
+            const COUNT: usize = #count;
+        }
+    })
+}
+
+pub fn derive_ordinal(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let Enum { name, variants, .. } = input;
+
+    let mut errors = ErrorList::new();
+    let mut ordinal_arms = vec![];
+    let mut from_ordinal_arms = vec![];
+    for (v, attrs) in variants.iter() {
+        let vident = &v.ident;
+        if attrs.skip {
+            let msg = format!("`{}::{}` is skipped and has no ordinal", name, vident);
+            ordinal_arms.push(quote!(#name::#vident { .. } => panic!(#msg)));
+            continue;
+        }
+
+        if v.fields != syn::Fields::Unit {
+            errors.push_back(format_diag!(VariantHasFields, "An (unskipped) variant cannot have fields")
+                .with_span(vident.span()));
+            continue;
+        }
+
+        let ordinal = Literal::usize_unsuffixed(from_ordinal_arms.len());
+        ordinal_arms.push(quote!(#name::#vident => #ordinal));
+        from_ordinal_arms.push(quote!(#ordinal => Some(#name::#vident)));
+    }
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    Ok(quote! {
+        impl #name {
+            const fn ordinal(&self) -> usize {
+                match *self {
+                    #( #ordinal_arms, )*
+                }
+            }
+
+            const fn from_ordinal(n: usize) -> Option<Self> {
+                match n {
+                    #( #from_ordinal_arms, )*
+                    _ => None,
+                }
+            }
+        }
+    })
+}
+
+pub fn derive_successor(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let name = input.name;
+    let unskipped = input.unskipped_unit_variants()?;
+
+    // Pairs each variant with the one that follows it, wrapping around to the first variant if
+    // requested.
+    let mut pairs: Vec<_> = unskipped.iter().zip(unskipped.iter().skip(1)).collect();
+    if input.attrs.wrapping {
+        if let (Some(first), Some(last)) = (unskipped.first(), unskipped.last()) {
+            pairs.push((last, first));
+        }
+    }
+
+    let next_arms = pairs.iter().map(|(a, b)| quote!(#name::#a => Some(#name::#b)));
+    let prev_arms = pairs.iter().map(|(a, b)| quote!(#name::#b => Some(#name::#a)));
+
+    Ok(quote! {
+        impl #name {
+            fn next(self) -> Option<Self> {
+                match self {
+                    #( #next_arms, )*
+                    _ => None,
+                }
+            }
+
+            fn prev(self) -> Option<Self> {
+                match self {
+                    #( #prev_arms, )*
+                    _ => None,
+                }
+            }
+        }
+    })
+}
+
+pub fn derive_bounds(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let Enum { name, variants, discriminants, .. } = input;
+
+    let mut errors = ErrorList::new();
+    for (v, _) in variants.iter() {
+        if v.fields != syn::Fields::Unit {
+            errors.push_back(format_diag!(VariantHasFields, "Variant cannot have fields")
+                .with_span(v.ident.span()));
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    let discriminants = match discriminants {
+        Some(ds) => ds,
+        None => bail_list!(UnknownDiscriminant,
+                           "All discriminants must be integer literals to derive `EnumBounds`"),
+    };
+
+    let by_discriminant = || discriminants.iter().zip(variants.iter());
+    let min = by_discriminant().min_by_key(|(&d, _)| d);
+    let max = by_discriminant().max_by_key(|(&d, _)| d);
+    let (min, max) = match (min, max) {
+        (Some((_, (min, _))), Some((_, (max, _)))) => (&min.ident, &max.ident),
+        _ => bail_list!(NoVariants, "`EnumBounds` cannot be derived for an empty enum"),
+    };
+
+    Ok(quote! {
+        impl #name {
+            const MIN: Self = #name::#min;
+            const MAX: Self = #name::#max;
+        }
+    })
+}
+
+pub fn derive_step(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let FromRepr { name, repr, assertions, .. } = FromRepr::for_enum(input)?;
+
+    let discriminants = match &input.discriminants {
+        Some(ds) => ds,
+        None => bail_list!(UnknownDiscriminant,
+                           "All discriminants must be integer literals to derive `Step`"),
+    };
+
+    // Stepping is done on the discriminant, so the variants must be declared in order with no gaps
+    // for a derived `PartialOrd` to agree with it.
+    let range = match detect_contiguous_run(discriminants.iter().copied()) {
+        Some(range) => range,
+        None => bail_list!(NonContiguousDiscriminants,
+                           "The discriminants must be increasing and contiguous to derive `Step`"),
+    };
+
+    let start = Literal::i128_unsuffixed(*range.start());
+    let end = Literal::i128_unsuffixed(*range.end());
+
+    let checked = checked_variants(input, true);
+    let transmute = transmute_closure(&repr, checked.as_deref(), "Step");
+
+    Ok(quote! {
+        #assertions
+
+        impl ::std::iter::Step for #name {
+            fn steps_between(start: &Self, end: &Self) -> (usize, Option<usize>) {
+                let start = ::std::clone::Clone::clone(start) as #repr as i128;
+                let end = ::std::clone::Clone::clone(end) as #repr as i128;
+                if start <= end {
+                    let n = (end - start) as usize;
+                    (n, Some(n))
+                } else {
+                    (0, None)
+                }
+            }
+
+            fn forward_checked(start: Self, count: usize) -> Option<Self> {
+                let d = (start as #repr as i128).checked_add(count as i128)?;
+                if d <= #end {
+                    Some((#transmute)(d as #repr))
+                } else {
+                    None
+                }
+            }
+
+            fn backward_checked(start: Self, count: usize) -> Option<Self> {
+                let d = (start as #repr as i128).checked_sub(count as i128)?;
+                if d >= #start {
+                    Some((#transmute)(d as #repr))
+                } else {
+                    None
+                }
+            }
+        }
+    })
 }
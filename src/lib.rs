@@ -1,32 +1,176 @@
 //! A set of procedural macros for deriving useful functionality on enums.
+//!
+//! # Errors
+//!
+//! Every error reported by these macros is prefixed with a stable code. The code for a given
+//! kind of error will not change between releases, even if the wording of the message does.
+//!
+//! | Code     | Meaning                                                                  |
+//! |----------|--------------------------------------------------------------------------|
+//! | `EU0001` | The derive was applied to something other than an enum                   |
+//! | `EU0002` | An `#[enumeration(...)]` or `#[repr(...)]` attribute is malformed        |
+//! | `EU0003` | A variant which is not skipped has fields                                |
+//! | `EU0004` | An argument to `#[enumeration(...)]` is not recognized                   |
+//! | `EU0005` | An argument is not valid on an enum (or on a variant)                    |
+//! | `EU0006` | An argument which may only appear once was repeated                      |
+//! | `EU0007` | The argument to `rename_all` is not a known rename rule                  |
+//! | `EU0008` | A primitive `#[repr(...)]` is required but was not specified             |
+//! | `EU0009` | More than one primitive `#[repr(...)]` was specified                     |
+//! | `EU0010` | A discriminant must be known, but is not an integer literal              |
+//! | `EU0011` | A variant is missing from (or repeated in) `assert_discriminants`        |
+//! | `EU0012` | No variant is marked with `#[enumeration(default)]`                      |
+//! | `EU0013` | The `other` variant has more than one field                              |
+//! | `EU0014` | The enum has too many variants for a bitset                              |
+//! | `EU0015` | The variant weights sum to zero or overflow a `u64`                      |
+//! | `EU0016` | Every variant is skipped, but one must be constructed                    |
+//! | `EU0017` | An unskipped variant has no `char` tag                                   |
+//! | `EU0018` | Two variants have the same `char` tag                                    |
+//! | `EU0019` | The names of two variants hash to the same ID                            |
+//! | `EU0020` | The enum does not have one `true` variant and one `false` variant        |
+//! | `EU0021` | The discriminants are not a single, increasing run of integers           |
+//! | `EU0022` | A variant has no `order`                                                 |
+//! | `EU0023` | Two variants have the same `order`                                       |
+//! | `EU0024` | A variant has no `value`                                                 |
+//! | `EU0025` | Two variants have `value`s of different types                            |
+//! | `EU0026` | Two variants have the same name or alias                                 |
+//! | `EU0027` | An attribute would make a `no_panic` parser call code which may panic    |
+//! | `EU0028` | The `aliases_file` could not be read, or names an unknown variant        |
 
 #![doc(html_root_url = "https://docs.rs/enum-utils/0.1.2")]
 
 extern crate proc_macro;
 
+#[macro_use]
+mod error;
 #[macro_use]
 mod attr;
+mod accessor;
+#[cfg(any(feature = "arbitrary", feature = "proptest", feature = "quickcheck"))]
+mod arbitrary;
+#[cfg(feature = "bytemuck")]
+mod bytemuck;
+#[cfg(feature = "clap")]
+mod clap;
+mod id;
 mod iter;
 mod from_str;
 mod conv;
+mod default;
+mod error_impl;
+#[cfg(feature = "defmt")]
+mod defmt;
+#[cfg(feature = "diesel")]
+mod diesel;
+mod discriminants;
+mod for_each;
+#[cfg(feature = "async-graphql")]
+mod graphql;
+mod map;
+mod matcher;
+mod set;
+mod name;
+#[cfg(feature = "num-traits")]
+mod num;
+mod ord;
+#[cfg(feature = "postgres-types")]
+mod postgres;
+mod property;
+#[cfg(feature = "rand")]
+mod random;
+#[cfg(feature = "rusqlite")]
+mod rusqlite;
+#[cfg(feature = "schemars")]
+mod schemars;
+#[cfg(feature = "serde")]
+mod serde;
+#[cfg(feature = "sqlx")]
+mod sqlx;
+mod umbrella;
+#[cfg(feature = "utoipa")]
+mod utoipa;
+mod value;
+mod visitor;
 
 use proc_macro::TokenStream;
 use syn::{DeriveInput, parse_macro_input};
 
-fn unwrap_errors<T>(res: Result<T, attr::ErrorList>) -> T {
+fn unwrap_errors(res: Result<proc_macro2::TokenStream, attr::ErrorList>) -> proc_macro2::TokenStream {
     match res {
         Ok(x) => x,
-        Err(list) => {
-            // TODO: print error spans with proc_macro_diagnostic
-            let desc: String = list.iter()
-                .map(|e| format!("\n{}", e))
-                .collect();
-
-            panic!("enum_utils encountered one or more errors:{}", desc);
-        }
+        Err(list) => list.iter()
+            .map(error::Diagnostic::to_compile_error)
+            .collect(),
     }
 }
 
+/// Runs the derive `name` on `ast`, after resolving any `skip(...)` arguments for it.
+fn derive_scoped(name: &str,
+                 ast: DeriveInput,
+                 derive: fn(&attr::Enum) -> Result<proc_macro2::TokenStream, attr::ErrorList>)
+    -> proc_macro2::TokenStream
+{
+    unwrap_errors(attr::Enum::parse(&ast).and_then(|mut input| {
+        let mut tokens = derive(input.for_derive(&[name]))?;
+        tokens.extend(input.attrs.aliases_file_dependency());
+        Ok(tokens)
+    }))
+}
+
+/// Derives any combination of [`FromStr`], [`Display`], [`IterVariants`], [`TryFromRepr`] and
+/// [`ReprFrom`], as selected with `#[enumeration(derive(...))]`.
+///
+/// The enum and its attributes are parsed only once, no matter how many derives are selected.
+/// Nothing else is shared: each selected derive generates exactly what it would on its own, and
+/// accepts the same attributes. For example, selecting both `FromStr` and `Display` emits the names
+/// of the variants once for parsing and again for formatting, since the two may differ (see
+/// [`rename_all`]). Selecting the same derive twice results in conflicting implementations.
+///
+/// [`rename_all`]: derive.FromStr.html#enumerationrename_all--
+///
+/// [`FromStr`]: derive.FromStr.html
+/// [`Display`]: derive.Display.html
+/// [`IterVariants`]: derive.IterVariants.html
+/// [`TryFromRepr`]: derive.TryFromRepr.html
+/// [`ReprFrom`]: derive.ReprFrom.html
+///
+/// # Examples
+///
+/// ```
+/// use std::convert::TryFrom;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, enum_utils::Enum)]
+/// #[enumeration(derive(FromStr, Display, IterVariants, TryFromRepr, ReprFrom))]
+/// #[enumeration(rename_all = "lowercase")]
+/// #[repr(u8)]
+/// pub enum Suit {
+///     Clubs,
+///     Diamonds,
+///     Hearts,
+///     Spades,
+/// }
+///
+/// assert_eq!("hearts".parse(), Ok(Suit::Hearts));
+/// assert_eq!(Suit::Spades.to_string(), "spades");
+/// assert_eq!(Suit::iter().count(), 4);
+/// assert_eq!(Suit::try_from(1), Ok(Suit::Diamonds));
+/// assert_eq!(u8::from(Suit::Clubs), 0);
+/// ```
+///
+/// Other derives cannot be selected.
+///
+/// ```compile_fail
+/// #[derive(enum_utils::Enum)]
+/// #[enumeration(derive(EnumCount))]
+/// pub enum Suit {
+///     Clubs,
+/// }
+/// ```
+#[proc_macro_derive(Enum, attributes(enumeration))]
+pub fn enum_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    unwrap_errors(umbrella::derive(&ast)).into()
+}
+
 /// Derives [`FromStr`] for C-like enums.
 ///
 /// The generated code will be more efficient than a simple `match` statement for most enums. It is
@@ -71,6 +215,23 @@ fn unwrap_errors<T>(res: Result<T, attr::ErrorList>) -> T {
 /// assert_eq!("Beta".parse(), Ok(Skip::Beta));
 /// ```
 ///
+/// `skip` applies to every derive from this crate. To skip a variant for only some of them, list
+/// them with `skip(...)`. Within the [`Enum`] derive, `skip(Enum)` applies to every derive it
+/// selects.
+///
+/// ```
+/// #[derive(Debug, Clone, Copy, PartialEq, enum_utils::FromStr, enum_utils::IterVariants)]
+/// enum Level {
+///     Low,
+///     #[enumeration(skip(FromStr))]
+///     Reserved,
+///     High,
+/// }
+///
+/// assert_eq!("Reserved".parse::<Level>(), Err(()));
+/// assert_eq!(Level::iter().collect::<Vec<_>>(), vec![Level::Low, Level::Reserved, Level::High]);
+/// ```
+///
 /// ## `#[enumeration(rename = "...")]`
 ///
 /// This attribute renames a single variant of an enum. This replaces the name of the variant and
@@ -112,14 +273,134 @@ fn unwrap_errors<T>(res: Result<T, attr::ErrorList>) -> T {
 /// assert_eq!("Beta".parse(), Ok(Alias::Beta));
 /// ```
 ///
+/// Two variants cannot be parsed from the same string, whether it comes from a name or an alias.
+///
+/// ```compile_fail
+/// #[derive(enum_utils::FromStr)]
+/// #[enumeration(rename_all = "lowercase")]
+/// enum Conflict {
+///     Start,
+///     #[enumeration(alias = "start")]
+///     Begin,
+/// }
+/// ```
+///
+/// Aliases which are kept only for compatibility can be marked `deprecated`. This applies to
+/// every alias in the same attribute. Deprecated aliases are still accepted, but an associated
+/// function, `is_deprecated_name`, is generated which tells whether a string would be parsed
+/// through one. They are also flagged by [`parse_detailed`].
+///
+/// ```
+/// #[derive(Debug, PartialEq, enum_utils::FromStr)]
+/// #[enumeration(rename_all = "snake_case")]
+/// enum Setting {
+///     #[enumeration(alias = "colour")]
+///     #[enumeration(alias = "colour_scheme", alias = "scheme", deprecated)]
+///     Color,
+///     FontSize,
+/// }
+///
+/// assert_eq!("scheme".parse(), Ok(Setting::Color));
+/// assert!(Setting::is_deprecated_name("scheme"));
+/// assert!(!Setting::is_deprecated_name("colour"));
+/// assert!(!Setting::is_deprecated_name("font_size"));
+/// ```
+///
+/// ## `#[enumeration(alias_bytes = b"...")]`
+///
+/// Like `alias`, but takes a byte string, which need not be valid UTF-8. Such aliases can only
+/// be matched by the functions which take bytes: `TryFrom<&[u8]>` (see [`from_bytes`]), the
+/// `parse_fn` and `from_prefix`. They are always matched exactly, regardless of
+/// `case_insensitive` and `dash_underscore_insensitive`.
+///
+/// ```
+/// use std::convert::TryFrom;
+///
+/// #[derive(Debug, PartialEq, enum_utils::FromStr)]
+/// #[enumeration(from_bytes)]
+/// enum Token {
+///     #[enumeration(alias_bytes = b"\xff\x01")]
+///     Start,
+///     End,
+/// }
+///
+/// assert_eq!(Token::try_from(&b"\xff\x01"[..]), Ok(Token::Start));
+/// assert_eq!(Token::try_from(&b"Start"[..]), Ok(Token::Start));
+/// ```
+///
+/// ## `#[enumeration(locale(...))]`
+///
+/// Gives the name of a variant in one or more locales, e.g. `locale(en = "Red", de = "Rot")`.
+/// The locales are arbitrary identifiers. If any variant has a localized name, two more
+/// functions are generated:
+///
+/// - `fn name_in(&self, locale: &str) -> &'static str` returns the name of a variant in the
+///   given locale. Variants without a name in that locale fall back to the name written by
+///   [`Display`].
+/// - `fn parse_in(locale: &str, s: &str) -> Option<Self>` parses a name in the given locale,
+///   using a separate trie for each one. Only localized names are accepted, and they are matched
+///   according to `case_insensitive`, `dash_underscore_insensitive` and `trim`.
+///
+/// Localized names are independent of the names and aliases accepted by `FromStr`.
+///
+/// ```
+/// #[derive(Debug, PartialEq, enum_utils::FromStr)]
+/// #[enumeration(case_insensitive)]
+/// enum Color {
+///     #[enumeration(locale(en = "Red", de = "Rot"))]
+///     Red,
+///     #[enumeration(locale(en = "Green", de = "Grün"))]
+///     Green,
+/// }
+///
+/// assert_eq!(Color::Green.name_in("de"), "Grün");
+/// assert_eq!(Color::parse_in("de", "rot"), Some(Color::Red));
+/// assert_eq!(Color::parse_in("fr", "rouge"), None);
+/// assert_eq!("Rot".parse::<Color>(), Err(()));
+/// ```
+///
+/// ## `#[enumeration(aliases_file = "...")]`
+///
+/// Reads additional aliases from a file when the derive is expanded, which keeps large tables of
+/// aliases out of the source. The path is relative to the directory containing `Cargo.toml`. Each
+/// line of the file is the name of a variant followed by its aliases, separated by whitespace. A
+/// `#` begins a comment which runs to the end of the line. The aliases behave exactly as if they
+/// were given with `alias`, and the crate is recompiled whenever the file changes.
+///
+/// ```text
+/// # tests/data/http_methods.txt
+/// Get     GET get
+/// Post    POST post
+///
+/// Delete  DELETE del  # not `remove`
+/// ```
+///
+/// ```
+/// #[derive(Debug, PartialEq, enum_utils::FromStr)]
+/// #[enumeration(aliases_file = "tests/data/http_methods.txt")]
+/// enum HttpMethod {
+///     Get,
+///     Post,
+///     Delete,
+/// }
+///
+/// assert_eq!("GET".parse(), Ok(HttpMethod::Get));
+/// assert_eq!("del".parse(), Ok(HttpMethod::Delete));
+/// ```
+///
 /// ## `#[enumeration(rename_all = "...")]`
 ///
 /// This attribute can be applied to an entire enum, and causes all fields to be renamed according
 /// to the given [rename rule]. All rename rules defined in [`serde`] are supported.
 ///
+/// The `serde` rules start a new word at every capital, so `ParseHTTPResponse` becomes
+/// `parse_h_t_t_p_response`. The additional rules `"snake_case_acronyms"`,
+/// `"kebab-case-acronyms"` and `"SCREAMING_SNAKE_CASE_ACRONYMS"` treat a run of capitals as a
+/// single word instead, giving `parse_http_response`.
+///
 /// ```
-/// #[enumeration(rename_all = "snake_case")]
 /// #[derive(Debug, PartialEq, enum_utils::FromStr)]
+/// #[enumeration(rename_all = "snake_case")]
 /// enum RenameAll {
 ///     FooBar,
 ///     BarFoo,
@@ -129,14 +410,136 @@ fn unwrap_errors<T>(res: Result<T, attr::ErrorList>) -> T {
 /// assert_eq!("bar_foo".parse(), Ok(RenameAll::BarFoo));
 /// ```
 ///
+/// As in [`serde`], the rule for names which are written out (by `Display`, `Serialize` and the
+/// like) can differ from the rule for names which are parsed. Either rule may be omitted, in which
+/// case the name of the variant is used as is.
+///
+/// ```
+/// #[derive(Debug, PartialEq, enum_utils::FromStr, enum_utils::Display)]
+/// #[enumeration(rename_all(serialize = "SCREAMING_SNAKE_CASE", deserialize = "lowercase"))]
+/// enum Split {
+///     FooBar,
+/// }
+///
+/// assert_eq!("foobar".parse(), Ok(Split::FooBar));
+/// assert_eq!(Split::FooBar.to_string(), "FOO_BAR");
+/// ```
+///
+/// ## `#[enumeration(alias_all = "...")]`
+///
+/// This attribute can be applied to an entire enum, any number of times. Each variant also gets
+/// its name under the given [rename rule] as an alias, without changing its canonical name.
+/// Variants with an explicit [`rename`] are not affected.
+///
+/// ```
+/// #[derive(Debug, PartialEq, enum_utils::FromStr)]
+/// #[enumeration(rename_all = "snake_case", alias_all = "kebab-case", alias_all = "camelCase")]
+/// enum Flag {
+///     DryRun,
+///     #[enumeration(rename = "v")]
+///     Verbose,
+/// }
+///
+/// assert_eq!("dry_run".parse(), Ok(Flag::DryRun));
+/// assert_eq!("dry-run".parse(), Ok(Flag::DryRun));
+/// assert_eq!("dryRun".parse(), Ok(Flag::DryRun));
+/// assert_eq!("verbose".parse::<Flag>(), Err(()));
+/// ```
+///
+/// ## `#[enumeration(prefix = "...")]`
+///
+/// This attribute can be applied to an entire enum, and prepends a string to the name of each
+/// variant after [`rename`] or [`rename_all`] have been applied. Aliases are not prefixed. A
+/// variant marked with `#[enumeration(no_prefix)]` keeps its name as is.
+///
+/// The prefix is part of the canonical name, so it is also used by derives like `Display` and
+/// `VariantNames`.
+///
+/// ```
+/// #[derive(Debug, PartialEq, enum_utils::FromStr)]
+/// #[enumeration(prefix = "HTTP_", rename_all = "SCREAMING_SNAKE_CASE")]
+/// enum Header {
+///     ContentType,
+///     #[enumeration(rename = "HOST")]
+///     Host,
+///     #[enumeration(no_prefix)]
+///     Cookie,
+/// }
+///
+/// assert_eq!("HTTP_CONTENT_TYPE".parse(), Ok(Header::ContentType));
+/// assert_eq!("HTTP_HOST".parse(), Ok(Header::Host));
+/// assert_eq!("COOKIE".parse(), Ok(Header::Cookie));
+/// assert_eq!("CONTENT_TYPE".parse::<Header>(), Err(()));
+/// ```
+///
+/// ## `#[enumeration(suffix = "...")]`
+///
+/// Like `prefix`, but appends a string to the name of each variant. The suffix is not affected by
+/// [`rename_all`]. A variant marked with `#[enumeration(no_suffix)]` keeps its name as is.
+///
+/// ```
+/// #[derive(Debug, PartialEq, enum_utils::FromStr)]
+/// #[enumeration(suffix = "_V2", rename_all = "snake_case")]
+/// enum Key {
+///     MaxConnections,
+///     #[enumeration(no_suffix)]
+///     Timeout,
+/// }
+///
+/// assert_eq!("max_connections_V2".parse(), Ok(Key::MaxConnections));
+/// assert_eq!("timeout".parse(), Ok(Key::Timeout));
+/// ```
+///
+/// ## `#[enumeration(numeric_aliases)]`
+///
+/// This attribute can be applied to an entire enum, and makes the decimal representation of each
+/// variant's discriminant an alias for it. All discriminants must be integer literals, or be
+/// implicit.
+///
+/// ```
+/// #[derive(Debug, PartialEq, enum_utils::FromStr)]
+/// #[enumeration(numeric_aliases, rename_all = "lowercase")]
+/// enum Level {
+///     Error = 1,
+///     Warn,
+///     Debug = -1,
+/// }
+///
+/// assert_eq!("warn".parse(), Ok(Level::Warn));
+/// assert_eq!("2".parse(), Ok(Level::Warn));
+/// assert_eq!("-1".parse(), Ok(Level::Debug));
+/// assert_eq!("02".parse::<Level>(), Err(()));
+/// ```
+///
+/// ## `#[enumeration(qualified)]`
+///
+/// This attribute can be applied to an entire enum. Each name and alias is also accepted when
+/// qualified with the name of the enum, as printed by tooling which shows the path of a
+/// variant. With `qualified = "only"`, the unqualified names are no longer accepted, and the
+/// qualified name becomes the canonical one. Discriminants added by `numeric_aliases` are never
+/// qualified.
+///
+/// ```
+/// #[derive(Debug, PartialEq, enum_utils::FromStr)]
+/// #[enumeration(qualified)]
+/// enum Direction {
+///     North,
+///     South,
+/// }
+///
+/// assert_eq!("Direction::North".parse(), Ok(Direction::North));
+/// assert_eq!("North".parse(), Ok(Direction::North));
+/// assert_eq!("Compass::North".parse::<Direction>(), Err(()));
+/// ```
+///
 /// ## `#[enumeration(case_insensitive)]`
 ///
 /// This attribute can be applied to an entire enum, it causes all variants to be parsed
 /// case-insensitively.
 ///
 /// ```
-/// #[enumeration(case_insensitive)]
 /// #[derive(Debug, PartialEq, enum_utils::FromStr)]
+/// #[enumeration(case_insensitive)]
 /// enum NoCase {
 ///     Alpha,
 ///     Beta,
@@ -146,152 +549,2775 @@ fn unwrap_errors<T>(res: Result<T, attr::ErrorList>) -> T {
 /// assert_eq!("beta".parse(), Ok(NoCase::Beta));
 /// ```
 ///
-/// [`FromStr`]: https://doc.rust-lang.org/std/str/trait.FromStr.html
-/// [`serde`]: https://serde.rs/attributes.html
-/// [`rename`]: #enumerationrename--
-/// [`rename_all`]: #enumerationrename_all--
-/// [rename rule]: https://serde.rs/container-attrs.html#rename_all
-#[proc_macro_derive(FromStr, attributes(enumeration))]
-pub fn from_str_derive(input: TokenStream) -> TokenStream {
-    let ast = parse_macro_input!(input as DeriveInput);
-    unwrap_errors(from_str::derive(&ast)).into()
-}
-
-/// Derives a static method, `iter()`, which iterates over the variants of an enum.
+/// Since `"OK"` and `"Ok"` are the same string when case is ignored, they cannot name different
+/// variants.
 ///
-/// # Examples
+/// ```compile_fail
+/// #[derive(enum_utils::FromStr)]
+/// #[enumeration(case_insensitive)]
+/// enum Status {
+///     #[enumeration(rename = "OK")]
+///     Ok,
+///     #[enumeration(rename = "Ok")]
+///     Okay,
+/// }
+/// ```
 ///
-/// Variants are yielded in the order they are defined. If the discriminants of the enum form a
-/// single, increasing run and `#[repr(...)]` is specified as in the following example, a fast
-/// implementation of `iter` can be generated which does not require the enum to implement `Clone`.
+/// It can also be applied to a single variant, along with its negation, `case_sensitive`, to
+/// override the setting for the enum. If a string matches one variant exactly and another only
+/// case-insensitively, the exact match wins.
 ///
 /// ```
-/// /// The discriminants of this enum are `[1, 2, 3, 4]`.
-/// #[derive(Debug, PartialEq, Eq, enum_utils::IterVariants)]
-/// #[repr(u8)]
-/// pub enum Direction {
-///     North = 1,
-///     East,
-///     South,
-///     West,
+/// #[derive(Debug, PartialEq, enum_utils::FromStr)]
+/// enum Token {
+///     #[enumeration(rename = "Ok")]
+///     Ok,
+///     #[enumeration(rename = "select", case_insensitive)]
+///     Select,
 /// }
 ///
-/// use Direction::*;
-/// assert_eq!(Direction::iter().collect::<Vec<_>>(), vec![North, East, South, West]);
+/// assert_eq!("SeLeCt".parse(), Ok(Token::Select));
+/// assert_eq!("OK".parse::<Token>(), Err(()));
 /// ```
 ///
-/// If the preceding conditions are not met, `Clone` must be implemented to successfully derive
-/// `IterVariants`. `enum_utils` will create a `const` array containing each variant and iterate
-/// over that.
+/// By default, only ASCII letters are matched case-insensitively. With
+/// `case_insensitive = "unicode"`, names are compared after simple Unicode case folding instead.
+/// Names are folded at compile time, and input is only folded at runtime if it is not ASCII.
+/// Characters whose uppercase or lowercase form is more than one character (e.g. `'ß'`) must
+/// match exactly.
 ///
 /// ```
-/// #[derive(Debug, Clone, Copy, PartialEq, Eq, enum_utils::IterVariants)]
-/// #[repr(u16)]
-/// pub enum Bitmask {
-///     Empty = 0x0000,
-///     Full = 0xffff,
+/// #[derive(Debug, PartialEq, enum_utils::FromStr)]
+/// #[enumeration(case_insensitive = "unicode")]
+/// enum Letter {
+///     #[enumeration(rename = "α")]
+///     Alpha,
+///     #[enumeration(rename = "Straße")]
+///     Street,
 /// }
 ///
-/// use Bitmask::*;
-/// assert_eq!(Bitmask::iter().collect::<Vec<_>>(), vec![Empty, Full]);
+/// assert_eq!("Α".parse(), Ok(Letter::Alpha));
+/// assert_eq!("STRAßE".parse(), Ok(Letter::Street));
 /// ```
 ///
-/// Named constants or complex expressions (beyond an integer literal) are not evaluated when used
-/// as a discriminant and will cause `IterVariants` to default to the `Clone`-based implementation.
+/// ## `#[enumeration(assume_lowercase)]`
 ///
-/// ```compile_fail
-/// #[derive(Debug, PartialEq, Eq, enum_utils::IterVariants)] // Missing `Clone` impl
-/// #[repr(u8)]
-/// pub enum Bitmask {
-///     Bit1 = 1 << 0,
-///     Bit2 = 1 << 1,
+/// This attribute can be applied to an entire enum. It promises that the input to a
+/// `case_insensitive` parser has already been converted to ASCII lowercase, so names can be
+/// compared with it exactly instead of ignoring case one byte at a time. Input containing
+/// uppercase letters may then fail to match. This has no effect on variants which are matched
+/// case-sensitively or with `case_insensitive = "unicode"`.
+///
+/// ```
+/// #[derive(Debug, PartialEq, enum_utils::FromStr)]
+/// #[enumeration(case_insensitive, assume_lowercase)]
+/// enum Method {
+///     Get,
+///     Post,
 /// }
+///
+/// assert_eq!("get".parse(), Ok(Method::Get));
+/// assert_eq!("post".parse(), Ok(Method::Post));
 /// ```
 ///
-/// # Attributes
+/// ## `#[enumeration(other)]`
 ///
-/// ## `#[enumeration(skip)]`
+/// This attribute marks a single variant as a catch-all, which is returned for any input that does
+/// not match the name of another variant. The variant may have no fields, or a single unnamed
+/// field which is constructed from the input using `From<&str>`. The catch-all variant is
+/// implicitly skipped.
 ///
-/// Use `#[enumeration(skip)]` to avoid iterating over a variant. This can be useful when an enum
-/// contains a "catch-all" variant.
+/// ```
+/// #[derive(Debug, PartialEq, enum_utils::FromStr)]
+/// enum Header {
+///     Accept,
+///     Host,
+///     #[enumeration(other)]
+///     Unknown(String),
+/// }
 ///
+/// assert_eq!("Host".parse(), Ok(Header::Host));
+/// assert_eq!("X-Forwarded-For".parse(), Ok(Header::Unknown("X-Forwarded-For".to_owned())));
 /// ```
-/// #[derive(Debug, Clone, Copy, PartialEq, Eq, enum_utils::IterVariants)]
-/// pub enum Http2FrameType {
-///     Data,
-///     Headers,
 ///
-///     /* ... */
+/// ## `#[enumeration(on_miss = "...")]`
 ///
-///     Continuation,
+/// This attribute can be applied to an entire enum. It names a function which is called with the
+/// rejected input whenever it does not match the name of any variant, just before `from_str`
+/// returns `Err` (or the [`other`] variant). This is useful for recording which unknown values are
+/// being received.
 ///
-///     #[enumeration(skip)]
-///     Unknown(u8),
+/// ```
+/// use std::cell::RefCell;
+///
+/// thread_local! {
+///     static MISSES: RefCell<Vec<String>> = RefCell::new(vec![]);
 /// }
 ///
-/// use Http2FrameType::*;
-/// assert_eq!(Http2FrameType::iter().collect::<Vec<_>>(),
-///            vec![Data, Headers, /* ... */ Continuation]);
+/// fn record_miss(s: &str) {
+///     MISSES.with(|m| m.borrow_mut().push(s.to_owned()));
+/// }
+///
+/// #[derive(Debug, PartialEq, enum_utils::FromStr)]
+/// #[enumeration(on_miss = "record_miss")]
+/// enum OnMiss {
+///     Alpha,
+///     Beta,
+/// }
+///
+/// assert_eq!("Alpha".parse(), Ok(OnMiss::Alpha));
+/// assert_eq!("Gamma".parse::<OnMiss>(), Err(()));
+/// MISSES.with(|m| assert_eq!(*m.borrow(), vec!["Gamma"]));
 /// ```
-#[proc_macro_derive(IterVariants, attributes(enumeration))]
-pub fn iter_variants_derive(input: TokenStream) -> TokenStream {
-    let ast = parse_macro_input!(input as DeriveInput);
-    unwrap_errors(iter::derive(&ast)).into()
-}
-
-/// Derives [`TryFrom<Repr>`] for a C-like enum, where `Repr` is a [primitive representation]
-/// specified in `#[repr(...)]`.
 ///
-/// [`TryFrom<Repr>`]: https://doc.rust-lang.org/std/convert/trait.TryFrom.html
-/// [primitive representation]: https://doc.rust-lang.org/reference/type-layout.html#primitive-representations
+/// ## `#[enumeration(parse_tail, separator = "...")]`
 ///
-/// # Examples
+/// A variant with a single unnamed field can be marked with `parse_tail`. It matches any input
+/// which starts with its name followed by the `separator` (if one is given), and the remainder of
+/// the input is parsed as the field using its own `FromStr` implementation. Exact matches for
+/// other variants take priority, and if several prefixes match, the longest is tried first.
 ///
 /// ```
-/// use std::convert::{TryFrom, TryInto};
-///
-/// #[derive(Debug, Clone, Copy, PartialEq, Eq, enum_utils::TryFromRepr)]
-/// #[repr(u8)]
-/// pub enum Direction {
-///     North = 1,
-///     East,
-///     South,
-///     West
+/// #[derive(Debug, PartialEq, enum_utils::FromStr)]
+/// #[enumeration(rename_all = "lowercase")]
+/// enum Setting {
+///     Verbose,
+///     #[enumeration(parse_tail, separator = ":")]
+///     Timeout(u32),
+///     #[enumeration(parse_tail, separator = "=")]
+///     Name(String),
 /// }
 ///
-/// use Direction::*;
-/// assert_eq!(North, 1u8.try_into().unwrap());
-/// assert_eq!(West,  4u8.try_into().unwrap());
-/// assert_eq!(Err(()), Direction::try_from(0u8));
-/// assert_eq!(Err(()), Direction::try_from(5u8));
+/// assert_eq!("verbose".parse(), Ok(Setting::Verbose));
+/// assert_eq!("timeout:30".parse(), Ok(Setting::Timeout(30)));
+/// assert_eq!("name=a:b".parse(), Ok(Setting::Name("a:b".to_owned())));
+/// assert_eq!("timeout:soon".parse::<Setting>(), Err(()));
 /// ```
 ///
-/// This macro only works on C-like enums.
+/// ## `#[enumeration(from_name)]`
 ///
-/// ```compile_fail
-/// #[derive(Debug, Clone,  enum_utils::TryFromRepr)]
-/// #[repr(u8)]
-/// pub enum Letter {
-///     A,
-///     B,
-///     Other(u8),
+/// Generates an associated function, `from_name`, which returns `Some` variant if the string
+/// names one, and `None` otherwise. This is more convenient than [`FromStr`] when the error is
+/// not needed, and does not require the trait to be in scope.
+///
+/// ```
+/// #[derive(Debug, PartialEq, enum_utils::FromStr)]
+/// #[enumeration(from_name, rename_all = "lowercase")]
+/// enum Fruit {
+///     Apple,
+///     Pear,
 /// }
+///
+/// assert_eq!(Fruit::from_name("pear"), Some(Fruit::Pear));
+/// assert_eq!(Fruit::from_name("plum"), None);
 /// ```
-#[proc_macro_derive(TryFromRepr, attributes(enumeration))]
-pub fn try_from_repr_derive(input: TokenStream) -> TokenStream {
-    let ast = parse_macro_input!(input as DeriveInput);
-    unwrap_errors(conv::derive_try_from_repr(&ast)).into()
-}
-
-/// Derives [`From<CLikeEnum>`] for the [primitive representation] specified in `#[repr(...)]`.
 ///
-/// [`From<Enum>`]: https://doc.rust-lang.org/std/convert/trait.From.html
-/// [primitive representation]: https://doc.rust-lang.org/reference/type-layout.html#primitive-representations
+/// ## `#[enumeration(from_bytes)]`
 ///
-/// # Examples
+/// Also implements `TryFrom<&[u8]>`, which matches the name of each variant against a byte slice
+/// without checking that it is valid UTF-8 first. This is useful for parsing protocol tokens
+/// straight out of a network buffer. The input is only converted to a `&str` if none of the names
+/// match and it is needed by `parse_tail`, `on_miss` or an [`other`] variant, in which case
+/// invalid UTF-8 is simply an error.
 ///
 /// ```
-/// #[derive(Debug, Clone, Copy, PartialEq, Eq, enum_utils::ReprFrom)]
+/// use std::convert::TryFrom;
+///
+/// #[derive(Debug, PartialEq, enum_utils::FromStr)]
+/// #[enumeration(from_bytes)]
+/// enum Method {
+///     GET,
+///     HEAD,
+///     POST,
+/// }
+///
+/// assert_eq!(Method::try_from(&b"HEAD"[..]), Ok(Method::HEAD));
+/// assert_eq!(Method::try_from(&b"\xff\xfe"[..]), Err(()));
+/// ```
+///
+/// ## `#[enumeration(from_cstr)]`
+///
+/// Implements `TryFrom<&CStr>`, matching the bytes before the nul terminator like
+/// [`from_bytes`]. This avoids checking that a string received over FFI is valid UTF-8 before
+/// parsing it.
+///
+/// `#[enumeration(from_cstr_ptr = "...")]` additionally generates an unsafe associated function
+/// which parses a `*const c_char`, returning `None` if it is null. Its name and visibility are
+/// given like those of `parse_fn`.
+///
+/// ```
+/// use std::convert::TryFrom;
+/// use std::ffi::CStr;
+///
+/// #[derive(Debug, PartialEq, enum_utils::FromStr)]
+/// #[enumeration(from_cstr, from_cstr_ptr = "pub from_ptr", rename_all = "lowercase")]
+/// enum Level {
+///     Info,
+///     Warn,
+/// }
+///
+/// let s = CStr::from_bytes_with_nul(b"warn\0").unwrap();
+/// assert_eq!(Level::try_from(s), Ok(Level::Warn));
+/// assert_eq!(unsafe { Level::from_ptr(s.as_ptr()) }, Some(Level::Warn));
+/// assert_eq!(unsafe { Level::from_ptr(std::ptr::null()) }, None);
+/// ```
+///
+/// ## `#[enumeration(try_from_str)]`
+///
+/// Also implements `TryFrom<&str>` and `TryFrom<String>`, with the same behavior as `FromStr`.
+/// This is useful for APIs which are bounded on `TryFrom` rather than `FromStr`.
+///
+/// ```
+/// use std::convert::{TryFrom, TryInto};
+///
+/// #[derive(Debug, PartialEq, enum_utils::FromStr)]
+/// #[enumeration(try_from_str, rename_all = "lowercase")]
+/// enum Level {
+///     Debug,
+///     Info,
+/// }
+///
+/// assert_eq!(Level::try_from("info"), Ok(Level::Info));
+/// assert_eq!(String::from("debug").try_into(), Ok(Level::Debug));
+/// assert_eq!(Level::try_from("warn"), Err(()));
+/// ```
+///
+/// ## `#[enumeration(parse_fn = "...")]`
+///
+/// Also generates an associated function with the given name, which parses a byte slice in the
+/// same way as [`from_bytes`]. The name may be preceded by a visibility (e.g. `"pub
+/// parse_method"`), otherwise the function is private. This is useful for reusing the generated
+/// matcher in a parser that works on bytes, such as one written with `nom`.
+///
+/// ```
+/// #[derive(Debug, PartialEq, enum_utils::FromStr)]
+/// #[enumeration(parse_fn = "pub(crate) parse_method")]
+/// enum Method {
+///     GET,
+///     POST,
+/// }
+///
+/// let (token, rest) = b"POST /index.html".split_at(4);
+/// assert_eq!(Method::parse_method(token), Some(Method::POST));
+/// assert_eq!(rest, b" /index.html");
+/// assert_eq!(Method::parse_method(b"PUT"), None);
+/// ```
+///
+/// ## `#[enumeration(from_prefix)]`
+///
+/// Also generates `fn from_prefix(s: &[u8]) -> Option<(Self, usize)>`, which finds the longest
+/// name or alias that `s` starts with, and returns its variant along with the number of bytes
+/// matched. This is useful for writing a lexer by hand. `parse_tail` and [`other`] variants are
+/// ignored.
+///
+/// ```
+/// #[derive(Debug, PartialEq, enum_utils::FromStr)]
+/// #[enumeration(from_prefix)]
+/// enum Op {
+///     #[enumeration(rename = "<")]
+///     Lt,
+///     #[enumeration(rename = "<=")]
+///     Le,
+///     #[enumeration(rename = "<<")]
+///     Shl,
+/// }
+///
+/// assert_eq!(Op::from_prefix(b"<= 3"), Some((Op::Le, 2)));
+/// assert_eq!(Op::from_prefix(b"< 3"), Some((Op::Lt, 1)));
+/// assert_eq!(Op::from_prefix(b"> 3"), None);
+/// ```
+///
+/// ## `#[enumeration(canonical_name)]`
+///
+/// Also generates `fn canonical_name(s: &str) -> Option<&'static str>`, which maps any name or
+/// alias of a variant (in any case, if `case_insensitive` is set) to its canonical name. This is
+/// useful for normalizing user input without constructing the enum. Only exact matches are
+/// normalized, so `parse_tail` and [`other`] variants are ignored.
+///
+/// ```
+/// #[derive(enum_utils::FromStr)]
+/// #[enumeration(canonical_name, case_insensitive, rename_all = "snake_case")]
+/// enum Setting {
+///     #[enumeration(alias = "colour")]
+///     Color,
+///     FontSize,
+/// }
+///
+/// assert_eq!(Setting::canonical_name("COLOUR"), Some("color"));
+/// assert_eq!(Setting::canonical_name("Font_Size"), Some("font_size"));
+/// assert_eq!(Setting::canonical_name("size"), None);
+/// ```
+///
+/// ## `#[enumeration(parse_detailed)]`
+///
+/// Generates an associated function, `parse_detailed`, which returns the matching variant along
+/// with the name or alias it was matched by, as written in the enum definition, and whether that
+/// alias is `deprecated`. This tells whether a caller used the canonical name or an alias.
+/// Variants parsed with `parse_tail` or [`other`] are not matched by it.
+///
+/// ```
+/// #[derive(Debug, PartialEq, enum_utils::FromStr)]
+/// #[enumeration(parse_detailed, case_insensitive, rename_all = "lowercase")]
+/// enum Color {
+///     Red,
+///     #[enumeration(alias = "grey")]
+///     Gray,
+/// }
+///
+/// assert_eq!(Color::parse_detailed("GRAY"), Some((Color::Gray, "gray", false)));
+/// assert_eq!(Color::parse_detailed("grey"), Some((Color::Gray, "grey", false)));
+/// assert_eq!(Color::parse_detailed("blue"), None);
+/// ```
+///
+/// ## `#[enumeration(dash_underscore_insensitive)]`
+///
+/// This attribute can be applied to an entire enum, and makes `-` and `_` interchangeable when
+/// matching names and aliases. It combines with `case_insensitive`.
+///
+/// ```
+/// #[derive(Debug, PartialEq, enum_utils::FromStr)]
+/// #[enumeration(dash_underscore_insensitive, case_insensitive, rename_all = "kebab-case")]
+/// enum Flag {
+///     DryRun,
+///     NoColor,
+/// }
+///
+/// assert_eq!("dry-run".parse(), Ok(Flag::DryRun));
+/// assert_eq!("DRY_RUN".parse(), Ok(Flag::DryRun));
+/// assert_eq!("no_color".parse(), Ok(Flag::NoColor));
+/// assert_eq!("dryrun".parse::<Flag>(), Err(()));
+/// ```
+///
+/// ## `#[enumeration(trim)]`
+///
+/// Strips leading and trailing ASCII whitespace from the input before matching it. The catch-all
+/// variant (see [`other`]) receives the trimmed string. This also applies to `from_bytes` and
+/// `canonical_name`.
+///
+/// ```
+/// #[derive(Debug, PartialEq, enum_utils::FromStr)]
+/// #[enumeration(trim)]
+/// enum Mode {
+///     Fast,
+///     Safe,
+/// }
+///
+/// assert_eq!(" Fast\n".parse(), Ok(Mode::Fast));
+/// assert_eq!("\tSafe".parse(), Ok(Mode::Safe));
+/// assert_eq!("Sa fe".parse::<Mode>(), Err(()));
+/// ```
+///
+/// ## `#[enumeration(parse_error)]`
+///
+/// Returns an [`enum_utils_traits::ParseError`] instead of `()` when parsing fails. It holds the
+/// input and the canonical name of each variant, and implements `Display` and `Error`, so it can
+/// be reported to the user directly. This requires a dependency on the [`enum-utils-traits`]
+/// crate. `TryFrom<&[u8]>` still returns `()`, since its input may not be valid UTF-8.
+///
+/// ```
+/// #[derive(Debug, PartialEq, enum_utils::FromStr)]
+/// #[enumeration(parse_error, rename_all = "lowercase")]
+/// enum Color {
+///     Red,
+///     #[enumeration(alias = "grey")]
+///     Gray,
+/// }
+///
+/// let err = "blue".parse::<Color>().unwrap_err();
+/// assert_eq!(err.input(), "blue");
+/// assert_eq!(err.expected(), &["red", "gray"]);
+/// assert_eq!(err.to_string(), "unknown variant `blue`, expected `red` or `gray`");
+/// ```
+///
+/// If the input is only a few edits away from the name of a variant, that name is suggested by
+/// `ParseError::suggestion`, and in the error message.
+///
+/// ```
+/// # #[derive(Debug, PartialEq, enum_utils::FromStr)]
+/// # #[enumeration(parse_error, rename_all = "lowercase")]
+/// # enum Color {
+/// #     Red,
+/// #     Gray,
+/// # }
+/// let err = "grey".parse::<Color>().unwrap_err();
+/// assert_eq!(err.suggestion(), Some("gray"));
+/// assert_eq!(err.to_string(), "unknown variant `grey`, did you mean `gray`?");
+/// ```
+///
+/// ## `#[enumeration(expecting = "...")]`
+///
+/// Describes the expected input in error messages instead of listing the name of every variant.
+/// It is used by the `Display` implementation of `ParseError` and by the [`Deserialize`] derive.
+///
+/// ```
+/// #[derive(Debug, PartialEq, enum_utils::FromStr)]
+/// #[enumeration(parse_error, expecting = "a compass direction")]
+/// enum Direction {
+///     North,
+///     South,
+/// }
+///
+/// let err = "up".parse::<Direction>().unwrap_err();
+/// assert_eq!(err.to_string(), "unknown variant `up`, expected a compass direction");
+/// ```
+///
+/// ## `#[enumeration(no_panic)]`
+///
+/// The generated parser never indexes into its input, so it has no bounds checks and no
+/// panicking paths of its own. This attribute asserts that this holds for the parser as a whole,
+/// which is useful when auditing for panics (e.g. with [`no-panic`]) on embedded targets. It is
+/// an error to combine it with attributes which call code outside the parser or which allocate:
+/// `on_miss`, `parse_tail`, `parse_error`, `case_insensitive = "unicode"` and an [`other`]
+/// variant with a field.
+///
+/// ```compile_fail
+/// #[derive(enum_utils::FromStr)]
+/// #[enumeration(no_panic)]
+/// enum Value {
+///     Null,
+///     #[enumeration(parse_tail)]
+///     Int(i64),
+/// }
+/// ```
+///
+/// ```compile_fail
+/// #[derive(enum_utils::FromStr)]
+/// #[enumeration(no_panic, parse_error)]
+/// enum Value {
+///     Null,
+/// }
+/// ```
+///
+/// ```compile_fail
+/// #[derive(enum_utils::FromStr)]
+/// #[enumeration(no_panic)]
+/// enum Value {
+///     #[enumeration(case_insensitive = "unicode")]
+///     Straße,
+/// }
+/// ```
+///
+/// ## `#[enumeration(parser = "...")]`
+///
+/// Selects the code generated to match names and aliases. This never changes which strings are
+/// accepted, only how quickly. The options are:
+///
+/// - `"trie"` (the default): dispatch on the length of the input, then compare it one byte at a
+///   time, sharing comparisons between names with a common prefix. If many names begin with
+///   different characters, the first byte of the input is dispatched on before its length.
+/// - `"match"`: compare the input with each name in turn using a single `match` expression,
+///   leaving the choice of strategy to the compiler.
+/// - `"table"`: dispatch on the length of the input, then search a table of the names of that
+///   length.
+///
+/// Which is fastest depends on the names, so it is best to measure.
+///
+/// ```
+/// #[derive(Debug, PartialEq, enum_utils::FromStr)]
+/// #[enumeration(parser = "match", rename_all = "UPPERCASE")]
+/// enum Method {
+///     Get,
+///     Post,
+/// }
+///
+/// assert_eq!("GET".parse(), Ok(Method::Get));
+/// assert_eq!("POST".parse(), Ok(Method::Post));
+/// ```
+///
+/// ## `#[enumeration(inline = "...")]`
+///
+/// Places `#[inline(always)]` or `#[inline(never)]` on `from_str` and on every other function
+/// generated by this derive, such as `try_from` and `canonical_name`. Without it, the compiler
+/// decides for itself. `"never"` keeps a large parser out of each of its callers, while
+/// `"always"` lets a parser for a handful of names be folded into the surrounding code.
+///
+/// ```
+/// #[derive(Debug, PartialEq, enum_utils::FromStr)]
+/// #[enumeration(inline = "never")]
+/// enum Method {
+///     Get,
+///     Post,
+/// }
+///
+/// assert_eq!("Get".parse(), Ok(Method::Get));
+/// ```
+///
+/// [`enum_utils_traits::ParseError`]: https://docs.rs/enum-utils-traits/0.1/enum_utils_traits/struct.ParseError.html
+/// [`enum-utils-traits`]: https://docs.rs/enum-utils-traits/0.1
+/// [`no-panic`]: https://docs.rs/no-panic
+/// [`FromStr`]: https://doc.rust-lang.org/std/str/trait.FromStr.html
+/// [`serde`]: https://serde.rs/attributes.html
+/// [`rename`]: #enumerationrename--
+/// [`rename_all`]: #enumerationrename_all--
+/// [`other`]: #enumerationother
+/// [`Enum`]: derive.Enum.html
+/// [`from_bytes`]: #enumerationfrom_bytes
+/// [`parse_detailed`]: #enumerationparse_detailed
+/// [`Deserialize`]: derive.Deserialize.html
+/// [`Display`]: derive.Display.html
+/// [rename rule]: https://serde.rs/container-attrs.html#rename_all
+#[proc_macro_derive(FromStr, attributes(enumeration))]
+pub fn from_str_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    derive_scoped("FromStr", ast, from_str::derive).into()
+}
+
+/// Derives [`serde::Deserialize`] for a C-like enum, which reads a variant from a string using the
+/// same matcher as [`FromStr`].
+///
+/// This requires the `serde` feature, and a dependency on version 1 of the [`serde`] crate.
+///
+/// Variant names are determined exactly as they are for [`FromStr`], so all of its attributes
+/// (e.g. `rename`, `alias`, `rename_all`, `case_insensitive` and `other`) are supported. Unlike
+/// the derive in `serde` itself, the input is matched against a trie instead of each name in turn.
+///
+/// [`serde::Deserialize`]: https://docs.rs/serde/1/serde/trait.Deserialize.html
+/// [`serde`]: https://docs.rs/serde/1
+/// [`FromStr`]: derive.FromStr.html
+///
+/// # Examples
+///
+/// ```
+/// #[derive(Debug, PartialEq, enum_utils::Deserialize)]
+/// #[enumeration(case_insensitive, rename_all = "snake_case")]
+/// pub enum Format {
+///     Json,
+///     #[enumeration(alias = "yml")]
+///     Yaml,
+///     PlainText,
+/// }
+///
+/// let formats: Vec<Format> = serde_json::from_str(r#"["JSON", "yml", "plain_text"]"#).unwrap();
+/// assert_eq!(formats, vec![Format::Json, Format::Yaml, Format::PlainText]);
+///
+/// assert!(serde_json::from_str::<Format>(r#""xml""#).is_err());
+/// ```
+#[cfg(feature = "serde")]
+#[proc_macro_derive(Deserialize, attributes(enumeration))]
+pub fn deserialize_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    derive_scoped("Deserialize", ast, serde::derive_deserialize).into()
+}
+
+/// Derives [`serde::Serialize`], which writes the canonical name of each variant as a string.
+///
+/// This requires the `serde` feature, and a dependency on version 1 of the [`serde`] crate.
+///
+/// The canonical name is the one produced by [`Display`] and accepted by [`FromStr`], so both
+/// directions share the same `#[enumeration(...)]` attributes. If the catch-all `other` variant
+/// has a field, that field is serialized instead. Serializing any other skipped variant returns
+/// an error.
+///
+/// [`serde::Serialize`]: https://docs.rs/serde/1/serde/trait.Serialize.html
+/// [`serde`]: https://docs.rs/serde/1
+/// [`Display`]: derive.Display.html
+/// [`FromStr`]: derive.FromStr.html
+///
+/// # Examples
+///
+/// ```
+/// #[derive(Debug, PartialEq, enum_utils::Serialize, enum_utils::Deserialize)]
+/// #[enumeration(rename_all = "SCREAMING_SNAKE_CASE")]
+/// pub enum Status {
+///     InProgress,
+///     Done,
+///     #[enumeration(other)]
+///     Custom(String),
+/// }
+///
+/// let json = serde_json::to_string(&[Status::InProgress, Status::Custom("Blocked".into())]).unwrap();
+/// assert_eq!(json, r#"["IN_PROGRESS","Blocked"]"#);
+///
+/// let statuses: Vec<Status> = serde_json::from_str(&json).unwrap();
+/// assert_eq!(statuses, vec![Status::InProgress, Status::Custom("Blocked".into())]);
+/// ```
+#[cfg(feature = "serde")]
+#[proc_macro_derive(Serialize, attributes(enumeration))]
+pub fn serialize_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    derive_scoped("Serialize", ast, serde::derive_serialize).into()
+}
+
+/// Derives [`serde::Serialize`] for a C-like enum, which writes the discriminant of each variant
+/// as its [primitive representation].
+///
+/// This requires the `serde` feature, and a dependency on version 1 of the [`serde`] crate. Like
+/// [`TryFromRepr`], a primitive `#[repr(...)]` must be specified and `assert_discriminants` is
+/// supported.
+///
+/// [`serde::Serialize`]: https://docs.rs/serde/1/serde/trait.Serialize.html
+/// [`serde`]: https://docs.rs/serde/1
+/// [`TryFromRepr`]: derive.TryFromRepr.html
+/// [primitive representation]: https://doc.rust-lang.org/reference/type-layout.html#primitive-representations
+///
+/// # Examples
+///
+/// ```
+/// #[derive(Debug, PartialEq, enum_utils::SerializeRepr, enum_utils::DeserializeRepr)]
+/// #[repr(i8)]
+/// pub enum Sign {
+///     Negative = -1,
+///     Zero,
+///     Positive,
+/// }
+///
+/// assert_eq!(serde_json::to_string(&[Sign::Negative, Sign::Positive]).unwrap(), "[-1,1]");
+/// assert_eq!(serde_json::from_str::<Sign>("0").unwrap(), Sign::Zero);
+/// ```
+#[cfg(feature = "serde")]
+#[proc_macro_derive(SerializeRepr, attributes(enumeration))]
+pub fn serialize_repr_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    derive_scoped("SerializeRepr", ast, serde::derive_serialize_repr).into()
+}
+
+/// Derives [`serde::Deserialize`] for a C-like enum, which reads a value of its [primitive
+/// representation] and returns the variant with that discriminant.
+///
+/// This requires the `serde` feature, and a dependency on version 1 of the [`serde`] crate. The
+/// conversion is the same one derived by [`TryFromRepr`]. Any value which is not the discriminant
+/// of a variant is an error.
+///
+/// [`serde::Deserialize`]: https://docs.rs/serde/1/serde/trait.Deserialize.html
+/// [`serde`]: https://docs.rs/serde/1
+/// [`TryFromRepr`]: derive.TryFromRepr.html
+/// [primitive representation]: https://doc.rust-lang.org/reference/type-layout.html#primitive-representations
+///
+/// # Examples
+///
+/// ```
+/// #[derive(Debug, PartialEq, enum_utils::DeserializeRepr)]
+/// #[repr(u16)]
+/// pub enum Port {
+///     Http = 80,
+///     Https = 443,
+/// }
+///
+/// assert_eq!(serde_json::from_str::<Port>("443").unwrap(), Port::Https);
+///
+/// let err = serde_json::from_str::<Port>("8080").unwrap_err();
+/// assert!(err.to_string().starts_with("invalid discriminant `8080` for `Port`"));
+/// ```
+#[cfg(feature = "serde")]
+#[proc_macro_derive(DeserializeRepr, attributes(enumeration))]
+pub fn deserialize_repr_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    derive_scoped("DeserializeRepr", ast, serde::derive_deserialize_repr).into()
+}
+
+/// Derives [`schemars::JsonSchema`], which describes an enum as a string restricted to the names
+/// accepted by [`FromStr`].
+///
+/// This requires the `schemars` feature, and a dependency on version 1 of the [`schemars`] crate.
+///
+/// The schema lists the canonical name of each unskipped variant in declaration order, followed
+/// by any aliases. If there is a catch-all `other` variant, any string is accepted, so the names
+/// are not listed. JSON Schema has no way to express `case_insensitive`, so only the names as
+/// written are listed.
+///
+/// [`schemars::JsonSchema`]: https://docs.rs/schemars/1/schemars/trait.JsonSchema.html
+/// [`schemars`]: https://docs.rs/schemars/1
+/// [`FromStr`]: derive.FromStr.html
+///
+/// # Examples
+///
+/// ```
+/// #[derive(enum_utils::JsonSchema)]
+/// #[enumeration(rename_all = "snake_case")]
+/// pub enum Unit {
+///     Meters,
+///     #[enumeration(alias = "ft")]
+///     Feet,
+/// }
+///
+/// let schema = schemars::schema_for!(Unit);
+/// assert_eq!(schema.get("enum").unwrap(), &serde_json::json!(["meters", "feet", "ft"]));
+/// ```
+#[cfg(feature = "schemars")]
+#[proc_macro_derive(JsonSchema, attributes(enumeration))]
+pub fn json_schema_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    derive_scoped("JsonSchema", ast, schemars::derive).into()
+}
+
+/// Derives the [`async-graphql`] traits for a GraphQL enum type whose values are the names
+/// accepted by [`FromStr`].
+///
+/// This requires the `async-graphql` feature, and a dependency on version 7 of the
+/// [`async-graphql`] crate without its `boxed-trait` feature. It is a replacement for
+/// `#[derive(async_graphql::Enum)]` which respects `rename`, `rename_all` and `skip`, so a single
+/// set of attributes controls both the GraphQL schema and the parser. Aliases are not part of the
+/// schema, and the first paragraph of the doc comment on each variant becomes its description.
+///
+/// As with `async_graphql::Enum`, the enum must implement `Copy` and `Eq`. Converting a skipped
+/// variant to a GraphQL value will panic.
+///
+/// [`async-graphql`]: https://docs.rs/async-graphql/7
+/// [`FromStr`]: derive.FromStr.html
+///
+/// # Examples
+///
+/// ```
+/// use async_graphql::{InputType, Value};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, enum_utils::GraphQLEnum)]
+/// #[enumeration(rename_all = "SCREAMING_SNAKE_CASE")]
+/// enum Episode {
+///     NewHope,
+///     #[enumeration(rename = "EMPIRE")]
+///     EmpireStrikesBack,
+/// }
+///
+/// assert_eq!(Episode::NewHope.to_value(), Value::Enum(async_graphql::Name::new("NEW_HOPE")));
+/// assert_eq!(Episode::parse(Some(Value::Enum(async_graphql::Name::new("EMPIRE")))).ok(),
+///            Some(Episode::EmpireStrikesBack));
+/// ```
+#[cfg(feature = "async-graphql")]
+#[proc_macro_derive(GraphQLEnum, attributes(enumeration))]
+pub fn graphql_enum_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    derive_scoped("GraphQLEnum", ast, graphql::derive).into()
+}
+
+/// Derives [`utoipa::ToSchema`], which describes an enum in an OpenAPI document as a string
+/// restricted to the names accepted by [`FromStr`].
+///
+/// This requires the `utoipa` feature, and a dependency on version 5 of the [`utoipa`] crate.
+/// The canonical name of each variant is listed in the schema, and any aliases are given as
+/// examples. If there is an [`other`] variant, any string is allowed, and all the names and
+/// aliases are given as examples.
+///
+/// [`utoipa::ToSchema`]: https://docs.rs/utoipa/5/utoipa/trait.ToSchema.html
+/// [`utoipa`]: https://docs.rs/utoipa/5
+/// [`FromStr`]: derive.FromStr.html
+/// [`other`]: derive.FromStr.html#enumerationother
+///
+/// # Examples
+///
+/// ```
+/// use utoipa::PartialSchema;
+///
+/// #[derive(enum_utils::ToSchema)]
+/// #[enumeration(rename_all = "lowercase")]
+/// enum Unit {
+///     Meters,
+///     #[enumeration(alias = "ft")]
+///     Feet,
+/// }
+///
+/// let schema = serde_json::to_value(Unit::schema()).unwrap();
+/// assert_eq!(schema["enum"], serde_json::json!(["meters", "feet"]));
+/// assert_eq!(schema["examples"], serde_json::json!(["ft"]));
+/// ```
+#[cfg(feature = "utoipa")]
+#[proc_macro_derive(ToSchema, attributes(enumeration))]
+pub fn to_schema_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    derive_scoped("ToSchema", ast, utoipa::derive).into()
+}
+
+/// Derives [`sqlx::Type`], [`sqlx::Encode`] and [`sqlx::Decode`], which store an enum as the
+/// canonical name of each variant in a text column.
+///
+/// This requires the `sqlx` feature, and a dependency on version 0.8 of the [`sqlx`] crate. The
+/// implementations are generic over any database which can store a `&str`.
+///
+/// Values are encoded with the same names produced by [`Display`], and decoded with the same
+/// matcher as [`FromStr`], so `rename`, `alias`, `rename_all`, `case_insensitive` and `other` are
+/// all supported. A catch-all `other` variant with a field is encoded as that field, which must
+/// implement `AsRef<str>`. Encoding any other skipped variant returns an error.
+///
+/// [`sqlx::Type`]: https://docs.rs/sqlx/0.8/sqlx/trait.Type.html
+/// [`sqlx::Encode`]: https://docs.rs/sqlx/0.8/sqlx/trait.Encode.html
+/// [`sqlx::Decode`]: https://docs.rs/sqlx/0.8/sqlx/trait.Decode.html
+/// [`sqlx`]: https://docs.rs/sqlx/0.8
+/// [`Display`]: derive.Display.html
+/// [`FromStr`]: derive.FromStr.html
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn run(pool: sqlx::SqlitePool) -> sqlx::Result<()> {
+/// #[derive(Debug, PartialEq, enum_utils::SqlxType)]
+/// #[enumeration(rename_all = "snake_case")]
+/// pub enum OrderStatus {
+///     Pending,
+///     Shipped,
+/// }
+///
+/// sqlx::query("INSERT INTO orders (status) VALUES (?)")
+///     .bind(OrderStatus::Shipped)
+///     .execute(&pool)
+///     .await?;
+///
+/// let status: OrderStatus = sqlx::query_scalar("SELECT status FROM orders")
+///     .fetch_one(&pool)
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "sqlx")]
+#[proc_macro_derive(SqlxType, attributes(enumeration))]
+pub fn sqlx_type_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    derive_scoped("SqlxType", ast, sqlx::derive).into()
+}
+
+/// Derives Diesel's [`ToSql`] and [`FromSql`], which store an enum as the canonical name of each
+/// variant in a `Text` column.
+///
+/// This requires the `diesel` feature, and a dependency on version 2 of the [`diesel`] crate. The
+/// implementations are generic over the backend. To use the enum in queries, also derive
+/// Diesel's `AsExpression` and `FromSqlRow` with the appropriate `#[diesel(sql_type = ...)]`.
+///
+/// Values are stored with the same names produced by [`Display`], and loaded with the same
+/// matcher as [`FromStr`], so `rename`, `alias`, `rename_all`, `case_insensitive` and `other` are
+/// all supported. A catch-all `other` variant with a field is stored as that field, which must
+/// implement `AsRef<str>`. Storing any other skipped variant returns an error.
+///
+/// [`ToSql`]: https://docs.rs/diesel/2/diesel/serialize/trait.ToSql.html
+/// [`FromSql`]: https://docs.rs/diesel/2/diesel/deserialize/trait.FromSql.html
+/// [`diesel`]: https://docs.rs/diesel/2
+/// [`Display`]: derive.Display.html
+/// [`FromStr`]: derive.FromStr.html
+///
+/// # Examples
+///
+/// ```
+/// use diesel::deserialize::FromSqlRow;
+/// use diesel::expression::AsExpression;
+/// use diesel::prelude::*;
+/// use diesel::sql_types::Text;
+///
+/// #[derive(Debug, PartialEq, AsExpression, FromSqlRow, enum_utils::DieselSql)]
+/// #[diesel(sql_type = Text)]
+/// #[enumeration(rename_all = "snake_case")]
+/// pub enum Role {
+///     Admin,
+///     ReadOnly,
+/// }
+///
+/// let mut conn = SqliteConnection::establish(":memory:").unwrap();
+/// let role = diesel::select(Role::ReadOnly.into_sql::<Text>())
+///     .get_result::<Role>(&mut conn)
+///     .unwrap();
+/// assert_eq!(role, Role::ReadOnly);
+/// ```
+///
+/// # Attributes
+///
+/// ## `#[enumeration(sql_repr)]`
+///
+/// Stores each variant as its discriminant instead of its name. The enum must have a
+/// `#[repr(i16)]`, `#[repr(i32)]` or `#[repr(i64)]`, which is stored as a `SmallInt`, `Integer` or
+/// `BigInt` respectively. Like [`TryFromRepr`], `assert_discriminants` is supported.
+///
+/// [`TryFromRepr`]: derive.TryFromRepr.html
+///
+/// ```
+/// use diesel::deserialize::FromSqlRow;
+/// use diesel::expression::AsExpression;
+/// use diesel::prelude::*;
+/// use diesel::sql_types::Integer;
+///
+/// #[derive(Debug, PartialEq, AsExpression, FromSqlRow, enum_utils::DieselSql)]
+/// #[diesel(sql_type = Integer)]
+/// #[enumeration(sql_repr)]
+/// #[repr(i32)]
+/// pub enum Priority {
+///     Low = 1,
+///     High = 10,
+/// }
+///
+/// let mut conn = SqliteConnection::establish(":memory:").unwrap();
+/// let raw = diesel::select(Priority::High.into_sql::<Integer>())
+///     .get_result::<i32>(&mut conn)
+///     .unwrap();
+/// assert_eq!(raw, 10);
+/// ```
+#[cfg(feature = "diesel")]
+#[proc_macro_derive(DieselSql, attributes(enumeration))]
+pub fn diesel_sql_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    derive_scoped("DieselSql", ast, diesel::derive).into()
+}
+
+/// Derives [`postgres_types::FromSql`] and [`postgres_types::ToSql`], which map an enum onto a
+/// Postgres `ENUM` type.
+///
+/// This requires the `postgres-types` feature, and a dependency on version 0.2 of the
+/// [`postgres-types`] crate (or on a crate which re-exports it, such as `postgres`).
+///
+/// Each variant is stored as its canonical name, and labels are read with the same matcher as
+/// [`FromStr`], so `rename`, `alias`, `rename_all`, `case_insensitive` and `other` are all
+/// supported. Storing a skipped variant other than a catch-all with a field returns an error.
+///
+/// Both traits only accept a Postgres enum with the same name as the Rust enum. Unless there is a
+/// catch-all `other` variant, its labels must also be exactly the canonical names of the
+/// unskipped variants.
+///
+/// [`postgres_types::FromSql`]: https://docs.rs/postgres-types/0.2/postgres_types/trait.FromSql.html
+/// [`postgres_types::ToSql`]: https://docs.rs/postgres-types/0.2/postgres_types/trait.ToSql.html
+/// [`postgres-types`]: https://docs.rs/postgres-types/0.2
+/// [`FromStr`]: derive.FromStr.html
+///
+/// # Examples
+///
+/// ```
+/// use postgres_types::{FromSql, Kind, Type};
+///
+/// #[derive(Debug, PartialEq, enum_utils::PostgresEnum)]
+/// #[enumeration(rename_all = "lowercase", pg_name = "mood")]
+/// pub enum Mood {
+///     Happy,
+///     Sad,
+/// }
+///
+/// let ty = Type::new("mood".into(), 0, Kind::Enum(vec!["happy".into(), "sad".into()]), "public".into());
+/// assert!(<Mood as FromSql>::accepts(&ty));
+/// assert_eq!(Mood::from_sql(&ty, b"sad").unwrap(), Mood::Sad);
+/// ```
+///
+/// # Attributes
+///
+/// ## `#[enumeration(pg_name = "...")]`
+///
+/// The name of the Postgres enum type, if it differs from the name of the Rust enum.
+#[cfg(feature = "postgres-types")]
+#[proc_macro_derive(PostgresEnum, attributes(enumeration))]
+pub fn postgres_enum_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    derive_scoped("PostgresEnum", ast, postgres::derive).into()
+}
+
+/// Derives rusqlite's [`ToSql`] and [`FromSql`], which store an enum as the canonical name of each
+/// variant in a `TEXT` value.
+///
+/// This requires the `rusqlite` feature, and a dependency on version 0.32 of the [`rusqlite`]
+/// crate.
+///
+/// Values are stored with the same names produced by [`Display`], and loaded with the same
+/// matcher as [`FromStr`], so `rename`, `alias`, `rename_all`, `case_insensitive` and `other` are
+/// all supported. A catch-all `other` variant with a field is stored as that field, which must
+/// implement `AsRef<str>`. Storing any other skipped variant returns an error, and loading a
+/// value which matches no variant returns `FromSqlError::InvalidType`.
+///
+/// [`ToSql`]: https://docs.rs/rusqlite/0.32/rusqlite/types/trait.ToSql.html
+/// [`FromSql`]: https://docs.rs/rusqlite/0.32/rusqlite/types/trait.FromSql.html
+/// [`rusqlite`]: https://docs.rs/rusqlite/0.32
+/// [`Display`]: derive.Display.html
+/// [`FromStr`]: derive.FromStr.html
+///
+/// # Examples
+///
+/// ```
+/// use rusqlite::Connection;
+///
+/// #[derive(Debug, PartialEq, enum_utils::RusqliteSql)]
+/// #[enumeration(rename_all = "snake_case")]
+/// pub enum Role {
+///     Admin,
+///     ReadOnly,
+/// }
+///
+/// let conn = Connection::open_in_memory().unwrap();
+/// let role: Role = conn.query_row("SELECT ?1", [Role::ReadOnly], |row| row.get(0)).unwrap();
+/// assert_eq!(role, Role::ReadOnly);
+///
+/// let name: String = conn.query_row("SELECT ?1", [Role::ReadOnly], |row| row.get(0)).unwrap();
+/// assert_eq!(name, "read_only");
+/// ```
+///
+/// # Attributes
+///
+/// ## `#[enumeration(sql_repr)]`
+///
+/// Stores each variant as its discriminant in an `INTEGER` value instead of its name. The enum
+/// must have a `#[repr(...)]` which fits in an `i64`. Loading an integer which is out of range for
+/// the repr returns `FromSqlError::OutOfRange`. Like [`TryFromRepr`], `assert_discriminants` is
+/// supported.
+///
+/// [`TryFromRepr`]: derive.TryFromRepr.html
+///
+/// ```
+/// use rusqlite::Connection;
+///
+/// #[derive(Debug, PartialEq, enum_utils::RusqliteSql)]
+/// #[enumeration(sql_repr)]
+/// #[repr(u8)]
+/// pub enum Priority {
+///     Low = 1,
+///     High = 10,
+/// }
+///
+/// let conn = Connection::open_in_memory().unwrap();
+/// let raw: i64 = conn.query_row("SELECT ?1", [Priority::High], |row| row.get(0)).unwrap();
+/// assert_eq!(raw, 10);
+/// ```
+#[cfg(feature = "rusqlite")]
+#[proc_macro_derive(RusqliteSql, attributes(enumeration))]
+pub fn rusqlite_sql_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    derive_scoped("RusqliteSql", ast, rusqlite::derive).into()
+}
+
+/// Derives [`num_traits::FromPrimitive`] for a C-like enum, which returns the variant with a given
+/// discriminant.
+///
+/// This requires the `num-traits` feature, and a dependency on version 0.2 of the [`num-traits`]
+/// crate. Like [`TryFromRepr`], a primitive `#[repr(...)]` must be specified and
+/// `assert_discriminants` is supported. Values which do not fit in the repr, or which are not the
+/// discriminant of a variant, return `None`.
+///
+/// If the discriminants form a single, contiguous run, the conversion is a bounds check instead of
+/// a `match`. With `#[enumeration(debug_checks)]`, the discriminant is also verified against the
+/// list of variants in debug builds, as for [`IterVariants`].
+///
+/// [`num_traits::FromPrimitive`]: https://docs.rs/num-traits/0.2/num_traits/cast/trait.FromPrimitive.html
+/// [`num-traits`]: https://docs.rs/num-traits/0.2
+/// [`TryFromRepr`]: derive.TryFromRepr.html
+/// [`IterVariants`]: derive.IterVariants.html
+///
+/// # Examples
+///
+/// ```
+/// use num_traits::FromPrimitive;
+///
+/// #[derive(Debug, PartialEq, enum_utils::FromPrimitive)]
+/// #[repr(u8)]
+/// pub enum Opcode {
+///     Nop = 0,
+///     Load,
+///     Store,
+/// }
+///
+/// assert_eq!(Opcode::from_u8(2), Some(Opcode::Store));
+/// assert_eq!(Opcode::from_i32(-1), None);
+/// assert_eq!(Opcode::from_u64(3), None);
+/// ```
+#[cfg(feature = "num-traits")]
+#[proc_macro_derive(FromPrimitive, attributes(enumeration))]
+pub fn from_primitive_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    derive_scoped("FromPrimitive", ast, num::derive_from_primitive).into()
+}
+
+/// Derives [`num_traits::ToPrimitive`] for a C-like enum, which converts each variant to its
+/// discriminant.
+///
+/// This requires the `num-traits` feature, and a dependency on version 0.2 of the [`num-traits`]
+/// crate. Like [`TryFromRepr`], a primitive `#[repr(...)]` must be specified and
+/// `assert_discriminants` is supported.
+///
+/// [`num_traits::ToPrimitive`]: https://docs.rs/num-traits/0.2/num_traits/cast/trait.ToPrimitive.html
+/// [`num-traits`]: https://docs.rs/num-traits/0.2
+/// [`TryFromRepr`]: derive.TryFromRepr.html
+///
+/// # Examples
+///
+/// ```
+/// use num_traits::ToPrimitive;
+///
+/// #[derive(enum_utils::ToPrimitive)]
+/// #[repr(i16)]
+/// pub enum Offset {
+///     Back = -1,
+///     Forward = 300,
+/// }
+///
+/// assert_eq!(Offset::Forward.to_i16(), Some(300));
+/// assert_eq!(Offset::Forward.to_u8(), None);
+/// assert_eq!(Offset::Back.to_u64(), None);
+/// ```
+#[cfg(feature = "num-traits")]
+#[proc_macro_derive(ToPrimitive, attributes(enumeration))]
+pub fn to_primitive_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    derive_scoped("ToPrimitive", ast, num::derive_to_primitive).into()
+}
+
+/// Derives [`bytemuck::CheckedBitPattern`] and [`bytemuck::NoUninit`] for a C-like enum, so it
+/// can be cast to and from raw bytes.
+///
+/// This requires the `bytemuck` feature, and a dependency on version 1 of the [`bytemuck`]
+/// crate. The enum must implement `Copy`. Like [`TryFromRepr`], a primitive `#[repr(...)]` must
+/// be specified and `assert_discriminants` is supported. A bit pattern is valid exactly when it
+/// is the discriminant of one of the variants.
+///
+/// [`bytemuck::CheckedBitPattern`]: https://docs.rs/bytemuck/1/bytemuck/checked/trait.CheckedBitPattern.html
+/// [`bytemuck::NoUninit`]: https://docs.rs/bytemuck/1/bytemuck/trait.NoUninit.html
+/// [`bytemuck`]: https://docs.rs/bytemuck/1
+/// [`TryFromRepr`]: derive.TryFromRepr.html
+///
+/// # Examples
+///
+/// ```
+/// #[derive(Debug, Clone, Copy, PartialEq, enum_utils::CheckedBitPattern)]
+/// #[repr(u8)]
+/// enum Kind {
+///     File = 1,
+///     Dir = 2,
+///     Link = 8,
+/// }
+///
+/// let kinds: &[Kind] = bytemuck::checked::cast_slice(&[8u8, 1, 2]);
+/// assert_eq!(kinds, &[Kind::Link, Kind::File, Kind::Dir]);
+/// assert!(bytemuck::checked::try_cast::<u8, Kind>(3).is_err());
+/// assert_eq!(bytemuck::bytes_of(&Kind::Dir), &[2]);
+/// ```
+#[cfg(feature = "bytemuck")]
+#[proc_macro_derive(CheckedBitPattern, attributes(enumeration))]
+pub fn checked_bit_pattern_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    derive_scoped("CheckedBitPattern", ast, bytemuck::derive).into()
+}
+
+/// Derives [`clap::ValueEnum`], so that an enum can be used as the value of a command-line
+/// argument.
+///
+/// This requires the `clap` feature, and a dependency on version 4 of the [`clap`] crate.
+///
+/// Each unskipped variant becomes a possible value, named and aliased exactly as it is for
+/// [`FromStr`]. The help text for a value is the first paragraph of the variant's doc comment, or
+/// its `#[enumeration(message = "...")]` if given (see [`EnumMessage`]). Like any `ValueEnum`, the
+/// enum must implement `Clone`.
+///
+/// [`clap::ValueEnum`]: https://docs.rs/clap/4/clap/trait.ValueEnum.html
+/// [`clap`]: https://docs.rs/clap/4
+/// [`FromStr`]: derive.FromStr.html
+/// [`EnumMessage`]: derive.EnumMessage.html
+///
+/// # Examples
+///
+/// ```
+/// use clap::ValueEnum;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, enum_utils::ValueEnum)]
+/// #[enumeration(rename_all = "lowercase")]
+/// pub enum Color {
+///     /// Always use colors.
+///     Always,
+///     /// Never use colors.
+///     Never,
+///     /// Use colors if the output is a terminal.
+///     #[enumeration(alias = "tty")]
+///     Auto,
+/// }
+///
+/// assert_eq!(Color::from_str("tty", false), Ok(Color::Auto));
+/// assert_eq!(Color::from_str("NEVER", true), Ok(Color::Never));
+///
+/// let value = Color::Always.to_possible_value().unwrap();
+/// assert_eq!(value.get_name(), "always");
+/// assert_eq!(value.get_help().unwrap().to_string(), "Always use colors.");
+/// ```
+#[cfg(feature = "clap")]
+#[proc_macro_derive(ValueEnum, attributes(enumeration))]
+pub fn value_enum_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    derive_scoped("ValueEnum", ast, clap::derive).into()
+}
+
+/// Derives [`defmt::Format`], which logs the canonical name of each variant.
+///
+/// This requires the `defmt` feature, and a dependency on version 1 of the [`defmt`] crate.
+///
+/// Each name is written as an interned string, so the output matches [`Display`] without pulling
+/// `core::fmt` into the binary. Skipped variants are logged as their identifier, like [`AsStr`].
+///
+/// [`defmt::Format`]: https://docs.rs/defmt/1/defmt/trait.Format.html
+/// [`defmt`]: https://docs.rs/defmt/1
+/// [`Display`]: derive.Display.html
+/// [`AsStr`]: derive.AsStr.html
+///
+/// # Examples
+///
+/// ```no_run
+/// #[derive(Clone, Copy, enum_utils::Format)]
+/// #[enumeration(rename_all = "lowercase")]
+/// pub enum State {
+///     Idle,
+///     Running,
+/// }
+///
+/// fn transition(from: State, to: State) {
+///     defmt::info!("{} -> {}", from, to);
+/// }
+/// ```
+#[cfg(feature = "defmt")]
+#[proc_macro_derive(Format, attributes(enumeration))]
+pub fn format_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    derive_scoped("Format", ast, defmt::derive).into()
+}
+
+/// Derives [`Display`], writing the same name which is accepted by the [`FromStr`] derive.
+///
+/// Variants may have fields, which are ignored: only the name of the variant is written. The
+/// same is true of the other derives which only convert a variant to its name ([`AsStr`],
+/// [`AsRefStr`], [`PartialEqStr`], [`VariantNames`] and `Format`), so they can be used with
+/// message enums which carry data.
+///
+/// [`AsStr`]: derive.AsStr.html
+/// [`AsRefStr`]: derive.AsRefStr.html
+/// [`PartialEqStr`]: derive.PartialEqStr.html
+/// [`VariantNames`]: derive.VariantNames.html
+///
+/// # Examples
+///
+/// ```
+/// #[derive(Debug, PartialEq, enum_utils::Display, enum_utils::FromStr)]
+/// #[enumeration(rename_all = "snake_case")]
+/// enum Test {
+///     Alpha,
+///     #[enumeration(rename = "β")]
+///     Beta,
+///     GammaDelta,
+/// }
+///
+/// assert_eq!(Test::Alpha.to_string(), "alpha");
+/// assert_eq!(Test::Beta.to_string(), "β");
+/// assert_eq!(Test::GammaDelta.to_string(), "gamma_delta");
+///
+/// for s in &["alpha", "β", "gamma_delta"] {
+///     assert_eq!(&s.parse::<Test>().unwrap().to_string(), s);
+/// }
+/// ```
+///
+/// # Attributes
+///
+/// `Display` supports the `rename` and `rename_all` attributes described in the documentation for
+/// [`FromStr`][derive-from-str]. Since it emits a single name for each variant, `alias` has no
+/// effect.
+///
+/// ## `#[enumeration(skip)]`
+///
+/// A skipped variant is written as its identifier, like in [`AsStr`], unless it has a
+/// [`format`](#enumerationformat--) template.
+///
+/// ```
+/// #[derive(Debug, PartialEq, enum_utils::Display)]
+/// #[enumeration(rename_all = "lowercase")]
+/// enum Skip {
+///     Alpha,
+///     #[enumeration(skip)]
+///     Other(u8),
+/// }
+///
+/// assert_eq!(Skip::Alpha.to_string(), "alpha");
+/// assert_eq!(Skip::Other(42).to_string(), "Other");
+/// ```
+///
+/// ## `#[enumeration(format = "...")]`
+///
+/// Formats a variant using a template instead of its name. The template uses the same syntax as
+/// [`format!`]. The fields of a tuple variant are referred to by position (`{0}`), and those of a
+/// struct variant by name (`{field}`). Fields which are not referred to are ignored. This pairs
+/// well with `parse_tail` in [`FromStr`][derive-from-str].
+///
+/// ```
+/// #[derive(Debug, PartialEq, enum_utils::Display)]
+/// #[enumeration(rename_all = "lowercase")]
+/// enum Command {
+///     Stop,
+///     #[enumeration(format = "timeout({0}s)")]
+///     Timeout(u32),
+///     #[enumeration(format = "move {x:+},{y:+}")]
+///     Move { x: i32, y: i32, speed: u8 },
+/// }
+///
+/// assert_eq!(Command::Stop.to_string(), "stop");
+/// assert_eq!(Command::Timeout(30).to_string(), "timeout(30s)");
+/// assert_eq!(Command::Move { x: 1, y: -2, speed: 0 }.to_string(), "move +1,-2");
+/// ```
+///
+/// ## `#[enumeration(display = "...")]`
+///
+/// Writes a variant as the given string instead of its canonical name, without changing which
+/// strings are parsed. This is useful when `rename` is needed to parse a particular form, but the
+/// output should use another. The string is used exactly as given, so `prefix`, `suffix` and
+/// `rename_all` do not apply to it. It is also respected by `Serialize` and the other derives
+/// which write out the name of a variant.
+///
+/// ```
+/// #[derive(Debug, PartialEq, enum_utils::Display, enum_utils::FromStr)]
+/// enum Unit {
+///     #[enumeration(rename = "sec", alias = "s", display = "seconds")]
+///     Second,
+/// }
+///
+/// assert_eq!(Unit::Second.to_string(), "seconds");
+/// assert_eq!("sec".parse(), Ok(Unit::Second));
+/// assert_eq!("seconds".parse::<Unit>(), Err(()));
+/// ```
+///
+/// [`format!`]: https://doc.rust-lang.org/std/macro.format.html
+/// [`Display`]: https://doc.rust-lang.org/std/fmt/trait.Display.html
+/// [`FromStr`]: https://doc.rust-lang.org/std/str/trait.FromStr.html
+/// [derive-from-str]: derive.FromStr.html
+#[proc_macro_derive(Display, attributes(enumeration))]
+pub fn display_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    derive_scoped("Display", ast, name::derive_display).into()
+}
+
+/// Derives a `const` method, `name()`, which returns the name of a variant as a `&'static str`.
+///
+/// The returned name is the same one which is accepted by the [`FromStr`] derive and written by
+/// the [`Display`] derive, but does not require any formatting machinery or allocation.
+///
+/// # Examples
+///
+/// ```
+/// #[derive(Debug, PartialEq, enum_utils::AsStr)]
+/// #[enumeration(rename_all = "kebab-case")]
+/// enum Method {
+///     Get,
+///     Post,
+///     #[enumeration(rename = "M-SEARCH")]
+///     MSearch,
+/// }
+///
+/// assert_eq!(Method::Get.name(), "get");
+/// assert_eq!(Method::MSearch.name(), "M-SEARCH");
+///
+/// const POST: &str = Method::Post.name();
+/// assert_eq!(POST, "post");
+/// ```
+///
+/// # Attributes
+///
+/// `AsStr` supports the same attributes as [`Display`]. A skipped variant has no canonical name, so
+/// `name()` returns its identifier instead.
+///
+/// ```
+/// #[derive(Debug, PartialEq, enum_utils::AsStr)]
+/// #[enumeration(rename_all = "lowercase")]
+/// enum Skip {
+///     Alpha,
+///     #[enumeration(skip)]
+///     Other(u8),
+/// }
+///
+/// assert_eq!(Skip::Alpha.name(), "alpha");
+/// assert_eq!(Skip::Other(42).name(), "Other");
+/// ```
+///
+/// [`FromStr`]: derive.FromStr.html
+/// [`Display`]: derive.Display.html
+#[proc_macro_derive(AsStr, attributes(enumeration))]
+pub fn as_str_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    derive_scoped("AsStr", ast, name::derive_as_str).into()
+}
+
+/// Derives `const fn name_of(d: Repr) -> Option<&'static str>`, which returns the name of the
+/// variant whose discriminant is `d`.
+///
+/// This combines [`TryFromRepr`] with [`AsStr`], and is useful for displaying a raw discriminant,
+/// for example one received over the wire, in a log message. Like `TryFromRepr`, a primitive
+/// `#[repr(...)]` must be specified and `assert_discriminants` is supported. Like `AsStr`, the
+/// identifier of a skipped variant is returned in place of its name.
+///
+/// [`TryFromRepr`]: derive.TryFromRepr.html
+/// [`AsStr`]: derive.AsStr.html
+///
+/// # Examples
+///
+/// ```
+/// #[derive(enum_utils::NameOf)]
+/// #[enumeration(rename_all = "SCREAMING_SNAKE_CASE")]
+/// #[repr(u8)]
+/// enum Opcode {
+///     Continuation = 0x0,
+///     Text = 0x1,
+///     ConnectionClose = 0x8,
+/// }
+///
+/// assert_eq!(Opcode::name_of(0x8), Some("CONNECTION_CLOSE"));
+/// assert_eq!(Opcode::name_of(0x2), None);
+/// ```
+#[proc_macro_derive(NameOf, attributes(enumeration))]
+pub fn name_of_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    derive_scoped("NameOf", ast, name::derive_name_of).into()
+}
+
+/// Derives [`AsRef<str>`], [`Borrow<str>`] and `From<Enum> for &'static str`, which return the
+/// canonical name of each variant.
+///
+/// The names are the same as those returned by [`AsStr`], including the fallback to the
+/// identifier for skipped variants.
+///
+/// [`Borrow<str>`] requires that `Eq`, `Ord` and `Hash` behave identically for the enum and its
+/// name. This is not the case for the `derive`d implementations of those traits, so implement
+/// them by comparing or hashing `as_ref()` before using the enum as a key which is looked up by
+/// `&str`.
+///
+/// [`AsRef<str>`]: https://doc.rust-lang.org/std/convert/trait.AsRef.html
+/// [`Borrow<str>`]: https://doc.rust-lang.org/std/borrow/trait.Borrow.html
+/// [`AsStr`]: derive.AsStr.html
+///
+/// # Examples
+///
+/// ```
+/// #[derive(Clone, Copy, enum_utils::AsRefStr)]
+/// #[enumeration(rename_all = "lowercase")]
+/// enum Level {
+///     Debug,
+///     Info,
+/// }
+///
+/// fn shout<S: AsRef<str>>(s: S) -> String {
+///     s.as_ref().to_uppercase()
+/// }
+///
+/// assert_eq!(shout(Level::Info), "INFO");
+///
+/// let name: &'static str = Level::Debug.into();
+/// assert_eq!(name, "debug");
+/// ```
+#[proc_macro_derive(AsRefStr, attributes(enumeration))]
+pub fn as_ref_str_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    derive_scoped("AsRefStr", ast, name::derive_as_ref_str).into()
+}
+
+/// Derives `PartialEq<str>` and `PartialEq<&str>`, which compare each variant with its canonical
+/// name.
+///
+/// Comparisons do not allocate or parse the string. Aliases are not considered, since they are
+/// not the canonical name of a variant. Names are compared the same way [`FromStr`] matches them,
+/// so `case_insensitive` and `dash_underscore_insensitive` are respected.
+///
+/// # Examples
+///
+/// ```
+/// #[derive(enum_utils::PartialEqStr)]
+/// #[enumeration(rename_all = "lowercase")]
+/// enum Direction {
+///     North,
+///     South,
+/// }
+///
+/// assert!(Direction::North == "north");
+/// assert!(Direction::South != "North");
+/// assert!(Direction::South == *"south");
+/// ```
+///
+/// # Attributes
+///
+/// `PartialEqStr` supports the same attributes as [`FromStr`]. A skipped variant is never equal
+/// to any string, except for a catch-all `other` variant with a field, which is compared with
+/// that field.
+///
+/// ```
+/// #[derive(enum_utils::PartialEqStr)]
+/// #[enumeration(case_insensitive)]
+/// enum Shell {
+///     Bash,
+///     #[enumeration(other)]
+///     Other(String),
+/// }
+///
+/// assert!(Shell::Bash == "BASH");
+/// assert!(Shell::Other("fish".to_owned()) == "Fish");
+/// ```
+///
+/// [`FromStr`]: derive.FromStr.html
+#[proc_macro_derive(PartialEqStr, attributes(enumeration))]
+pub fn partial_eq_str_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    derive_scoped("PartialEqStr", ast, name::derive_partial_eq_str).into()
+}
+
+/// Derives `const fn id(&self) -> u32` and `const fn from_id(u32) -> Option<Self>`, which convert
+/// between a variant and a stable identifier derived from its name.
+///
+/// The ID of a variant is the 32-bit [FNV-1a] hash of its canonical name, so it does not change
+/// if variants are reordered or added. This makes it suitable for network protocols and save
+/// formats. Renaming a variant changes its ID unless `rename` is used to keep the old name.
+///
+/// If the names of two variants hash to the same ID, the derive fails to compile. Calling `id`
+/// on a skipped variant will panic.
+///
+/// [FNV-1a]: https://en.wikipedia.org/wiki/Fowler%E2%80%93Noll%E2%80%93Vo_hash_function
+///
+/// # Examples
+///
+/// ```
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, enum_utils::VariantId)]
+/// pub enum Message {
+///     Ping,
+///     Pong,
+/// }
+///
+/// assert_eq!(Message::Ping.id(), 0x7fb7_f0a9);
+/// assert_eq!(Message::from_id(Message::Pong.id()), Some(Message::Pong));
+/// assert_eq!(Message::from_id(0), None);
+/// ```
+///
+/// "costarring" and "liquid" have the same FNV-1a hash.
+///
+/// ```compile_fail
+/// #[derive(enum_utils::VariantId)]
+/// #[enumeration(rename_all = "lowercase")]
+/// pub enum Word {
+///     Costarring,
+///     Liquid,
+/// }
+/// ```
+#[proc_macro_derive(VariantId, attributes(enumeration))]
+pub fn variant_id_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    derive_scoped("VariantId", ast, id::derive).into()
+}
+
+/// Derives a declarative macro, `for_each_*!`, which invokes a callback macro once for each
+/// variant.
+///
+/// The name of the generated macro is `for_each_` followed by the name of the enum in
+/// `snake_case`. It takes the name of another macro, which is invoked as `callback!(Variant,
+/// "name");` for each variant that is not skipped, in the order they are declared. The name is
+/// the one accepted by the [`FromStr`] derive. The expansion is a sequence of statements, so it
+/// can be used wherever items or statements are allowed.
+///
+/// This is useful for generating tables, trait impls or tests which must cover every variant, since
+/// they will be updated along with the enum. Like any `macro_rules!` macro, the generated macro is
+/// only in scope after the enum in the same module.
+///
+/// [`FromStr`]: derive.FromStr.html
+///
+/// # Examples
+///
+/// ```
+/// #[derive(Debug, PartialEq, enum_utils::ForEachVariant)]
+/// #[enumeration(rename_all = "lowercase")]
+/// enum Direction {
+///     North,
+///     South,
+///     #[enumeration(skip)]
+///     Nowhere,
+/// }
+///
+/// macro_rules! check_name {
+///     ($v:ident, $name:literal) => {
+///         assert_eq!(format!("{:?}", Direction::$v).to_lowercase(), $name);
+///     };
+/// }
+///
+/// for_each_direction!(check_name);
+///
+/// let mut names = vec![];
+/// macro_rules! push_name {
+///     ($v:ident, $name:literal) => { names.push($name) };
+/// }
+///
+/// for_each_direction!(push_name);
+/// assert_eq!(names, ["north", "south"]);
+/// ```
+#[proc_macro_derive(ForEachVariant, attributes(enumeration))]
+pub fn for_each_variant_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    derive_scoped("ForEachVariant", ast, for_each::derive).into()
+}
+
+/// Derives an associated constant, `NAMES`, containing the name of each variant.
+///
+/// The names are those accepted by the [`FromStr`] derive, in the order the variants are
+/// declared. Skipped variants are omitted. Aliases are not included.
+///
+/// # Examples
+///
+/// ```
+/// #[derive(Debug, PartialEq, enum_utils::VariantNames)]
+/// #[enumeration(rename_all = "snake_case")]
+/// enum Level {
+///     Trace,
+///     Debug,
+///     #[enumeration(alias = "warn")]
+///     Warning,
+///     #[enumeration(skip)]
+///     Custom(u8),
+/// }
+///
+/// assert_eq!(Level::NAMES, &["trace", "debug", "warning"]);
+/// ```
+///
+/// With `#[enumeration(traits)]`, [`enum_utils_traits::VariantNames`] is implemented instead of
+/// generating an inherent constant.
+///
+/// [`enum_utils_traits::VariantNames`]: https://docs.rs/enum-utils-traits/0.1/enum_utils_traits/trait.VariantNames.html
+/// [`FromStr`]: derive.FromStr.html
+#[proc_macro_derive(VariantNames, attributes(enumeration))]
+pub fn variant_names_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    derive_scoped("VariantNames", ast, name::derive_variant_names).into()
+}
+
+/// Derives a `const fn message(&self) -> &'static str` which returns a human-readable
+/// description of each variant.
+///
+/// The message is taken from the first paragraph of the variant's doc comment. Lines in that
+/// paragraph are joined by a single space. If a variant has no doc comment, its canonical name
+/// (see [`FromStr`]) is used instead.
+///
+/// Unlike the other derives which deal with names, variants may have fields.
+///
+/// [`FromStr`]: derive.FromStr.html
+///
+/// # Examples
+///
+/// ```
+/// #[derive(Debug, enum_utils::EnumMessage)]
+/// enum ConfigError {
+///     /// The configuration file
+///     /// could not be found.
+///     ///
+///     /// This paragraph is not part of the message.
+///     NotFound,
+///
+///     /// The configuration file is not valid TOML.
+///     Parse { line: usize },
+///
+///     #[enumeration(message = "The configuration file is empty.")]
+///     Empty,
+///
+///     Unknown,
+/// }
+///
+/// assert_eq!(ConfigError::NotFound.message(), "The configuration file could not be found.");
+/// assert_eq!(ConfigError::Parse { line: 1 }.message(), "The configuration file is not valid TOML.");
+/// assert_eq!(ConfigError::Empty.message(), "The configuration file is empty.");
+/// assert_eq!(ConfigError::Unknown.message(), "Unknown");
+/// ```
+///
+/// # Attributes
+///
+/// ## `#[enumeration(message = "...")]`
+///
+/// Sets the message for a variant, taking priority over its doc comment.
+#[proc_macro_derive(EnumMessage, attributes(enumeration))]
+pub fn enum_message_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    derive_scoped("EnumMessage", ast, name::derive_message).into()
+}
+
+/// Derives [`Display`] and [`std::error::Error`] for an enum of error kinds.
+///
+/// Each variant is displayed using its message, as with [`EnumMessage`]: the `message` attribute,
+/// or the first paragraph of its doc comment, or its canonical name. A variant with a `format`
+/// template (see [`Display`](derive.Display.html)) uses that instead, so its fields can be part of
+/// the message. Variants may have fields.
+///
+/// [`Display`]: https://doc.rust-lang.org/std/fmt/trait.Display.html
+/// [`std::error::Error`]: https://doc.rust-lang.org/std/error/trait.Error.html
+/// [`EnumMessage`]: derive.EnumMessage.html
+///
+/// # Examples
+///
+/// ```
+/// use std::error::Error as _;
+///
+/// #[derive(Debug, enum_utils::Error)]
+/// enum LoadError {
+///     /// The file could not be read.
+///     Io(#[enumeration(source)] std::io::Error),
+///
+///     #[enumeration(format = "line {line}: unexpected {found:?}")]
+///     Syntax { line: usize, found: char },
+///
+///     #[enumeration(message = "The file is empty.")]
+///     Empty,
+/// }
+///
+/// let err = LoadError::Io(std::io::ErrorKind::NotFound.into());
+/// assert_eq!(err.to_string(), "The file could not be read.");
+/// assert!(err.source().is_some());
+///
+/// let err = LoadError::Syntax { line: 3, found: '}' };
+/// assert_eq!(err.to_string(), "line 3: unexpected '}'");
+/// assert!(err.source().is_none());
+///
+/// assert_eq!(LoadError::Empty.to_string(), "The file is empty.");
+/// ```
+///
+/// # Attributes
+///
+/// ## `#[enumeration(source)]`
+///
+/// Marks a field as the underlying cause of an error, which is returned by
+/// [`Error::source`](https://doc.rust-lang.org/std/error/trait.Error.html#method.source). It
+/// must implement `Error` and be `'static`. Each variant can have at most one `source` field.
+#[proc_macro_derive(Error, attributes(enumeration))]
+pub fn error_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    derive_scoped("Error", ast, error_impl::derive).into()
+}
+
+/// Derives a `fn get_property(&self, key: &str) -> Option<&'static str>` which looks up arbitrary
+/// string metadata attached to each variant.
+///
+/// Properties are declared on each variant with `#[enumeration(props(...))]`. Not every variant
+/// needs to declare every property. Variants may have fields.
+///
+/// # Examples
+///
+/// ```
+/// #[derive(enum_utils::EnumProperty)]
+/// enum Fruit {
+///     #[enumeration(props(color = "red", taste = "sweet"))]
+///     Apple,
+///     #[enumeration(props(color = "yellow", taste = "sour"))]
+///     Lemon,
+///     #[enumeration(props(color = "brown"))]
+///     Kiwi { ripe: bool },
+/// }
+///
+/// assert_eq!(Fruit::Apple.get_property("color"), Some("red"));
+/// assert_eq!(Fruit::Lemon.get_property("taste"), Some("sour"));
+/// assert_eq!(Fruit::Kiwi { ripe: true }.get_property("taste"), None);
+/// assert_eq!(Fruit::Apple.get_property("shape"), None);
+/// ```
+///
+/// # Attributes
+///
+/// ## `#[enumeration(props(key = "value", ...))]`
+///
+/// Attaches the given properties to a variant. This may be repeated, but each key may only be
+/// given once per variant.
+#[proc_macro_derive(EnumProperty, attributes(enumeration))]
+pub fn enum_property_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    derive_scoped("EnumProperty", ast, property::derive).into()
+}
+
+/// Derives [`Default`] for an enum, returning the variant marked with `#[enumeration(default)]`.
+///
+/// # Examples
+///
+/// ```
+/// #[derive(Debug, PartialEq, enum_utils::Default)]
+/// enum Level {
+///     Trace,
+///     #[enumeration(default)]
+///     Info,
+///     Error,
+/// }
+///
+/// assert_eq!(Level::default(), Level::Info);
+/// ```
+///
+/// Exactly one variant must be marked as the default, and it cannot have fields.
+///
+/// ```compile_fail
+/// #[derive(Debug, PartialEq, enum_utils::Default)]
+/// enum Level {
+///     #[enumeration(default)]
+///     Trace,
+///     #[enumeration(default)]
+///     Info,
+/// }
+/// ```
+///
+/// [`Default`]: https://doc.rust-lang.org/std/default/trait.Default.html
+#[proc_macro_derive(Default, attributes(enumeration))]
+pub fn default_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    derive_scoped("Default", ast, default::derive).into()
+}
+
+/// Derives a static method, `iter()`, which iterates over the variants of an enum.
+///
+/// # Examples
+///
+/// Variants are yielded in the order they are defined. If the discriminants of the enum form a
+/// single, increasing run and `#[repr(...)]` is specified as in the following example, a fast
+/// implementation of `iter` can be generated which does not require the enum to implement `Clone`.
+///
+/// ```
+/// /// The discriminants of this enum are `[1, 2, 3, 4]`.
+/// #[derive(Debug, PartialEq, Eq, enum_utils::IterVariants)]
+/// #[repr(u8)]
+/// pub enum Direction {
+///     North = 1,
+///     East,
+///     South,
+///     West,
+/// }
+///
+/// use Direction::*;
+/// assert_eq!(Direction::iter().collect::<Vec<_>>(), vec![North, East, South, West]);
+/// ```
+///
+/// If the preceding conditions are not met, `Clone` must be implemented to successfully derive
+/// `IterVariants`. `enum_utils` will create a `const` array containing each variant and iterate
+/// over that.
+///
+/// ```
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, enum_utils::IterVariants)]
+/// #[repr(u16)]
+/// pub enum Bitmask {
+///     Empty = 0x0000,
+///     Full = 0xffff,
+/// }
+///
+/// use Bitmask::*;
+/// assert_eq!(Bitmask::iter().collect::<Vec<_>>(), vec![Empty, Full]);
+/// ```
+///
+/// Named constants or complex expressions (beyond an integer literal) are not evaluated when used
+/// as a discriminant and will cause `IterVariants` to default to the `Clone`-based implementation.
+///
+/// ```compile_fail
+/// #[derive(Debug, PartialEq, Eq, enum_utils::IterVariants)] // Missing `Clone` impl
+/// #[repr(u8)]
+/// pub enum Bitmask {
+///     Bit1 = 1 << 0,
+///     Bit2 = 1 << 1,
+/// }
+/// ```
+///
+/// A second static method, `iter_adjacent()`, yields each pair of consecutive variants in the same
+/// order as `iter()`. This is useful for building transition tables over ordered enums.
+///
+/// ```
+/// #[derive(Debug, PartialEq, Eq, enum_utils::IterVariants)]
+/// #[repr(u8)]
+/// pub enum Phase {
+///     Idle,
+///     Running,
+///     Done,
+/// }
+///
+/// use Phase::*;
+/// assert_eq!(Phase::iter_adjacent().collect::<Vec<_>>(),
+///            vec![(Idle, Running), (Running, Done)]);
+/// ```
+///
+/// # Attributes
+///
+/// ## `#[enumeration(skip)]`
+///
+/// Use `#[enumeration(skip)]` to avoid iterating over a variant. This can be useful when an enum
+/// contains a "catch-all" variant.
+///
+/// ```
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, enum_utils::IterVariants)]
+/// pub enum Http2FrameType {
+///     Data,
+///     Headers,
+///
+///     /* ... */
+///
+///     Continuation,
+///
+///     #[enumeration(skip)]
+///     Unknown(u8),
+/// }
+///
+/// use Http2FrameType::*;
+/// assert_eq!(Http2FrameType::iter().collect::<Vec<_>>(),
+///            vec![Data, Headers, /* ... */ Continuation]);
+/// ```
+///
+/// ## `#[enumeration(debug_checks)]`
+///
+/// The fast implementation of `iter` described above uses `unsafe` code to convert each
+/// discriminant back into a variant. Applying `#[enumeration(debug_checks)]` to the enum adds a
+/// `debug_assert!` which verifies each discriminant against the list of variants before
+/// converting it. It also emits a `#[cfg(test)]` unit test comparing the fast iterator with that
+/// list, which can be run under [Miri] in CI.
+///
+/// ```
+/// #[derive(Debug, PartialEq, Eq, enum_utils::IterVariants)]
+/// #[enumeration(debug_checks)]
+/// #[repr(u8)]
+/// pub enum Direction {
+///     North = 1,
+///     East,
+///     South,
+///     West,
+/// }
+///
+/// assert_eq!(Direction::iter().count(), 4);
+/// ```
+///
+/// ## `#[enumeration(traits)]`
+///
+/// Implements [`enum_utils_traits::IntoEnumIterator`] instead of generating an inherent `iter`
+/// method, so the iterator can be used from generic code. This requires a dependency on the
+/// [`enum-utils-traits`] crate. `iter_adjacent` is not generated.
+///
+/// ```
+/// use enum_utils_traits::IntoEnumIterator;
+///
+/// fn first<E: IntoEnumIterator>() -> Option<E> {
+///     E::iter().next()
+/// }
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, enum_utils::IterVariants)]
+/// #[enumeration(traits)]
+/// pub enum Direction {
+///     North,
+///     South,
+/// }
+///
+/// assert_eq!(first::<Direction>(), Some(Direction::North));
+/// ```
+///
+/// ## `#[enumeration(inline = "...")]`
+///
+/// Places `#[inline(always)]` or `#[inline(never)]` on the generated `iter` and `iter_adjacent`
+/// functions. See [`FromStr`] for details.
+///
+/// [`FromStr`]: derive.FromStr.html
+/// [`enum_utils_traits::IntoEnumIterator`]: https://docs.rs/enum-utils-traits/0.1/enum_utils_traits/trait.IntoEnumIterator.html
+/// [`enum-utils-traits`]: https://docs.rs/enum-utils-traits/0.1
+/// [Miri]: https://github.com/rust-lang/miri
+#[proc_macro_derive(IterVariants, attributes(enumeration))]
+pub fn iter_variants_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    derive_scoped("IterVariants", ast, iter::derive).into()
+}
+
+/// Derives an associated constant, `COUNT`, containing the number of variants in an enum.
+///
+/// Variants marked with `#[enumeration(skip)]` are not counted, so `COUNT` is always equal to the
+/// number of items yielded by the iterator derived by [`IterVariants`].
+///
+/// # Examples
+///
+/// ```
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, enum_utils::EnumCount)]
+/// pub enum Direction {
+///     North,
+///     East,
+///     South,
+///     West,
+///     #[enumeration(skip)]
+///     Unknown(u8),
+/// }
+///
+/// let visits = [0usize; Direction::COUNT];
+/// assert_eq!(visits.len(), 4);
+/// ```
+///
+/// With `#[enumeration(traits)]`, [`enum_utils_traits::EnumCount`] is implemented instead of
+/// generating an inherent constant.
+///
+/// [`enum_utils_traits::EnumCount`]: https://docs.rs/enum-utils-traits/0.1/enum_utils_traits/trait.EnumCount.html
+/// [`IterVariants`]: derive.IterVariants.html
+#[proc_macro_derive(EnumCount, attributes(enumeration))]
+pub fn enum_count_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    derive_scoped("EnumCount", ast, iter::derive_count).into()
+}
+
+/// Derives `const fn ordinal(&self) -> usize` and `const fn from_ordinal(usize) -> Option<Self>`,
+/// which convert between a variant and its position in the enum.
+///
+/// Positions are assigned in declaration order, starting from zero, and ignore the value of any
+/// discriminants. Variants marked with `#[enumeration(skip)]` are not assigned a position, so the
+/// ordinals of the remaining variants are always in `0..COUNT` (see [`EnumCount`]). This makes
+/// them suitable for indexing into a dense table. Calling `ordinal` on a skipped variant will
+/// panic.
+///
+/// [`EnumCount`]: derive.EnumCount.html
+///
+/// # Examples
+///
+/// ```
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, enum_utils::Ordinal)]
+/// #[repr(u8)]
+/// pub enum Priority {
+///     Low = 10,
+///     #[enumeration(skip)]
+///     Unset = 0,
+///     High = 20,
+/// }
+///
+/// assert_eq!(Priority::Low.ordinal(), 0);
+/// assert_eq!(Priority::High.ordinal(), 1);
+/// assert_eq!(Priority::from_ordinal(1), Some(Priority::High));
+/// assert_eq!(Priority::from_ordinal(2), None);
+///
+/// const HIGH: Option<Priority> = Priority::from_ordinal(1);
+/// assert_eq!(HIGH, Some(Priority::High));
+/// ```
+#[proc_macro_derive(Ordinal, attributes(enumeration))]
+pub fn ordinal_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    derive_scoped("Ordinal", ast, iter::derive_ordinal).into()
+}
+
+/// Derives `fn next(self) -> Option<Self>` and `fn prev(self) -> Option<Self>`, which step
+/// forwards and backwards through the variants of an enum in declaration order.
+///
+/// Variants marked with `#[enumeration(skip)]` are stepped over, and calling `next` or `prev` on
+/// them returns `None`.
+///
+/// # Examples
+///
+/// ```
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, enum_utils::Successor)]
+/// pub enum Stage {
+///     Parse,
+///     Check,
+///     Emit,
+/// }
+///
+/// assert_eq!(Stage::Parse.next(), Some(Stage::Check));
+/// assert_eq!(Stage::Emit.next(), None);
+/// assert_eq!(Stage::Check.prev(), Some(Stage::Parse));
+/// assert_eq!(Stage::Parse.prev(), None);
+/// ```
+///
+/// # Attributes
+///
+/// ## `#[enumeration(wrapping)]`
+///
+/// Calling `next` on the last variant returns the first one, and calling `prev` on the first
+/// variant returns the last one.
+///
+/// ```
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, enum_utils::Successor)]
+/// #[enumeration(wrapping)]
+/// pub enum Focus {
+///     Editor,
+///     Sidebar,
+///     Terminal,
+/// }
+///
+/// assert_eq!(Focus::Terminal.next(), Some(Focus::Editor));
+/// assert_eq!(Focus::Editor.prev(), Some(Focus::Terminal));
+/// ```
+#[proc_macro_derive(Successor, attributes(enumeration))]
+pub fn successor_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    derive_scoped("Successor", ast, iter::derive_successor).into()
+}
+
+/// Derives associated constants, `MIN` and `MAX`, which are the variants with the smallest and
+/// largest discriminant.
+///
+/// Every discriminant must be an integer literal (or be omitted), and the enum must have at least
+/// one variant. Skipped variants are still considered, since the constants describe the range of
+/// discriminants and not the set of names. This is useful for range checks when decoding a binary
+/// format.
+///
+/// # Examples
+///
+/// ```
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, enum_utils::EnumBounds)]
+/// #[repr(i8)]
+/// pub enum Priority {
+///     Normal = 0,
+///     Low = -4,
+///     High = 4,
+///     Urgent,
+/// }
+///
+/// assert_eq!(Priority::MIN, Priority::Low);
+/// assert_eq!(Priority::MAX, Priority::Urgent);
+///
+/// let d: i8 = 3;
+/// assert!((Priority::MIN as i8..=Priority::MAX as i8).contains(&d));
+/// ```
+#[proc_macro_derive(EnumBounds, attributes(enumeration))]
+pub fn enum_bounds_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    derive_scoped("EnumBounds", ast, iter::derive_bounds).into()
+}
+
+/// Derives `PartialOrd` and `Ord`, ordering variants by an explicit `#[enumeration(order = N)]`
+/// instead of by their declaration order.
+///
+/// This is useful when the discriminants are fixed by a wire format, but a different ordering is
+/// more meaningful. Every variant must have a distinct `order`, and may not have fields. The enum
+/// must also implement `Eq`.
+///
+/// # Examples
+///
+/// ```
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, enum_utils::EnumOrd)]
+/// #[repr(u8)]
+/// enum Severity {
+///     #[enumeration(order = 2)]
+///     Error = 0,
+///     #[enumeration(order = 0)]
+///     Info = 1,
+///     #[enumeration(order = 1)]
+///     Warning = 2,
+/// }
+///
+/// assert!(Severity::Info < Severity::Warning);
+/// assert_eq!([Severity::Error, Severity::Info].iter().max(), Some(&Severity::Error));
+/// ```
+///
+/// Two variants with the same order are an error.
+///
+/// ```compile_fail
+/// #[derive(PartialEq, Eq, enum_utils::EnumOrd)]
+/// enum Tie {
+///     #[enumeration(order = 1)]
+///     A,
+///     #[enumeration(order = 1)]
+///     B,
+/// }
+/// ```
+#[proc_macro_derive(EnumOrd, attributes(enumeration))]
+pub fn enum_ord_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    derive_scoped("EnumOrd", ast, ord::derive).into()
+}
+
+/// Derives [`std::iter::Step`], so that ranges of variants such as `North..=West` can be
+/// iterated over.
+///
+/// `Step` is unstable, so this requires a nightly compiler and `#![feature(step_trait)]` in the
+/// crate which uses the derive. A primitive `#[repr(...)]` must be specified, and the
+/// discriminants must be integer literals which increase by one with each variant. This ensures
+/// that stepping agrees with a derived `PartialOrd`, which must also be implemented. Skipped
+/// variants are included. As for [`IterVariants`], `#[enumeration(debug_checks)]` verifies each
+/// discriminant in debug builds before converting it back into a variant.
+///
+/// [`std::iter::Step`]: https://doc.rust-lang.org/nightly/std/iter/trait.Step.html
+/// [`IterVariants`]: derive.IterVariants.html
+///
+/// # Examples
+///
+/// ```ignore
+/// #![feature(step_trait)]
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, enum_utils::Step)]
+/// #[repr(u8)]
+/// enum Direction {
+///     North,
+///     East,
+///     South,
+///     West,
+/// }
+///
+/// let dirs: Vec<_> = (Direction::East..=Direction::West).collect();
+/// assert_eq!(dirs, [Direction::East, Direction::South, Direction::West]);
+/// ```
+#[proc_macro_derive(Step, attributes(enumeration))]
+pub fn step_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    derive_scoped("Step", ast, iter::derive_step).into()
+}
+
+/// Derives a companion type, `<Enum>Map<V>`, which maps each variant of an enum to a value of
+/// type `V`.
+///
+/// The map is backed by an array with one element per variant, so lookups are a single
+/// `match` followed by an array access. It has the same visibility as the enum, and implements
+/// [`Index`] and [`IndexMut`] with the enum as the key. `Debug`, `Clone`, `Copy`, `PartialEq`,
+/// `Eq`, `Hash` and `Default` are implemented whenever `V` implements them.
+///
+/// Variants marked with `#[enumeration(skip)]` are not keys in the map, and indexing with one
+/// will panic. All other variants must not have fields.
+///
+/// [`Index`]: https://doc.rust-lang.org/std/ops/trait.Index.html
+/// [`IndexMut`]: https://doc.rust-lang.org/std/ops/trait.IndexMut.html
+///
+/// # Examples
+///
+/// ```
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, enum_utils::EnumMap)]
+/// pub enum Direction {
+///     North,
+///     East,
+///     South,
+///     West,
+/// }
+///
+/// let mut visits = DirectionMap::<u32>::default();
+/// visits[Direction::North] += 2;
+/// visits[Direction::West] += 1;
+///
+/// assert_eq!(visits[Direction::North], 2);
+/// assert_eq!(visits.into_array(), [2, 0, 0, 1]);
+///
+/// let opposite = DirectionMap::from_fn(|d| match d {
+///     Direction::North => Direction::South,
+///     Direction::East => Direction::West,
+///     Direction::South => Direction::North,
+///     Direction::West => Direction::East,
+/// });
+///
+/// for (d, &o) in opposite.iter() {
+///     assert_eq!(opposite[o], d);
+/// }
+/// ```
+#[proc_macro_derive(EnumMap, attributes(enumeration))]
+pub fn enum_map_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    derive_scoped("EnumMap", ast, map::derive).into()
+}
+
+/// Derives [`Index<Enum>`] and [`IndexMut<Enum>`] for arrays with one element per variant.
+///
+/// This allows a plain array to be used as a lookup table keyed by the enum. Elements are
+/// assigned to variants in declaration order. If a separate map type is preferred, see
+/// [`EnumMap`].
+///
+/// Variants marked with `#[enumeration(skip)]` do not have an element, and indexing with one will
+/// panic. All other variants must not have fields.
+///
+/// [`Index<Enum>`]: https://doc.rust-lang.org/std/ops/trait.Index.html
+/// [`IndexMut<Enum>`]: https://doc.rust-lang.org/std/ops/trait.IndexMut.html
+/// [`EnumMap`]: derive.EnumMap.html
+///
+/// # Examples
+///
+/// ```
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, enum_utils::EnumIndex)]
+/// pub enum Channel {
+///     Red,
+///     Green,
+///     Blue,
+/// }
+///
+/// let mut pixel = [0u8, 128, 255];
+/// assert_eq!(pixel[Channel::Green], 128);
+///
+/// pixel[Channel::Red] = 64;
+/// assert_eq!(pixel, [64, 128, 255]);
+/// ```
+#[proc_macro_derive(EnumIndex, attributes(enumeration))]
+pub fn enum_index_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    derive_scoped("EnumIndex", ast, map::derive_index).into()
+}
+
+/// Derives a companion type, `<Enum>Set`, which stores a set of variants as a bitset.
+///
+/// The set is backed by the smallest unsigned integer with at least one bit per variant, so an
+/// enum may have at most 128 variants. It has the same visibility as the enum and supports the
+/// usual set operations, both as methods and as the operators `|`, `&`, `^`, `-` and `!`.
+/// Iteration yields variants in declaration order.
+///
+/// Variants marked with `#[enumeration(skip)]` cannot be members of the set, and inserting one
+/// will panic. All other variants must not have fields.
+///
+/// # Examples
+///
+/// ```
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, enum_utils::EnumSet)]
+/// pub enum Permission {
+///     Read,
+///     Write,
+///     Execute,
+/// }
+///
+/// let mut perms = PermissionSet::empty();
+/// assert!(perms.insert(Permission::Read));
+/// assert!(!perms.insert(Permission::Read));
+/// assert!(perms.contains(Permission::Read));
+///
+/// let rw: PermissionSet = [Permission::Read, Permission::Write].iter().cloned().collect();
+/// assert_eq!(rw.len(), 2);
+/// assert_eq!(!rw, PermissionSet::from(Permission::Execute));
+/// assert_eq!(rw | perms, rw);
+/// assert_eq!(rw.iter().collect::<Vec<_>>(), vec![Permission::Read, Permission::Write]);
+/// assert_eq!(rw.bits(), 0b011);
+/// ```
+#[proc_macro_derive(EnumSet, attributes(enumeration))]
+pub fn enum_set_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    derive_scoped("EnumSet", ast, set::derive).into()
+}
+
+/// Derives [`Distribution<Enum>`] for [`Standard`], which selects a variant uniformly at random.
+///
+/// This requires the `rand` feature, and a dependency on version 0.8 of the [`rand`] crate.
+///
+/// Variants marked with `#[enumeration(skip)]` are never selected. All other variants must not
+/// have fields. Like [`IterVariants`], a variant is selected by generating a discriminant directly
+/// if the discriminants of the enum form a contiguous run, and `#[enumeration(debug_checks)]`
+/// verifies each generated discriminant in debug builds.
+///
+/// [`Distribution<Enum>`]: https://docs.rs/rand/0.8/rand/distributions/trait.Distribution.html
+/// [`Standard`]: https://docs.rs/rand/0.8/rand/distributions/struct.Standard.html
+/// [`rand`]: https://docs.rs/rand/0.8
+/// [`IterVariants`]: derive.IterVariants.html
+///
+/// # Examples
+///
+/// ```
+/// use rand::Rng;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, enum_utils::Random)]
+/// pub enum Coin {
+///     Heads,
+///     Tails,
+/// }
+///
+/// let flip: Coin = rand::thread_rng().gen();
+/// assert!(flip == Coin::Heads || flip == Coin::Tails);
+/// ```
+///
+/// At least one variant must not be skipped, so that there is something to select.
+///
+/// ```compile_fail
+/// #[derive(enum_utils::Random)]
+/// pub enum Nothing {
+///     #[enumeration(skip)]
+///     A,
+/// }
+/// ```
+///
+/// # Attributes
+///
+/// ## `#[enumeration(weight = N)]`
+///
+/// If any variant has an explicit weight, an additional method, `fn sample_weighted<R: Rng +
+/// ?Sized>(rng: &mut R) -> Self`, is generated. It selects each variant with a probability
+/// proportional to its weight. Variants without an explicit weight have a weight of one, and a
+/// variant with a weight of zero is never selected. The [`Distribution`] implementation is not
+/// affected.
+///
+/// [`Distribution`]: https://docs.rs/rand/0.8/rand/distributions/trait.Distribution.html
+///
+/// ```
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, enum_utils::Random)]
+/// pub enum Loot {
+///     #[enumeration(weight = 90)]
+///     Common,
+///     #[enumeration(weight = 10)]
+///     Rare,
+///     #[enumeration(weight = 0)]
+///     Unobtainable,
+/// }
+///
+/// let mut rng = rand::thread_rng();
+/// assert_ne!(Loot::sample_weighted(&mut rng), Loot::Unobtainable);
+/// ```
+///
+/// The sum of all weights must be non-zero and fit in a `u64`.
+///
+/// ```compile_fail
+/// #[derive(enum_utils::Random)]
+/// pub enum Never {
+///     #[enumeration(weight = 0)]
+///     A,
+/// }
+/// ```
+#[cfg(feature = "rand")]
+#[proc_macro_derive(Random, attributes(enumeration))]
+pub fn random_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    derive_scoped("Random", ast, random::derive).into()
+}
+
+/// Derives [`proptest::arbitrary::Arbitrary`], whose strategy selects one of the variants of an
+/// enum.
+///
+/// This requires the `proptest` feature, and a dependency on version 1 of the [`proptest`] crate.
+///
+/// The strategy is a [`prop_oneof!`] over every variant not marked with `#[enumeration(skip)]`.
+/// The fields of a variant are generated with [`any`], so their types must implement `Arbitrary`
+/// as well. Like any proptest strategy, the enum must implement `Clone` and `Debug`.
+///
+/// [`proptest::arbitrary::Arbitrary`]: https://docs.rs/proptest/1/proptest/arbitrary/trait.Arbitrary.html
+/// [`proptest`]: https://docs.rs/proptest/1
+/// [`prop_oneof!`]: https://docs.rs/proptest/1/proptest/macro.prop_oneof.html
+/// [`any`]: https://docs.rs/proptest/1/proptest/arbitrary/fn.any.html
+///
+/// # Examples
+///
+/// ```
+/// use proptest::prelude::*;
+///
+/// #[derive(Debug, Clone, PartialEq, enum_utils::ProptestArbitrary)]
+/// pub enum Shape {
+///     Point,
+///     Circle(u8),
+///     Rect { w: u8, h: u8 },
+///     #[enumeration(skip)]
+///     Invalid,
+/// }
+///
+/// proptest! {
+///     fn never_invalid(shape in any::<Shape>()) {
+///         prop_assert_ne!(shape, Shape::Invalid);
+///     }
+/// }
+///
+/// never_invalid();
+/// ```
+#[cfg(feature = "proptest")]
+#[proc_macro_derive(ProptestArbitrary, attributes(enumeration))]
+pub fn proptest_arbitrary_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    derive_scoped("ProptestArbitrary", ast, arbitrary::derive_proptest).into()
+}
+
+/// Derives [`arbitrary::Arbitrary`], which maps unstructured fuzzer input onto a variant.
+///
+/// This requires the `arbitrary` feature, and a dependency on version 1 of the [`arbitrary`]
+/// crate.
+///
+/// Variants marked with `#[enumeration(skip)]` are never constructed. The fields of other
+/// variants are constructed with their own `Arbitrary` implementations. If no unskipped variant
+/// has fields, and their discriminants form a contiguous run, the discriminant is read directly
+/// from the input like in [`IterVariants`]. As there, `#[enumeration(debug_checks)]` verifies it
+/// in debug builds.
+///
+/// [`arbitrary::Arbitrary`]: https://docs.rs/arbitrary/1/arbitrary/trait.Arbitrary.html
+/// [`arbitrary`]: https://docs.rs/arbitrary/1
+/// [`IterVariants`]: derive.IterVariants.html
+///
+/// # Examples
+///
+/// ```
+/// use arbitrary::{Arbitrary, Unstructured};
+///
+/// #[derive(Debug, PartialEq, enum_utils::Arbitrary)]
+/// pub enum Op {
+///     Push(u8),
+///     Pop,
+///     #[enumeration(skip)]
+///     Halt,
+/// }
+///
+/// let mut u = Unstructured::new(&[0, 42, 1]);
+/// assert_eq!(Op::arbitrary(&mut u), Ok(Op::Push(42)));
+/// assert_eq!(Op::arbitrary(&mut u), Ok(Op::Pop));
+/// ```
+#[cfg(feature = "arbitrary")]
+#[proc_macro_derive(Arbitrary, attributes(enumeration))]
+pub fn arbitrary_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    derive_scoped("Arbitrary", ast, arbitrary::derive_arbitrary).into()
+}
+
+/// Derives [`quickcheck::Arbitrary`], which selects a variant uniformly at random.
+///
+/// This requires the `quickcheck` feature, and a dependency on version 1 of the [`quickcheck`]
+/// crate.
+///
+/// Variants marked with `#[enumeration(skip)]` are never selected. The fields of other variants
+/// are generated with their own `Arbitrary` implementations. Values shrink toward the first
+/// declared variant: `shrink` yields each unskipped, fieldless variant declared before `self`,
+/// starting with the first. The fields of a variant are not shrunk.
+///
+/// [`quickcheck::Arbitrary`]: https://docs.rs/quickcheck/1/quickcheck/trait.Arbitrary.html
+/// [`quickcheck`]: https://docs.rs/quickcheck/1
+///
+/// # Examples
+///
+/// ```
+/// use quickcheck::Arbitrary;
+///
+/// #[derive(Debug, Clone, PartialEq, enum_utils::QuickcheckArbitrary)]
+/// pub enum Level {
+///     Low,
+///     Medium,
+///     High,
+///     #[enumeration(skip)]
+///     Broken,
+/// }
+///
+/// let mut g = quickcheck::Gen::new(10);
+/// assert_ne!(Level::arbitrary(&mut g), Level::Broken);
+///
+/// let shrunk: Vec<_> = Level::High.shrink().collect();
+/// assert_eq!(shrunk, vec![Level::Low, Level::Medium]);
+/// ```
+#[cfg(feature = "quickcheck")]
+#[proc_macro_derive(QuickcheckArbitrary, attributes(enumeration))]
+pub fn quickcheck_arbitrary_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    derive_scoped("QuickcheckArbitrary", ast, arbitrary::derive_quickcheck).into()
+}
+
+/// Derives a predicate method, `is_*()`, for each variant of an enum.
+///
+/// The name of each method is the name of the variant converted to `snake_case`. Variants may
+/// have fields, and `#[enumeration(...)]` attributes such as `skip` and `rename` have no effect.
+///
+/// # Examples
+///
+/// ```
+/// #[derive(Debug, enum_utils::EnumIs)]
+/// pub enum Message {
+///     Ping,
+///     TextFrame(String),
+///     Close { code: u16 },
+/// }
+///
+/// assert!(Message::Ping.is_ping());
+/// assert!(Message::TextFrame("hi".to_owned()).is_text_frame());
+/// assert!(!Message::Close { code: 1000 }.is_ping());
+///
+/// const IS_CLOSE: bool = Message::Close { code: 1000 }.is_close();
+/// assert!(IS_CLOSE);
+/// ```
+#[proc_macro_derive(EnumIs, attributes(enumeration))]
+pub fn enum_is_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    derive_scoped("EnumIs", ast, accessor::derive_is).into()
+}
+
+/// Derives accessors, `try_as_*()`, `try_as_*_mut()` and `try_into_*()`, for each variant of an
+/// enum which has fields.
+///
+/// As with [`EnumIs`], the name of each method is the name of the variant converted to
+/// `snake_case`. `try_as_*` and `try_as_*_mut` return a reference to the field of the variant, or
+/// `None` if `self` is a different variant. `try_into_*` returns the field by value, or gives back
+/// `self` as an error. If a variant has more than one field, they are returned as a tuple in
+/// declaration order. Unit variants have no accessors.
+///
+/// [`EnumIs`]: derive.EnumIs.html
+///
+/// # Examples
+///
+/// ```
+/// #[derive(Debug, PartialEq, enum_utils::EnumTryAs)]
+/// pub enum Message {
+///     Ping,
+///     TextFrame(String),
+///     Close { code: u16, reason: String },
+/// }
+///
+/// let mut msg = Message::TextFrame("hi".to_owned());
+/// assert_eq!(msg.try_as_text_frame().map(String::as_str), Some("hi"));
+/// assert_eq!(msg.try_as_close(), None);
+///
+/// msg.try_as_text_frame_mut().unwrap().push('!');
+/// assert_eq!(msg.try_into_text_frame(), Ok("hi!".to_owned()));
+///
+/// let close = Message::Close { code: 1000, reason: String::new() };
+/// assert_eq!(close.try_as_close(), Some((&1000, &String::new())));
+/// assert_eq!(Message::Ping.try_into_close(), Err(Message::Ping));
+/// ```
+#[proc_macro_derive(EnumTryAs, attributes(enumeration))]
+pub fn enum_try_as_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    derive_scoped("EnumTryAs", ast, accessor::derive_try_as).into()
+}
+
+/// Derives a fieldless "mirror" of an enum, along with a `kind` method which returns the
+/// discriminant of a value.
+///
+/// The mirror is named `<Enum>Kind` and has the same visibility as the original enum. It has a
+/// unit variant for every variant of the original, including skipped ones.
+///
+/// # Examples
+///
+/// ```
+/// #[derive(Debug, enum_utils::EnumDiscriminants)]
+/// #[enumeration(kind_derive(Debug, Clone, Copy, PartialEq, Eq))]
+/// pub enum Message {
+///     Ping,
+///     Text(String),
+///     Close { code: u16 },
+/// }
+///
+/// assert_eq!(Message::Text("hi".to_owned()).kind(), MessageKind::Text);
+/// assert_eq!(Message::Close { code: 1000 }.kind(), MessageKind::Close);
+/// ```
+///
+/// # Attributes
+///
+/// ## `#[enumeration(kind_name = "...")]`
+///
+/// Changes the name of the mirror enum.
+///
+/// ## `#[enumeration(kind_derive(...))]`
+///
+/// A list of derives to apply to the mirror. If this includes one of the derives in this crate,
+/// named by a path beginning with `enum_utils::`, any `#[enumeration(...)]` attributes on the
+/// original enum and its variants are copied to the mirror as well. This allows the data-carrying enum to share a single set of names with its
+/// fieldless counterpart.
+///
+/// ```
+/// #[derive(enum_utils::EnumDiscriminants)]
+/// #[enumeration(
+///     rename_all = "snake_case",
+///     kind_name = "Command",
+///     kind_derive(Debug, Clone, Copy, PartialEq, enum_utils::FromStr, enum_utils::IterVariants),
+/// )]
+/// pub enum Request {
+///     GetValue(String),
+///     #[enumeration(rename = "set")]
+///     SetValue(String, u32),
+/// }
+///
+/// assert_eq!("get_value".parse(), Ok(Command::GetValue));
+/// assert_eq!("set".parse(), Ok(Command::SetValue));
+/// assert_eq!(Command::iter().count(), 2);
+/// ```
+#[proc_macro_derive(EnumDiscriminants, attributes(enumeration))]
+pub fn enum_discriminants_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    derive_scoped("EnumDiscriminants", ast, discriminants::derive).into()
+}
+
+/// Derives a visitor trait, `*Visitor`, with one method for each variant, and a method,
+/// `accept()`, which dispatches to the method for the current variant.
+///
+/// The name of the trait is the name of the enum followed by `Visitor`, and it has the same
+/// visibility as the enum. Its methods are named `visit_*`, followed by the name of the variant
+/// in `snake_case`, and receive a reference to each field. Every method returns the associated
+/// type `Output`. Since none of the methods have a default implementation, adding a variant is a
+/// compile error for each visitor until it is handled.
+///
+/// # Examples
+///
+/// ```
+/// #[derive(enum_utils::Visitor)]
+/// pub enum Expr {
+///     Num(i64),
+///     Add(Box<Expr>, Box<Expr>),
+///     Neg { inner: Box<Expr> },
+/// }
+///
+/// struct Eval;
+///
+/// impl ExprVisitor for Eval {
+///     type Output = i64;
+///
+///     fn visit_num(&mut self, n: &i64) -> i64 {
+///         *n
+///     }
+///
+///     fn visit_add(&mut self, a: &Box<Expr>, b: &Box<Expr>) -> i64 {
+///         a.accept(self) + b.accept(self)
+///     }
+///
+///     fn visit_neg(&mut self, inner: &Box<Expr>) -> i64 {
+///         -inner.accept(self)
+///     }
+/// }
+///
+/// let expr = Expr::Add(Box::new(Expr::Num(2)), Box::new(Expr::Neg { inner: Box::new(Expr::Num(5)) }));
+/// assert_eq!(expr.accept(&mut Eval), -3);
+/// ```
+#[proc_macro_derive(Visitor, attributes(enumeration))]
+pub fn visitor_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    derive_scoped("Visitor", ast, visitor::derive).into()
+}
+
+/// Derives [`TryFrom<Repr>`] for a C-like enum, where `Repr` is a [primitive representation]
+/// specified in `#[repr(...)]`.
+///
+/// [`TryFrom<Repr>`]: https://doc.rust-lang.org/std/convert/trait.TryFrom.html
+/// [primitive representation]: https://doc.rust-lang.org/reference/type-layout.html#primitive-representations
+///
+/// # Examples
+///
+/// ```
+/// use std::convert::{TryFrom, TryInto};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, enum_utils::TryFromRepr)]
+/// #[repr(u8)]
+/// pub enum Direction {
+///     North = 1,
+///     East,
+///     South,
+///     West
+/// }
+///
+/// use Direction::*;
+/// assert_eq!(North, 1u8.try_into().unwrap());
+/// assert_eq!(West,  4u8.try_into().unwrap());
+/// assert_eq!(Err(()), Direction::try_from(0u8));
+/// assert_eq!(Err(()), Direction::try_from(5u8));
+/// ```
+///
+/// This macro only works on C-like enums.
+///
+/// ```compile_fail
+/// #[derive(Debug, Clone,  enum_utils::TryFromRepr)]
+/// #[repr(u8)]
+/// pub enum Letter {
+///     A,
+///     B,
+///     Other(u8),
+/// }
+/// ```
+///
+/// # Attributes
+///
+/// ## `#[enumeration(assert_discriminants(...))]`
+///
+/// When discriminants are part of a wire format, accidentally renumbering the variants of an enum
+/// is a breaking change. This attribute records the expected discriminant of every variant, and
+/// causes a compile-time error if any of them change. It is also supported by [`ReprFrom`].
+///
+/// ```
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, enum_utils::TryFromRepr)]
+/// #[enumeration(assert_discriminants(North = 1, East = 2, South = 3, West = 4))]
+/// #[repr(u8)]
+/// pub enum Direction {
+///     North = 1,
+///     East,
+///     South,
+///     West
+/// }
+/// ```
+///
+/// Adding a variant in the middle of the enum shifts the discriminants of the variants after it.
+///
+/// ```compile_fail
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, enum_utils::TryFromRepr)]
+/// #[enumeration(assert_discriminants(North = 1, East = 2, South = 3, West = 4))]
+/// #[repr(u8)]
+/// pub enum Direction {
+///     North = 1,
+///     NorthEast,
+///     East,
+///     South,
+///     West
+/// }
+/// ```
+///
+/// ## `#[enumeration(traits)]`
+///
+/// Also implements [`enum_utils_traits::FromRepr`], which returns an `Option` instead of a
+/// `Result`.
+///
+/// ```
+/// use enum_utils_traits::FromRepr;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, enum_utils::TryFromRepr)]
+/// #[enumeration(traits)]
+/// #[repr(u8)]
+/// pub enum Direction {
+///     North = 1,
+///     East,
+/// }
+///
+/// assert_eq!(Direction::from_repr(2), Some(Direction::East));
+/// ```
+///
+/// ## `#[enumeration(inline = "...")]`
+///
+/// Places `#[inline(always)]` or `#[inline(never)]` on the generated `try_from` (and
+/// `from_repr`) functions. It is also supported by [`ReprFrom`].
+///
+/// [`enum_utils_traits::FromRepr`]: https://docs.rs/enum-utils-traits/0.1/enum_utils_traits/trait.FromRepr.html
+/// [`ReprFrom`]: derive.ReprFrom.html
+#[proc_macro_derive(TryFromRepr, attributes(enumeration))]
+pub fn try_from_repr_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    derive_scoped("TryFromRepr", ast, conv::derive_try_from_repr).into()
+}
+
+/// Derives [`From<CLikeEnum>`] for the [primitive representation] specified in `#[repr(...)]`.
+///
+/// [`From<Enum>`]: https://doc.rust-lang.org/std/convert/trait.From.html
+/// [primitive representation]: https://doc.rust-lang.org/reference/type-layout.html#primitive-representations
+///
+/// # Examples
+///
+/// ```
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, enum_utils::ReprFrom)]
 /// #[repr(u8)]
 /// pub enum Direction {
 ///     North = 1,
@@ -319,5 +3345,361 @@ pub fn try_from_repr_derive(input: TokenStream) -> TokenStream {
 #[proc_macro_derive(ReprFrom, attributes(enumeration))]
 pub fn repr_from_derive(input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as DeriveInput);
-    unwrap_errors(conv::derive_repr_from(&ast)).into()
+    derive_scoped("ReprFrom", ast, conv::derive_repr_from).into()
+}
+
+/// Derives [`TryFrom<f64>`] and [`TryFrom<f32>`] for a C-like enum.
+///
+/// A float is converted only if it is exactly equal to the discriminant of a variant. Values with
+/// a fractional part, `NaN` and infinities are rejected. This is useful at boundaries where
+/// numeric tags arrive as floats, such as JSON or scripting languages.
+///
+/// [`TryFrom<f64>`]: https://doc.rust-lang.org/std/convert/trait.TryFrom.html
+/// [`TryFrom<f32>`]: https://doc.rust-lang.org/std/convert/trait.TryFrom.html
+///
+/// # Examples
+///
+/// ```
+/// use std::convert::TryFrom;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, enum_utils::TryFromFloat)]
+/// pub enum Direction {
+///     North = 1,
+///     East,
+///     South,
+///     West
+/// }
+///
+/// use Direction::*;
+/// assert_eq!(Ok(North), Direction::try_from(1.0f64));
+/// assert_eq!(Ok(West), Direction::try_from(4.0f32));
+/// assert_eq!(Err(()), Direction::try_from(1.5f64));
+/// assert_eq!(Err(()), Direction::try_from(f64::NAN));
+/// ```
+///
+/// Every discriminant must be an integer literal (or omitted).
+///
+/// ```compile_fail
+/// const BASE: u8 = 1;
+///
+/// #[derive(enum_utils::TryFromFloat)]
+/// #[repr(u8)]
+/// pub enum Direction {
+///     North = BASE,
+///     East,
+/// }
+/// ```
+#[proc_macro_derive(TryFromFloat, attributes(enumeration))]
+pub fn try_from_float_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    derive_scoped("TryFromFloat", ast, conv::derive_try_from_float).into()
+}
+
+/// Derives [`TryFrom<char>`] for an enum whose variants are tagged with a single character, and
+/// [`From`] for converting each variant back into its character.
+///
+/// Each unskipped variant must have a unique `#[enumeration(char = '...')]`. This is useful for
+/// wire formats which use single-character tags, such as FIX or PGN. Converting a skipped variant
+/// into a `char` panics.
+///
+/// [`TryFrom<char>`]: https://doc.rust-lang.org/std/convert/trait.TryFrom.html
+/// [`From`]: https://doc.rust-lang.org/std/convert/trait.From.html
+///
+/// # Examples
+///
+/// ```
+/// use std::convert::TryFrom;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, enum_utils::TryFromChar)]
+/// pub enum Side {
+///     #[enumeration(char = '1')]
+///     Buy,
+///     #[enumeration(char = '2')]
+///     Sell,
+/// }
+///
+/// assert_eq!(Side::try_from('2'), Ok(Side::Sell));
+/// assert_eq!(Side::try_from('3'), Err(()));
+/// assert_eq!(char::from(Side::Buy), '1');
+/// ```
+///
+/// Every unskipped variant must have a character.
+///
+/// ```compile_fail
+/// #[derive(enum_utils::TryFromChar)]
+/// pub enum Side {
+///     #[enumeration(char = '1')]
+///     Buy,
+///     Sell,
+/// }
+/// ```
+#[proc_macro_derive(TryFromChar, attributes(enumeration))]
+pub fn try_from_char_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    derive_scoped("TryFromChar", ast, conv::derive_try_from_char).into()
+}
+
+/// Derives [`From`] and [`TryFrom`] to convert other C-like enums into this one, by pairing
+/// variants with the same name.
+///
+/// Each source enum is given with `#[enumeration(from = "path::to::Enum")]` or
+/// `#[enumeration(try_from = "path::to::Enum")]`, either of which may be repeated. This is useful
+/// for keeping mirrored enums in sync across crate boundaries, such as the public and internal
+/// versions of the same enum.
+///
+/// For `from`, every variant of the source enum must have exactly one counterpart, otherwise the
+/// `match` in the generated code fails to compile. For `try_from`, the source enum may have
+/// variants which are not in this one, and converting them returns the original value as the
+/// error. In both cases, every unskipped variant of this enum must name a variant of the source
+/// enum.
+///
+/// [`From`]: https://doc.rust-lang.org/std/convert/trait.From.html
+/// [`TryFrom`]: https://doc.rust-lang.org/std/convert/trait.TryFrom.html
+///
+/// # Examples
+///
+/// ```
+/// mod internal {
+///     pub enum Status {
+///         Active,
+///         Disabled,
+///     }
+/// }
+///
+/// #[derive(Debug, PartialEq, enum_utils::FromEnum)]
+/// #[enumeration(from = "internal::Status")]
+/// pub enum Status {
+///     Active,
+///     #[enumeration(from_variant = "Disabled")]
+///     Inactive,
+/// }
+///
+/// assert_eq!(Status::from(internal::Status::Disabled), Status::Inactive);
+/// ```
+///
+/// Variants of the source enum without a counterpart are a compile error.
+///
+/// ```compile_fail
+/// mod internal {
+///     pub enum Status {
+///         Active,
+///         Disabled,
+///     }
+/// }
+///
+/// #[derive(enum_utils::FromEnum)]
+/// #[enumeration(from = "internal::Status")]
+/// pub enum Status {
+///     Active,
+/// }
+/// ```
+///
+/// A subset of a larger enum can be converted with `try_from`.
+///
+/// ```
+/// use std::convert::TryFrom;
+///
+/// #[derive(Debug, PartialEq)]
+/// pub enum Shape {
+///     Circle,
+///     Square,
+///     Hexagon,
+/// }
+///
+/// #[derive(Debug, PartialEq, enum_utils::FromEnum)]
+/// #[enumeration(try_from = "Shape")]
+/// pub enum Simple {
+///     Circle,
+///     Square,
+/// }
+///
+/// assert_eq!(Simple::try_from(Shape::Square), Ok(Simple::Square));
+/// assert_eq!(Simple::try_from(Shape::Hexagon), Err(Shape::Hexagon));
+/// ```
+///
+/// # Attributes
+///
+/// ## `#[enumeration(from_variant = "...")]`
+///
+/// The name of the variant in the source enum which converts to this one, if it differs from the
+/// name of this variant.
+#[proc_macro_derive(FromEnum, attributes(enumeration))]
+pub fn from_enum_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    derive_scoped("FromEnum", ast, conv::derive_from_enum).into()
+}
+
+/// Derives `fn from_i32(i32) -> Option<Self>` and `const fn as_i32(&self) -> i32`, which follow
+/// the conventions of enums generated by [`prost`].
+///
+/// This lets hand-written protobuf glue use an enum from this crate in place of a generated one.
+/// Like [`TryFromRepr`], a primitive `#[repr(...)]` must be specified and `assert_discriminants`
+/// is supported. Since protobuf enums are encoded as an `i32`, compilation will fail if any
+/// discriminant does not fit in one.
+///
+/// [`prost`]: https://docs.rs/prost
+/// [`TryFromRepr`]: derive.TryFromRepr.html
+///
+/// # Examples
+///
+/// ```
+/// #[derive(Debug, PartialEq, enum_utils::ProtoEnum)]
+/// #[repr(u8)]
+/// enum Status {
+///     Unspecified = 0,
+///     Active = 1,
+///     Banned = 7,
+/// }
+///
+/// assert_eq!(Status::from_i32(7), Some(Status::Banned));
+/// assert_eq!(Status::from_i32(263), None);
+/// assert_eq!(Status::Active.as_i32(), 1);
+/// ```
+///
+/// ```compile_fail
+/// #[derive(enum_utils::ProtoEnum)]
+/// #[repr(u64)]
+/// enum Large {
+///     Small = 0,
+///     Large = 1 << 40,
+/// }
+/// ```
+#[proc_macro_derive(ProtoEnum, attributes(enumeration))]
+pub fn proto_enum_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    derive_scoped("ProtoEnum", ast, conv::derive_proto_enum).into()
+}
+
+/// Derives `From<bool>` for an enum with two variants, and `From<Enum>` for `bool`.
+///
+/// This is for enums which are booleans with more descriptive names. By default, the first
+/// variant corresponds to `false` and the second to `true`, mirroring the order of `bool`.
+///
+/// # Examples
+///
+/// ```
+/// #[derive(Debug, PartialEq, enum_utils::FromBool)]
+/// enum Visibility {
+///     Hidden,
+///     Shown,
+/// }
+///
+/// assert_eq!(Visibility::from(true), Visibility::Shown);
+/// assert!(!bool::from(Visibility::Hidden));
+/// ```
+///
+/// # Attributes
+///
+/// ## `#[enumeration(true_variant)]`, `#[enumeration(false_variant)]`
+///
+/// Marks the variant which corresponds to `true` or `false`. Only one of the two variants needs
+/// to be marked.
+///
+/// ```
+/// #[derive(Debug, PartialEq, enum_utils::FromBool)]
+/// enum Mode {
+///     #[enumeration(true_variant)]
+///     Enabled,
+///     Disabled,
+/// }
+///
+/// assert_eq!(Mode::from(true), Mode::Enabled);
+/// assert!(!bool::from(Mode::Disabled));
+/// ```
+#[proc_macro_derive(FromBool, attributes(enumeration))]
+pub fn from_bool_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    derive_scoped("FromBool", ast, conv::derive_from_bool).into()
+}
+
+/// Generates a `const fn value(&self)` which returns a literal attached to each variant with
+/// `#[enumeration(value = ...)]`.
+///
+/// This is a generalization of the discriminant: the value can be any literal, and the return
+/// type is the type of that literal. Integer and float literals must therefore have a type suffix
+/// (e.g. `404u16`), and every variant must have a value of the same type. Variants may have
+/// fields.
+///
+/// # Examples
+///
+/// ```
+/// #[derive(enum_utils::EnumValue)]
+/// enum Status {
+///     #[enumeration(value = 200u16)]
+///     Ok,
+///     #[enumeration(value = 404u16)]
+///     NotFound,
+///     #[enumeration(value = 500u16)]
+///     Internal(String),
+/// }
+///
+/// #[derive(enum_utils::EnumValue)]
+/// enum Unit {
+///     #[enumeration(value = "m")]
+///     Meter,
+///     #[enumeration(value = "s")]
+///     Second,
+/// }
+///
+/// const NOT_FOUND: u16 = Status::NotFound.value();
+/// assert_eq!(NOT_FOUND, 404);
+/// assert_eq!(Status::Internal("oops".to_owned()).value(), 500);
+/// assert_eq!(Unit::Second.value(), "s");
+/// ```
+#[proc_macro_derive(EnumValue, attributes(enumeration))]
+pub fn enum_value_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    derive_scoped("EnumValue", ast, value::derive).into()
+}
+
+/// Generates a streaming matcher, `{Enum}Matcher`, which recognizes the names of each variant one
+/// byte at a time.
+///
+/// This is useful when a name may be split across several network buffers, since the bytes do
+/// not have to be copied into a contiguous slice first. Each call to `push` returns an
+/// [`enum_utils_traits::MatchState`], indicating whether the bytes so far are the name of a
+/// variant, the start of one, or neither. A name is only recognized once the caller knows where
+/// it ends, so `Matched` may be followed by `Incomplete` if a longer name continues it.
+///
+/// Names and aliases are determined in the same way as for [`FromStr`], including
+/// `case_insensitive` and `dash_underscore_insensitive`. `case_insensitive = "unicode"` is not
+/// supported. This requires a dependency on the [`enum-utils-traits`] crate.
+///
+/// # Examples
+///
+/// ```
+/// use enum_utils_traits::MatchState;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, enum_utils::Matcher)]
+/// #[enumeration(case_insensitive)]
+/// enum Command {
+///     Get,
+///     #[enumeration(alias = "GETALL")]
+///     GetMany,
+///     Set,
+/// }
+///
+/// let mut matcher = CommandMatcher::new();
+/// for chunk in [&b"G"[..], &b"e"[..]].iter() {
+///     for &b in chunk.iter() {
+///         assert_eq!(matcher.push(b), MatchState::Incomplete);
+///     }
+/// }
+///
+/// assert_eq!(matcher.push(b't'), MatchState::Matched(Command::Get));
+/// assert_eq!(matcher.push(b'A'), MatchState::Incomplete);
+/// assert_eq!(matcher.push(b'x'), MatchState::Failed);
+///
+/// matcher.reset();
+/// let last = b"SET".iter().map(|&b| matcher.push(b)).last();
+/// assert_eq!(last, Some(MatchState::Matched(Command::Set)));
+/// ```
+///
+/// [`FromStr`]: derive.FromStr.html
+/// [`enum_utils_traits::MatchState`]: https://docs.rs/enum-utils-traits/0.1/enum_utils_traits/enum.MatchState.html
+/// [`enum-utils-traits`]: https://docs.rs/enum-utils-traits/0.1
+#[proc_macro_derive(Matcher, attributes(enumeration))]
+pub fn matcher_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    derive_scoped("Matcher", ast, matcher::derive).into()
 }
@@ -0,0 +1,148 @@
+use proc_macro2::{Literal, TokenStream};
+use quote::{format_ident, quote};
+
+use crate::attr::{Enum, ErrorList};
+
+pub fn derive(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let name = input.name;
+    let unskipped = input.unskipped_unit_variants()?;
+
+    let vis = input.vis;
+    let map = format_ident!("{}Map", name);
+    let len = Literal::usize_unsuffixed(unskipped.len());
+    let doc = format!("A map from each variant of [`{}`] to a value of type `V`.", name);
+
+    let index_arms = unskipped.iter()
+        .enumerate()
+        .map(|(i, vident)| {
+            let i = Literal::usize_unsuffixed(i);
+            quote!(#name::#vident => #i)
+        });
+
+    let skipped_arms = input.variants.iter()
+        .filter(|(_, attrs)| attrs.skip)
+        .map(|(v, _)| {
+            let vident = &v.ident;
+            let msg = format!("`{}::{}` is skipped and cannot be used as a key", name, vident);
+            quote!(#name::#vident { .. } => panic!(#msg))
+        });
+
+    let from_fn_elems = unskipped.iter().map(|vident| quote!(f(#name::#vident)));
+    let keys: Vec<_> = unskipped.iter().map(|vident| quote!(#name::#vident)).collect();
+
+    Ok(quote! {
+        #[doc = #doc]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #vis struct #map<V>([V; #len]);
+
+        impl<V> #map<V> {
+            /// Creates a map by calling `f` on each key in declaration order.
+            #[allow(unused_mut, unused_variables)]
+            pub fn from_fn(mut f: impl FnMut(#name) -> V) -> Self {
+                #map([#( #from_fn_elems ),*])
+            }
+
+            /// Creates a map from an array of values, one for each key in declaration order.
+            pub const fn from_array(values: [V; #len]) -> Self {
+                #map(values)
+            }
+
+            /// Returns the values in this map as an array in declaration order.
+            pub fn into_array(self) -> [V; #len] {
+                self.0
+            }
+
+            /// Returns the index in the underlying array of the value for `key`.
+            fn __index(key: &#name) -> usize {
+                match *key {
+                    #( #index_arms, )*
+                    #( #skipped_arms, )*
+                }
+            }
+
+            /// Returns an iterator over each key and a reference to its value.
+            pub fn iter(&self) -> impl Iterator<Item = (#name, &V)> {
+                let keys: [#name; #len] = [#( #keys ),*];
+                ::std::iter::IntoIterator::into_iter(keys).zip(self.0.iter())
+            }
+
+            /// Returns an iterator over each key and a mutable reference to its value.
+            pub fn iter_mut(&mut self) -> impl Iterator<Item = (#name, &mut V)> {
+                let keys: [#name; #len] = [#( #keys ),*];
+                ::std::iter::IntoIterator::into_iter(keys).zip(self.0.iter_mut())
+            }
+
+            /// Returns an iterator over references to each value in declaration order.
+            pub fn values(&self) -> impl Iterator<Item = &V> {
+                self.0.iter()
+            }
+        }
+
+        impl<V: ::std::default::Default> ::std::default::Default for #map<V> {
+            fn default() -> Self {
+                Self::from_fn(|_| ::std::default::Default::default())
+            }
+        }
+
+        impl<V> ::std::ops::Index<#name> for #map<V> {
+            type Output = V;
+
+            fn index(&self, key: #name) -> &V {
+                &self.0[Self::__index(&key)]
+            }
+        }
+
+        impl<V> ::std::ops::IndexMut<#name> for #map<V> {
+            fn index_mut(&mut self, key: #name) -> &mut V {
+                &mut self.0[Self::__index(&key)]
+            }
+        }
+    })
+}
+
+pub fn derive_index(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let name = input.name;
+    let unskipped = input.unskipped_unit_variants()?;
+
+    let len = Literal::usize_unsuffixed(unskipped.len());
+
+    let index_arms = unskipped.iter()
+        .enumerate()
+        .map(|(i, vident)| {
+            let i = Literal::usize_unsuffixed(i);
+            quote!(#name::#vident => #i)
+        });
+
+    let skipped_arms = input.variants.iter()
+        .filter(|(_, attrs)| attrs.skip)
+        .map(|(v, _)| {
+            let vident = &v.ident;
+            let msg = format!("`{}::{}` is skipped and cannot be used as an index", name, vident);
+            quote!(#name::#vident { .. } => panic!(#msg))
+        });
+
+    Ok(quote! {
+        impl #name {
+            const fn __array_index(&self) -> usize {
+                match *self {
+                    #( #index_arms, )*
+                    #( #skipped_arms, )*
+                }
+            }
+        }
+
+        impl<T> ::std::ops::Index<#name> for [T; #len] {
+            type Output = T;
+
+            fn index(&self, key: #name) -> &T {
+                &self[key.__array_index()]
+            }
+        }
+
+        impl<T> ::std::ops::IndexMut<#name> for [T; #len] {
+            fn index_mut(&mut self, key: #name) -> &mut T {
+                &mut self[key.__array_index()]
+            }
+        }
+    })
+}
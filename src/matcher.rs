@@ -0,0 +1,189 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use proc_macro2::{Literal, TokenStream};
+use quote::{format_ident, quote};
+
+use crate::attr::{CaseFolding, Enum, ErrorList};
+use crate::from_str::FromStrImpl;
+
+/// A trie over bytes, where each edge matches a set of bytes instead of a single one.
+///
+/// Since the sets of different edges may overlap, this is nondeterministic, and is converted to a
+/// DFA before generating code.
+struct Nfa<'a> {
+    nodes: Vec<Node<'a>>,
+}
+
+#[derive(Default)]
+struct Node<'a> {
+    edges: Vec<(Vec<u8>, usize)>,
+
+    /// The variant named by the path to this node, and how that name is matched.
+    value: Option<(CaseFolding, &'a syn::Ident)>,
+}
+
+impl<'a> Nfa<'a> {
+    fn new() -> Self {
+        Nfa { nodes: vec![Node::default()] }
+    }
+
+    fn insert(&mut self, s: &str, variant: &'a syn::Ident, case: CaseFolding, dash_underscore: bool) {
+        let mut cur = 0;
+        for b in s.bytes() {
+            let mut class = match b {
+                b'-' | b'_' if dash_underscore => vec![b'-', b'_'],
+                _ if case == CaseFolding::Ascii && b.is_ascii_alphabetic() =>
+                    vec![b.to_ascii_uppercase(), b.to_ascii_lowercase()],
+                _ => vec![b],
+            };
+            class.sort_unstable();
+
+            let next = self.nodes[cur].edges.iter()
+                .find(|(c, _)| *c == class)
+                .map(|&(_, next)| next);
+
+            cur = match next {
+                Some(next) => next,
+                None => {
+                    self.nodes.push(Node::default());
+                    let next = self.nodes.len() - 1;
+                    self.nodes[cur].edges.push((class, next));
+                    next
+                }
+            };
+        }
+
+        // An exact match takes precedence over a case-insensitive one.
+        let value = &mut self.nodes[cur].value;
+        match value {
+            Some((prev, _)) if *prev <= case => {}
+            _ => *value = Some((case, variant)),
+        }
+    }
+}
+
+pub fn derive(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let imp = FromStrImpl::for_enum(input)?;
+    let name = &imp.enum_name;
+    let vis = input.vis;
+    let matcher = format_ident!("{}Matcher", name);
+
+    let mut nfa = Nfa::new();
+    for (s, variant, case) in imp.entries() {
+        if case == CaseFolding::Unicode {
+            let mut errors = ErrorList::new();
+            errors.push_back(format_diag!(MisplacedAttribute,
+                                          "`Matcher` does not support `case_insensitive = \"unicode\"`")
+                .with_span(variant.span()));
+            return Err(errors);
+        }
+
+        nfa.insert(s, variant, case, imp.dash_underscore);
+    }
+
+    // Convert the NFA to a DFA by the subset construction. `u16::MAX` is reserved for the failed
+    // state.
+    let mut ids: BTreeMap<BTreeSet<usize>, u16> = BTreeMap::new();
+    let mut states: Vec<BTreeSet<usize>> = vec![];
+    let mut transitions = vec![];
+    let mut accepting = vec![];
+
+    let start: BTreeSet<usize> = [0].iter().cloned().collect();
+    ids.insert(start.clone(), 0);
+    states.push(start);
+
+    let mut i = 0;
+    while i < states.len() {
+        let state = states[i].clone();
+
+        let mut next_by_byte: BTreeMap<u8, BTreeSet<usize>> = BTreeMap::new();
+        for &node in &state {
+            for (class, next) in &nfa.nodes[node].edges {
+                for &b in class {
+                    next_by_byte.entry(b).or_default().insert(*next);
+                }
+            }
+        }
+
+        // Group the bytes by the state they lead to, so each target gets a single match arm.
+        let mut bytes_by_next: BTreeMap<u16, Vec<u8>> = BTreeMap::new();
+        for (b, next) in next_by_byte {
+            let id = match ids.get(&next) {
+                Some(&id) => id,
+                None if states.len() < u16::MAX as usize => {
+                    let id = states.len() as u16;
+                    ids.insert(next.clone(), id);
+                    states.push(next);
+                    id
+                }
+                None => {
+                    let mut errors = ErrorList::new();
+                    errors.push_back(format_diag!(TooManyVariants, "Names are too long to fit in a `Matcher`")
+                        .with_span(name.span()));
+                    return Err(errors);
+                }
+            };
+
+            bytes_by_next.entry(id).or_default().push(b);
+        }
+
+        let from = Literal::u16_unsuffixed(i as u16);
+        for (next, bytes) in bytes_by_next {
+            let next = Literal::u16_unsuffixed(next);
+            let bytes = bytes.into_iter().map(Literal::u8_unsuffixed);
+            transitions.push(quote!(#( (#from, #bytes) )|* => #next));
+        }
+
+        let value = state.iter()
+            .filter_map(|&node| nfa.nodes[node].value)
+            .min_by_key(|&(case, _)| case);
+        if let Some((_, variant)) = value {
+            accepting.push(quote!(#from => ::enum_utils_traits::MatchState::Matched(#name::#variant)));
+        }
+
+        i += 1;
+    }
+
+    let doc = format!("A streaming matcher for the names and aliases of [`{}`].", name);
+    Ok(quote! {
+        #[doc = #doc]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        #vis struct #matcher {
+            state: u16,
+        }
+
+        impl #matcher {
+            const FAILED: u16 = u16::MAX;
+
+            /// Returns a matcher which has not been fed any bytes.
+            #vis const fn new() -> Self {
+                #matcher { state: 0 }
+            }
+
+            /// Returns the matcher to its initial state.
+            #vis fn reset(&mut self) {
+                self.state = 0;
+            }
+
+            /// Feeds the next byte of the input to the matcher.
+            #vis fn push(&mut self, byte: u8) -> ::enum_utils_traits::MatchState<#name> {
+                self.state = match (self.state, byte) {
+                    #( #transitions, )*
+                    _ => Self::FAILED,
+                };
+
+                match self.state {
+                    #( #accepting, )*
+                    Self::FAILED => ::enum_utils_traits::MatchState::Failed,
+                    _ => ::enum_utils_traits::MatchState::Incomplete,
+                }
+            }
+        }
+
+        impl ::std::default::Default for #matcher {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+    })
+}
@@ -0,0 +1,400 @@
+use std::collections::BTreeSet;
+
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+
+use crate::attr::{CaseFolding, Enum, ErrorList, VariantAttrs};
+use crate::conv::FromRepr;
+
+/// Returns each variant of the enum paired with its canonical name, or `None` if the variant is
+/// skipped. Names follow the `serialize` rule of `rename_all`.
+///
+/// Unskipped variants must not have fields, since derives which use this table need to construct
+/// a variant from its name.
+pub fn name_table<'a>(input: &Enum<'a>) -> Result<Vec<(&'a syn::Variant, Option<String>)>, ErrorList> {
+    name_table_impl(input, false)
+}
+
+/// Like [`name_table`], but unskipped variants may have fields.
+///
+/// This is for derives which only go from a variant to its name, so the fields are ignored.
+pub fn name_table_ignoring_fields<'a>(input: &Enum<'a>)
+    -> Result<Vec<(&'a syn::Variant, Option<String>)>, ErrorList>
+{
+    name_table_impl(input, true)
+}
+
+fn name_table_impl<'a>(input: &Enum<'a>, allow_fields: bool)
+    -> Result<Vec<(&'a syn::Variant, Option<String>)>, ErrorList>
+{
+    let mut errors = ErrorList::new();
+    let table = input.variants.iter()
+        .map(|(v, attrs)| {
+            if attrs.skip {
+                return (*v, None);
+            }
+
+            if !allow_fields && v.fields != syn::Fields::Unit {
+                errors.push_back(format_diag!(VariantHasFields, "An (unskipped) variant cannot have fields")
+                    .with_span(v.ident.span()));
+            }
+
+            (*v, Some(input.attrs.display_name(v, attrs)))
+        })
+        .collect();
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    Ok(table)
+}
+
+/// Rewrites each positional argument in a format string (e.g. `{0}` or `{1:>4}`) to refer to the
+/// binding for that field of a tuple variant (`{_0}` or `{_1:>4}`).
+fn rewrite_positional_args(fmt: &str) -> String {
+    let mut ret = String::with_capacity(fmt.len());
+    let mut chars = fmt.chars().peekable();
+    while let Some(c) = chars.next() {
+        ret.push(c);
+        if c != '{' {
+            continue;
+        }
+
+        if chars.peek() == Some(&'{') {
+            ret.extend(chars.next());
+            continue;
+        }
+
+        if chars.peek().is_some_and(char::is_ascii_digit) {
+            ret.push('_');
+        }
+    }
+
+    ret
+}
+
+/// Returns a `match` arm which writes a variant using its `format` template.
+///
+/// Fields are bound by name, or as `_0`, `_1`, ... for tuple variants, and captured by the
+/// template. Unused fields are allowed.
+pub fn format_arm(name: &syn::Ident, v: &syn::Variant, fmt: &str) -> TokenStream {
+    let vident = &v.ident;
+    let (pattern, fmt) = match &v.fields {
+        syn::Fields::Unit => (quote!(#name::#vident), fmt.to_owned()),
+
+        syn::Fields::Named(fields) => {
+            let idents = fields.named.iter().map(|f| &f.ident);
+            (quote!(#name::#vident { #( ref #idents ),* }), fmt.to_owned())
+        }
+
+        syn::Fields::Unnamed(fields) => {
+            let idents = (0..fields.unnamed.len()).map(|i| format_ident!("_{}", i));
+            (quote!(#name::#vident( #( ref #idents ),* )), rewrite_positional_args(fmt))
+        }
+    };
+
+    let fmt = syn::LitStr::new(&fmt, Span::call_site());
+    quote! {
+        #[allow(unused_variables)]
+        #pattern => write!(f, #fmt)
+    }
+}
+
+pub fn derive_display(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let name = input.name;
+
+    let mut arms = vec![];
+    for (v, attrs) in input.variants.iter() {
+        let vident = &v.ident;
+        if let Some(fmt) = &attrs.format {
+            arms.push(format_arm(name, v, fmt));
+        } else if attrs.skip {
+            // Skipped variants have no canonical name, so we fall back to the identifier.
+            let s = vident.to_string();
+            arms.push(quote!(#name::#vident { .. } => f.pad(#s)));
+        } else {
+            let s = input.attrs.display_name(v, attrs);
+            arms.push(quote!(#name::#vident { .. } => f.pad(#s)));
+        }
+    }
+
+    Ok(quote! {
+        impl ::std::fmt::Display for #name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                match *self {
+                    #( #arms, )*
+                }
+            }
+        }
+    })
+}
+
+/// Returns a `match` arm for each variant which evaluates to its name as a `&'static str`.
+///
+/// Skipped variants have no canonical name, so we fall back to the identifier.
+fn static_name_arms(input: &Enum) -> Result<Vec<TokenStream>, ErrorList> {
+    let name = input.name;
+
+    let arms = name_table_ignoring_fields(input)?
+        .into_iter()
+        .map(|(v, s)| {
+            let vident = &v.ident;
+            match s {
+                Some(s) => quote!(#name::#vident { .. } => #s),
+                None => {
+                    let s = vident.to_string();
+                    quote!(#name::#vident { .. } => #s)
+                }
+            }
+        })
+        .collect();
+
+    Ok(arms)
+}
+
+pub fn derive_as_str(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let name = input.name;
+    let arms = static_name_arms(input)?;
+
+    Ok(quote! {
+        impl #name {
+            const fn name(&self) -> &'static str {
+                match *self {
+                    #( #arms, )*
+                }
+            }
+        }
+    })
+}
+
+pub fn derive_name_of(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let FromRepr { name, repr, assertions, lookup } = FromRepr::for_enum(input)?;
+    let arms = static_name_arms(input)?;
+
+    Ok(quote! {
+        #assertions
+
+        impl #name {
+            const fn name_of(d: #repr) -> Option<&'static str> {
+                match #lookup {
+                    Some(v) => Some(match v {
+                        #( #arms, )*
+                    }),
+                    None => None,
+                }
+            }
+        }
+    })
+}
+
+pub fn derive_as_ref_str(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let name = input.name;
+    let arms = static_name_arms(input)?;
+
+    Ok(quote! {
+        impl ::std::convert::AsRef<str> for #name {
+            fn as_ref(&self) -> &str {
+                let s: &'static str = match *self {
+                    #( #arms, )*
+                };
+
+                s
+            }
+        }
+
+        impl ::std::borrow::Borrow<str> for #name {
+            fn borrow(&self) -> &str {
+                ::std::convert::AsRef::<str>::as_ref(self)
+            }
+        }
+
+        impl ::std::convert::From<#name> for &'static str {
+            fn from(v: #name) -> Self {
+                match v {
+                    #( #arms, )*
+                }
+            }
+        }
+    })
+}
+
+pub fn derive_partial_eq_str(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let name = input.name;
+
+    let dash = input.attrs.dash_underscore;
+    let eq = |case| match (case, dash) {
+        (CaseFolding::None, false) => quote!(<str as ::std::cmp::PartialEq>::eq),
+        (CaseFolding::Ascii, false) => quote!(str::eq_ignore_ascii_case),
+        (CaseFolding::None, true) => quote!(_eq_dash),
+        (CaseFolding::Ascii, true) => quote!(_eq_dash_ignore_ascii_case),
+        (CaseFolding::Unicode, _) => quote!(_eq_folded),
+    };
+
+    let mut cases = BTreeSet::new();
+    let arms: Vec<_> = name_table_ignoring_fields(input)?
+        .into_iter()
+        .zip(input.variants.iter())
+        .map(|((v, s), (_, attrs))| {
+            let vident = &v.ident;
+            let case = input.attrs.variant_case(attrs);
+            let eq = eq(case);
+            match s {
+                Some(s) => {
+                    cases.insert(case);
+                    quote!(#name::#vident { .. } => #eq(#s, other))
+                }
+
+                // The catch-all variant is equal to the string it was parsed from.
+                None if attrs.other && v.fields.len() == 1 => {
+                    cases.insert(case);
+                    quote!(#name::#vident(ref s) => #eq(::std::convert::AsRef::<str>::as_ref(s), other))
+                }
+
+                None => quote!(#name::#vident { .. } => false),
+            }
+        })
+        .collect();
+
+    // Helpers for the comparisons which `str` does not provide.
+    let is_dash = quote!(|c| c == b'-' || c == b'_');
+    let helpers = cases.into_iter().map(|case| match (case, dash) {
+        (CaseFolding::None, false) | (CaseFolding::Ascii, false) => TokenStream::new(),
+
+        (CaseFolding::None, true) => quote! {
+            fn _eq_dash(a: &str, b: &str) -> bool {
+                let is_dash = #is_dash;
+                a.len() == b.len()
+                    && a.bytes().zip(b.bytes()).all(|(x, y)| x == y || (is_dash(x) && is_dash(y)))
+            }
+        },
+
+        (CaseFolding::Ascii, true) => quote! {
+            fn _eq_dash_ignore_ascii_case(a: &str, b: &str) -> bool {
+                let is_dash = #is_dash;
+                a.len() == b.len()
+                    && a.bytes().zip(b.bytes()).all(|(x, y)| x.eq_ignore_ascii_case(&y) || (is_dash(x) && is_dash(y)))
+            }
+        },
+
+        (CaseFolding::Unicode, _) => {
+            let fold_case_fn = crate::from_str::fold_case_fn();
+            let undash = if dash {
+                quote!(.map(|c| if c == '-' { '_' } else { c }))
+            } else {
+                TokenStream::new()
+            };
+
+            quote! {
+                fn _eq_folded(a: &str, b: &str) -> bool {
+                    #fold_case_fn
+                    a.chars().map(_fold_case)#undash.eq(b.chars().map(_fold_case)#undash)
+                }
+            }
+        }
+    });
+
+    Ok(quote! {
+        impl ::std::cmp::PartialEq<str> for #name {
+            fn eq(&self, other: &str) -> bool {
+                #( #helpers )*
+                match *self {
+                    #( #arms, )*
+                }
+            }
+        }
+
+        impl<'a> ::std::cmp::PartialEq<&'a str> for #name {
+            fn eq(&self, other: &&'a str) -> bool {
+                <Self as ::std::cmp::PartialEq<str>>::eq(self, *other)
+            }
+        }
+    })
+}
+
+pub fn derive_variant_names(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let name = input.name;
+
+    let names = name_table_ignoring_fields(input)?
+        .into_iter()
+        .filter_map(|(_, s)| s);
+
+    if input.attrs.traits {
+        return Ok(quote! {
+            impl ::enum_utils_traits::VariantNames for #name {
+                const NAMES: &'static [&'static str] = &[#( #names ),*];
+            }
+        });
+    }
+
+    Ok(quote! {
+        impl #name {
+            const NAMES: &'static [&'static str] = &[#( #names ),*];
+        }
+    })
+}
+
+/// Returns the first paragraph of the doc comment on a variant, with each line trimmed and joined
+/// by a single space.
+pub fn doc_summary(v: &syn::Variant) -> Option<String> {
+    use syn::{Lit, Meta, MetaNameValue};
+
+    let mut summary = String::new();
+    for attr in v.attrs.iter().filter(|attr| attr.path.is_ident("doc")) {
+        let line = match attr.parse_meta() {
+            Ok(Meta::NameValue(MetaNameValue { lit: Lit::Str(s), .. })) => s.value(),
+            _ => continue,
+        };
+
+        let line = line.trim();
+        if line.is_empty() {
+            if summary.is_empty() {
+                continue;
+            }
+
+            break;
+        }
+
+        if !summary.is_empty() {
+            summary.push(' ');
+        }
+
+        summary.push_str(line);
+    }
+
+    if summary.is_empty() {
+        None
+    } else {
+        Some(summary)
+    }
+}
+
+/// Returns the message for a variant: its `message` attribute, or the summary of its doc comment,
+/// or failing that its canonical name.
+pub fn message(input: &Enum, v: &syn::Variant, attrs: &VariantAttrs) -> String {
+    attrs.message.clone()
+        .or_else(|| doc_summary(v))
+        .unwrap_or_else(|| input.attrs.display_name(v, attrs))
+}
+
+pub fn derive_message(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let name = input.name;
+
+    let arms = input.variants.iter()
+        .map(|(v, attrs)| {
+            let vident = &v.ident;
+            let message = message(input, v, attrs);
+
+            quote!(#name::#vident { .. } => #message)
+        });
+
+    Ok(quote! {
+        impl #name {
+            const fn message(&self) -> &'static str {
+                match *self {
+                    #( #arms, )*
+                }
+            }
+        }
+    })
+}
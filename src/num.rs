@@ -0,0 +1,81 @@
+use proc_macro2::{Literal, TokenStream};
+use quote::quote;
+
+use crate::attr::{Enum, ErrorList};
+use crate::conv::FromRepr;
+use crate::iter::{checked_variants, detect_contiguous_run, transmute_closure};
+
+pub fn derive_from_primitive(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let FromRepr { name, repr, assertions, lookup } = FromRepr::for_enum(input)?;
+
+    // If the discriminants form a single, contiguous run, a bounds check followed by a transmute
+    // replaces the `match`.
+    let run = input.discriminants.as_ref().and_then(|ds| detect_contiguous_run(ds.iter().copied()));
+    let lookup = match run {
+        Some(range) => {
+            let start = Literal::i128_unsuffixed(*range.start());
+            let end = Literal::i128_unsuffixed(*range.end());
+            let checked = checked_variants(input, true);
+            let transmute = transmute_closure(&repr, checked.as_deref(), "FromPrimitive");
+            quote! {
+                if (#start ..= #end).contains(&d) {
+                    Some((#transmute)(d))
+                } else {
+                    None
+                }
+            }
+        }
+
+        None => lookup,
+    };
+
+    Ok(quote! {
+        #assertions
+
+        impl ::num_traits::FromPrimitive for #name {
+            fn from_i64(n: i64) -> Option<Self> {
+                let d = <#repr as ::std::convert::TryFrom<i64>>::try_from(n).ok()?;
+                #lookup
+            }
+
+            fn from_u64(n: u64) -> Option<Self> {
+                let d = <#repr as ::std::convert::TryFrom<u64>>::try_from(n).ok()?;
+                #lookup
+            }
+        }
+    })
+}
+
+pub fn derive_to_primitive(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let FromRepr { name, repr, assertions, .. } = FromRepr::for_enum(input)?;
+
+    // The enum need not be `Copy`, so each variant is cast separately.
+    let arms: Vec<_> = input.variants.iter()
+        .map(|(v, _)| {
+            let vident = &v.ident;
+            quote!(#name::#vident => #name::#vident as #repr)
+        })
+        .collect();
+
+    Ok(quote! {
+        #assertions
+
+        impl ::num_traits::ToPrimitive for #name {
+            fn to_i64(&self) -> Option<i64> {
+                let d: #repr = match *self {
+                    #( #arms, )*
+                };
+
+                <i64 as ::std::convert::TryFrom<#repr>>::try_from(d).ok()
+            }
+
+            fn to_u64(&self) -> Option<u64> {
+                let d: #repr = match *self {
+                    #( #arms, )*
+                };
+
+                <u64 as ::std::convert::TryFrom<#repr>>::try_from(d).ok()
+            }
+        }
+    })
+}
@@ -0,0 +1,64 @@
+use std::collections::BTreeMap;
+
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::attr::{Enum, ErrorList};
+
+pub fn derive(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let Enum { name, variants, .. } = input;
+
+    let mut errors = ErrorList::new();
+    let mut seen = BTreeMap::new();
+    let mut arms = vec![];
+    for (v, attrs) in variants.iter() {
+        let vident = &v.ident;
+        if v.fields != syn::Fields::Unit {
+            errors.push_back(format_diag!(VariantHasFields, "Variant cannot have fields")
+                .with_span(vident.span()));
+            continue;
+        }
+
+        let order = match attrs.order {
+            Some(n) => n,
+            None => {
+                errors.push_back(format_diag!(MissingOrder, "Variant `{}` has no \"order\"", vident)
+                    .with_span(vident.span()));
+                continue;
+            }
+        };
+
+        if let Some(prev) = seen.insert(order, vident) {
+            errors.push_back(format_diag!(DuplicateOrder, "Variants `{}` and `{}` have the same order ({})",
+                                          prev, vident, order)
+                .with_span(vident.span()));
+            continue;
+        }
+
+        arms.push(quote!(#name::#vident => #order));
+    }
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    Ok(quote! {
+        impl ::std::cmp::Ord for #name {
+            fn cmp(&self, other: &Self) -> ::std::cmp::Ordering {
+                fn order(v: &#name) -> u64 {
+                    match *v {
+                        #( #arms, )*
+                    }
+                }
+
+                ::std::cmp::Ord::cmp(&order(self), &order(other))
+            }
+        }
+
+        impl ::std::cmp::PartialOrd for #name {
+            fn partial_cmp(&self, other: &Self) -> Option<::std::cmp::Ordering> {
+                Some(::std::cmp::Ord::cmp(self, other))
+            }
+        }
+    })
+}
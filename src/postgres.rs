@@ -0,0 +1,93 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::attr::{Enum, ErrorList};
+use crate::from_str::FromStrImpl;
+use crate::name::name_table;
+
+pub fn derive(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let imp = FromStrImpl::for_enum(input)?;
+    let lookup = imp.lookup();
+    let labels = &imp.names;
+    let name = input.name;
+    let pg_name = input.attrs.pg_name.clone().unwrap_or_else(|| name.to_string());
+
+    let arms: Vec<_> = name_table(input)?
+        .into_iter()
+        .zip(input.variants.iter())
+        .map(|((v, s), (_, attrs))| {
+            let vident = &v.ident;
+            match s {
+                Some(s) => quote!(#name::#vident => #s),
+
+                // The catch-all variant is stored as the label it was parsed from.
+                None if attrs.other && v.fields.len() == 1 =>
+                    quote!(#name::#vident(ref s) => ::std::convert::AsRef::<str>::as_ref(s)),
+
+                None => {
+                    let msg = format!("`{}::{}` is skipped and cannot be stored", name, vident);
+                    quote!(#name::#vident { .. } => return Err(#msg.into()))
+                }
+            }
+        })
+        .collect();
+
+    // With a catch-all variant, any label can be read, so the labels of the Postgres enum are not
+    // checked.
+    let check_labels = if imp.has_other() {
+        quote!(true)
+    } else {
+        quote! {
+            const LABELS: &[&str] = &[#( #labels ),*];
+            labels.len() == LABELS.len() && LABELS.iter().all(|l| labels.iter().any(|m| m == l))
+        }
+    };
+
+    let accepts = quote! {
+        fn accepts(ty: &::postgres_types::Type) -> bool {
+            if ty.name() != #pg_name {
+                return false;
+            }
+
+            match ty.kind() {
+                ::postgres_types::Kind::Enum(labels) => { #check_labels }
+                _ => false,
+            }
+        }
+    };
+
+    let msg = format!("invalid label {{:?}} for `{}`", name);
+
+    Ok(quote! {
+        impl<'a> ::postgres_types::FromSql<'a> for #name {
+            fn from_sql(_: &::postgres_types::Type, raw: &'a [u8])
+                -> Result<Self, Box<dyn ::std::error::Error + Sync + Send>>
+            {
+                let s = ::std::str::from_utf8(raw)?;
+                match #lookup {
+                    Some(v) => Ok(v),
+                    None => Err(format!(#msg, s).into()),
+                }
+            }
+
+            #accepts
+        }
+
+        impl ::postgres_types::ToSql for #name {
+            fn to_sql(&self, _: &::postgres_types::Type, out: &mut ::postgres_types::private::BytesMut)
+                -> Result<::postgres_types::IsNull, Box<dyn ::std::error::Error + Sync + Send>>
+            {
+                let s: &str = match *self {
+                    #( #arms, )*
+                };
+
+                out.extend_from_slice(s.as_bytes());
+                Ok(::postgres_types::IsNull::No)
+            }
+
+            #accepts
+
+            ::postgres_types::to_sql_checked!();
+        }
+    })
+}
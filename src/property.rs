@@ -0,0 +1,49 @@
+use std::collections::BTreeMap;
+
+use proc_macro2::{Literal, TokenStream};
+use quote::quote;
+
+use crate::attr::{Enum, ErrorList};
+use enum_utils_from_str::StrMapFunc;
+
+pub fn derive(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let Enum { name, variants, .. } = input;
+
+    // Assign each distinct key an index, so that the key only needs to be looked up once.
+    let mut keys = BTreeMap::new();
+    for (_, attrs) in variants.iter() {
+        for key in attrs.props.keys() {
+            let next = keys.len();
+            keys.entry(key.as_str()).or_insert(next);
+        }
+    }
+
+    let mut trie = StrMapFunc::new("_key", "usize");
+    for (key, &idx) in &keys {
+        trie.entry(key, Literal::usize_unsuffixed(idx));
+    }
+
+    let arms = variants.iter()
+        .flat_map(|(v, attrs)| {
+            let vident = &v.ident;
+            let keys = &keys;
+            attrs.props.iter()
+                .map(move |(key, value)| {
+                    let idx = Literal::usize_unsuffixed(keys[key.as_str()]);
+                    quote!((#name::#vident { .. }, #idx) => Some(#value))
+                })
+        });
+
+    Ok(quote! {
+        impl #name {
+            fn get_property(&self, key: &str) -> Option<&'static str> {
+                #trie
+                let key = _key(key.as_bytes())?;
+                match (self, key) {
+                    #( #arms, )*
+                    _ => None,
+                }
+            }
+        }
+    })
+}
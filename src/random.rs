@@ -0,0 +1,135 @@
+use proc_macro2::{Literal, TokenStream};
+use quote::quote;
+
+use crate::attr::{Enum, ErrorList};
+use crate::iter::{checked_variants, transmute_closure, IterImpl};
+
+/// Returns an expression which uses `rng` to select a variant uniformly at random, erroring if
+/// there are no variants to select.
+///
+/// If `checked` is `Some`, each sampled discriminant is validated against it in debug builds.
+fn uniform(imp: &IterImpl, checked: Option<&[TokenStream]>) -> Result<TokenStream, ErrorList> {
+    let body = match imp {
+        IterImpl::Empty => bail_list!(NoVariants, "At least one variant must not be skipped"),
+
+        IterImpl::Range { repr, range } => {
+            let start = Literal::i128_unsuffixed(range.start);
+            let end = Literal::i128_unsuffixed(range.end);
+            let transmute = transmute_closure(repr, checked, "Random");
+            quote! {
+                let start: #repr = #start;
+                let end: #repr = #end;
+                let discrim: #repr = rng.gen_range(start..end);
+                (#transmute)(discrim)
+            }
+        }
+
+        IterImpl::RangeInclusive { repr, range } => {
+            let start = Literal::i128_unsuffixed(*range.start());
+            let end = Literal::i128_unsuffixed(*range.end());
+            let transmute = transmute_closure(repr, checked, "Random");
+            quote! {
+                let start: #repr = #start;
+                let end: #repr = #end;
+                let discrim: #repr = rng.gen_range(start..=end);
+                (#transmute)(discrim)
+            }
+        }
+
+        IterImpl::Slice(variants) => {
+            let len = Literal::usize_unsuffixed(variants.len());
+            let arms = variants.iter()
+                .enumerate()
+                .map(|(i, v)| {
+                    let i = Literal::usize_unsuffixed(i);
+                    quote!(#i => #v)
+                });
+
+            quote! {
+                match rng.gen_range(0..#len) {
+                    #( #arms, )*
+                    _ => unreachable!(),
+                }
+            }
+        }
+    };
+
+    Ok(body)
+}
+
+/// Returns a `sample_weighted` method which selects each variant with a probability proportional
+/// to its weight, or nothing if no variant has an explicit weight.
+///
+/// Variants without an explicit weight have a weight of one.
+fn weighted(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let name = input.name;
+    let variants: Vec<_> = input.variants.iter()
+        .filter(|(_, attrs)| !attrs.skip)
+        .collect();
+
+    if variants.iter().all(|(_, attrs)| attrs.weight.is_none()) {
+        return Ok(TokenStream::new());
+    }
+
+    // The cumulative weight of each variant, omitting those which can never be selected.
+    let mut total = 0u64;
+    let mut cumulative = vec![];
+    let mut selected = vec![];
+    for (v, attrs) in variants {
+        let weight = attrs.weight.unwrap_or(1);
+        if weight == 0 {
+            continue;
+        }
+
+        total = match total.checked_add(weight) {
+            Some(total) => total,
+            None => bail_list!(InvalidTotalWeight, "The total weight of all variants overflows a `u64`"),
+        };
+
+        cumulative.push(total);
+        selected.push(&v.ident);
+    }
+
+    if total == 0 {
+        bail_list!(InvalidTotalWeight, "At least one variant must have a non-zero weight");
+    }
+
+    let len = Literal::usize_unsuffixed(cumulative.len());
+    let arms = selected.iter()
+        .enumerate()
+        .map(|(i, vident)| {
+            let i = Literal::usize_unsuffixed(i);
+            quote!(#i => #name::#vident)
+        });
+
+    Ok(quote! {
+        impl #name {
+            fn sample_weighted<R: ::rand::Rng + ?Sized>(rng: &mut R) -> Self {
+                const CUMULATIVE: [u64; #len] = [#( #cumulative ),*];
+
+                let x = rng.gen_range(0..#total);
+                match CUMULATIVE.partition_point(|&c| c <= x) {
+                    #( #arms, )*
+                    _ => unreachable!(),
+                }
+            }
+        }
+    })
+}
+
+pub fn derive(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let name = input.name;
+    let checked = checked_variants(input, false);
+    let body = uniform(&IterImpl::for_enum(input)?, checked.as_deref())?;
+    let weighted = weighted(input)?;
+
+    Ok(quote! {
+        impl ::rand::distributions::Distribution<#name> for ::rand::distributions::Standard {
+            fn sample<R: ::rand::Rng + ?Sized>(&self, rng: &mut R) -> #name {
+                #body
+            }
+        }
+
+        #weighted
+    })
+}
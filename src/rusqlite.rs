@@ -0,0 +1,119 @@
+use std::convert::TryFrom;
+
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::attr::{Enum, ErrorList, Primitive};
+use crate::conv::FromRepr;
+use crate::from_str::FromStrImpl;
+use crate::name::name_table;
+
+/// Stores each variant as its canonical name in a `TEXT` value.
+fn derive_text(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let imp = FromStrImpl::for_enum(input)?;
+    let lookup = imp.lookup();
+    let name = input.name;
+
+    let arms: Vec<_> = name_table(input)?
+        .into_iter()
+        .zip(input.variants.iter())
+        .map(|((v, s), (_, attrs))| {
+            let vident = &v.ident;
+            match s {
+                Some(s) => quote!(#name::#vident => #s),
+
+                // The catch-all variant is stored as the string it was parsed from.
+                None if attrs.other && v.fields.len() == 1 =>
+                    quote!(#name::#vident(ref s) => ::std::convert::AsRef::<str>::as_ref(s)),
+
+                None => {
+                    let msg = format!("`{}::{}` is skipped and cannot be stored", name, vident);
+                    quote! {
+                        #name::#vident { .. } =>
+                            return Err(::rusqlite::Error::ToSqlConversionFailure(#msg.into()))
+                    }
+                }
+            }
+        })
+        .collect();
+
+    Ok(quote! {
+        impl ::rusqlite::types::ToSql for #name {
+            fn to_sql(&self) -> ::rusqlite::Result<::rusqlite::types::ToSqlOutput<'_>> {
+                let s: &str = match *self {
+                    #( #arms, )*
+                };
+
+                Ok(::rusqlite::types::ToSqlOutput::Borrowed(::rusqlite::types::ValueRef::Text(s.as_bytes())))
+            }
+        }
+
+        impl ::rusqlite::types::FromSql for #name {
+            fn column_result(value: ::rusqlite::types::ValueRef<'_>)
+                -> ::rusqlite::types::FromSqlResult<Self>
+            {
+                let s = value.as_str()?;
+                match #lookup {
+                    Some(v) => Ok(v),
+                    None => Err(::rusqlite::types::FromSqlError::InvalidType),
+                }
+            }
+        }
+    })
+}
+
+/// Stores each variant as its discriminant in an `INTEGER` value.
+fn derive_repr(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let FromRepr { name, repr, assertions, lookup } = FromRepr::for_enum(input)?;
+    let variants = &input.variants;
+
+    // SQLite integers are 64-bit and signed.
+    match Primitive::try_from(&repr) {
+        Ok(Primitive::I8) | Ok(Primitive::I16) | Ok(Primitive::I32) | Ok(Primitive::I64)
+        | Ok(Primitive::U8) | Ok(Primitive::U16) | Ok(Primitive::U32) => {}
+        _ => bail_list!(MissingRepr, "`sql_repr` requires a `#[repr(...)]` which fits in an `i64`"),
+    }
+
+    let arms = variants.iter()
+        .map(|(v, _)| {
+            let vident = &v.ident;
+            quote!(#name::#vident => #name::#vident as #repr)
+        });
+
+    Ok(quote! {
+        #assertions
+
+        impl ::rusqlite::types::ToSql for #name {
+            fn to_sql(&self) -> ::rusqlite::Result<::rusqlite::types::ToSqlOutput<'_>> {
+                let d: #repr = match *self {
+                    #( #arms, )*
+                };
+
+                Ok(::rusqlite::types::ToSqlOutput::Owned(::rusqlite::types::Value::Integer(d.into())))
+            }
+        }
+
+        impl ::rusqlite::types::FromSql for #name {
+            fn column_result(value: ::rusqlite::types::ValueRef<'_>)
+                -> ::rusqlite::types::FromSqlResult<Self>
+            {
+                let i = value.as_i64()?;
+                let d = <#repr as ::std::convert::TryFrom<i64>>::try_from(i)
+                    .map_err(|_| ::rusqlite::types::FromSqlError::OutOfRange(i))?;
+
+                match #lookup {
+                    Some(v) => Ok(v),
+                    None => Err(::rusqlite::types::FromSqlError::InvalidType),
+                }
+            }
+        }
+    })
+}
+
+pub fn derive(input: &Enum) -> Result<TokenStream, ErrorList> {
+    if input.attrs.sql_repr {
+        derive_repr(input)
+    } else {
+        derive_text(input)
+    }
+}
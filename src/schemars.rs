@@ -0,0 +1,31 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::attr::{Enum, ErrorList};
+use crate::from_str::FromStrImpl;
+
+pub fn derive(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let imp = FromStrImpl::for_enum(input)?;
+    let name = &imp.enum_name;
+    let schema_name = name.to_string();
+
+    // A catch-all variant accepts any string, so the schema cannot list them.
+    let schema = if imp.has_other() {
+        quote!(::schemars::json_schema!({ "type": "string" }))
+    } else {
+        let accepted = imp.accepted();
+        quote!(::schemars::json_schema!({ "type": "string", "enum": [#( #accepted ),*] }))
+    };
+
+    Ok(quote! {
+        impl ::schemars::JsonSchema for #name {
+            fn schema_name() -> ::std::borrow::Cow<'static, str> {
+                ::std::borrow::Cow::Borrowed(#schema_name)
+            }
+
+            fn json_schema(_: &mut ::schemars::SchemaGenerator) -> ::schemars::Schema {
+                #schema
+            }
+        }
+    })
+}
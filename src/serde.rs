@@ -0,0 +1,143 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::attr::{Enum, ErrorList};
+use crate::conv::FromRepr;
+use crate::from_str::FromStrImpl;
+use crate::name::name_table;
+
+pub fn derive_deserialize(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let imp = FromStrImpl::for_enum(input)?;
+    let name = &imp.enum_name;
+    let names = &imp.names;
+    let lookup = imp.lookup();
+
+    // With a custom `expecting` message, the names of the variants are not listed in errors.
+    let (expecting, unknown) = match &imp.expecting {
+        Some(e) => (e.clone(), quote!(E::invalid_value(::serde::de::Unexpected::Str(s), &self))),
+        None => (format!("a variant of `{}`", name), quote!(E::unknown_variant(s, &[#( #names ),*]))),
+    };
+
+    Ok(quote! {
+        impl<'de> ::serde::Deserialize<'de> for #name {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                where D: ::serde::Deserializer<'de>,
+            {
+                struct Visitor;
+
+                impl<'de> ::serde::de::Visitor<'de> for Visitor {
+                    type Value = #name;
+
+                    fn expecting(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                        f.write_str(#expecting)
+                    }
+
+                    fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
+                        where E: ::serde::de::Error,
+                    {
+                        match #lookup {
+                            Some(v) => Ok(v),
+                            None => Err(#unknown),
+                        }
+                    }
+
+                    fn visit_bytes<E>(self, b: &[u8]) -> Result<Self::Value, E>
+                        where E: ::serde::de::Error,
+                    {
+                        match ::std::str::from_utf8(b) {
+                            Ok(s) => self.visit_str(s),
+                            Err(_) => Err(E::invalid_value(::serde::de::Unexpected::Bytes(b), &self)),
+                        }
+                    }
+                }
+
+                deserializer.deserialize_str(Visitor)
+            }
+        }
+    })
+}
+
+pub fn derive_serialize(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let name = input.name;
+
+    let arms: Vec<_> = name_table(input)?
+        .into_iter()
+        .zip(input.variants.iter())
+        .map(|((v, s), (_, attrs))| {
+            let vident = &v.ident;
+            match s {
+                Some(s) => quote!(#name::#vident => serializer.serialize_str(#s)),
+
+                // The catch-all variant round-trips as the string it was parsed from.
+                None if attrs.other && v.fields.len() == 1 =>
+                    quote!(#name::#vident(ref s) => ::serde::Serialize::serialize(s, serializer)),
+
+                None => {
+                    let msg = format!("`{}::{}` is skipped and cannot be serialized", name, vident);
+                    quote!(#name::#vident { .. } => Err(::serde::ser::Error::custom(#msg)))
+                }
+            }
+        })
+        .collect();
+
+    Ok(quote! {
+        impl ::serde::Serialize for #name {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+                where S: ::serde::Serializer,
+            {
+                match *self {
+                    #( #arms, )*
+                }
+            }
+        }
+    })
+}
+
+pub fn derive_serialize_repr(input: &Enum) -> Result<TokenStream, ErrorList> {
+    // Parsing `FromRepr` validates the enum exactly as `TryFromRepr` does.
+    let FromRepr { name, repr, assertions, .. } = FromRepr::for_enum(input)?;
+
+    let arms = input.variants.iter()
+        .map(|(v, _)| {
+            let vident = &v.ident;
+            quote!(#name::#vident => #name::#vident as #repr)
+        });
+
+    Ok(quote! {
+        #assertions
+
+        impl ::serde::Serialize for #name {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+                where S: ::serde::Serializer,
+            {
+                let d: #repr = match *self {
+                    #( #arms, )*
+                };
+
+                ::serde::Serialize::serialize(&d, serializer)
+            }
+        }
+    })
+}
+
+pub fn derive_deserialize_repr(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let FromRepr { name, repr, assertions, lookup } = FromRepr::for_enum(input)?;
+
+    let msg = format!("invalid discriminant `{{}}` for `{}`", name);
+
+    Ok(quote! {
+        #assertions
+
+        impl<'de> ::serde::Deserialize<'de> for #name {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                where D: ::serde::Deserializer<'de>,
+            {
+                let d = <#repr as ::serde::Deserialize>::deserialize(deserializer)?;
+                match #lookup {
+                    Some(v) => Ok(v),
+                    None => Err(::serde::de::Error::custom(format_args!(#msg, d))),
+                }
+            }
+        }
+    })
+}
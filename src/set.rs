@@ -0,0 +1,231 @@
+use proc_macro2::{Literal, TokenStream};
+use quote::{format_ident, quote};
+
+use crate::attr::{Enum, ErrorList};
+
+/// Returns the smallest unsigned integer type with at least `n` bits.
+fn bits_repr(n: usize) -> Option<syn::Ident> {
+    let ty = match n {
+        0..=8 => "u8",
+        9..=16 => "u16",
+        17..=32 => "u32",
+        33..=64 => "u64",
+        65..=128 => "u128",
+        _ => return None,
+    };
+
+    Some(format_ident!("{}", ty))
+}
+
+pub fn derive(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let parsed = input;
+    let name = parsed.name;
+    let unskipped = parsed.unskipped_unit_variants()?;
+
+    let repr = match bits_repr(unskipped.len()) {
+        Some(repr) => repr,
+        None => bail_list!(TooManyVariants,
+                           "A set can hold at most 128 variants, but this enum has {}", unskipped.len()),
+    };
+
+    let vis = input.vis;
+    let set = format_ident!("{}Set", name);
+    let len = Literal::usize_unsuffixed(unskipped.len());
+    let all = match unskipped.len() {
+        128 => u128::MAX,
+        n => (1 << n) - 1,
+    };
+    let all = Literal::u128_unsuffixed(all);
+
+    let doc = format!("A set of variants of [`{}`], stored as a bitset.", name);
+
+    let bit_arms = unskipped.iter()
+        .enumerate()
+        .map(|(i, vident)| {
+            let i = Literal::usize_unsuffixed(i);
+            quote!(#name::#vident => 1 << #i)
+        });
+
+    let variant_arms = unskipped.iter()
+        .enumerate()
+        .map(|(i, vident)| {
+            let i = Literal::usize_unsuffixed(i);
+            quote!(#i => #name::#vident)
+        });
+
+    let skipped_arms = parsed.variants.iter()
+        .filter(|(_, attrs)| attrs.skip)
+        .map(|(v, _)| {
+            let vident = &v.ident;
+            let msg = format!("`{}::{}` is skipped and cannot be a member of a set", name, vident);
+            quote!(#name::#vident { .. } => panic!(#msg))
+        });
+
+    let ops = [
+        (quote!(BitOr), quote!(bitor), quote!(BitOrAssign), quote!(bitor_assign), quote!(union)),
+        (quote!(BitAnd), quote!(bitand), quote!(BitAndAssign), quote!(bitand_assign), quote!(intersection)),
+        (quote!(BitXor), quote!(bitxor), quote!(BitXorAssign), quote!(bitxor_assign), quote!(symmetric_difference)),
+        (quote!(Sub), quote!(sub), quote!(SubAssign), quote!(sub_assign), quote!(difference)),
+    ];
+
+    let op_impls = ops.iter()
+        .map(|(op, op_fn, assign, assign_fn, method)| quote! {
+            impl ::std::ops::#op for #set {
+                type Output = Self;
+
+                fn #op_fn(self, other: Self) -> Self {
+                    self.#method(other)
+                }
+            }
+
+            impl ::std::ops::#assign for #set {
+                fn #assign_fn(&mut self, other: Self) {
+                    *self = self.#method(other);
+                }
+            }
+        });
+
+    Ok(quote! {
+        #[doc = #doc]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+        #vis struct #set(#repr);
+
+        impl #set {
+            /// Returns a set containing no variants.
+            pub const fn empty() -> Self {
+                #set(0)
+            }
+
+            /// Returns a set containing every variant.
+            pub const fn all() -> Self {
+                #set(#all)
+            }
+
+            /// Returns a set from its bitwise representation, ignoring any bits which do not
+            /// correspond to a variant.
+            pub const fn from_bits_truncate(bits: #repr) -> Self {
+                #set(bits & #all)
+            }
+
+            /// Returns the bitwise representation of this set.
+            pub const fn bits(&self) -> #repr {
+                self.0
+            }
+
+            fn __bit(v: &#name) -> #repr {
+                match *v {
+                    #( #bit_arms, )*
+                    #( #skipped_arms, )*
+                }
+            }
+
+            fn __variant(i: usize) -> #name {
+                match i {
+                    #( #variant_arms, )*
+                    _ => unreachable!(),
+                }
+            }
+
+            /// Adds a variant to the set, returning `true` if it was not already present.
+            pub fn insert(&mut self, v: #name) -> bool {
+                let bit = Self::__bit(&v);
+                let absent = self.0 & bit == 0;
+                self.0 |= bit;
+                absent
+            }
+
+            /// Removes a variant from the set, returning `true` if it was present.
+            pub fn remove(&mut self, v: #name) -> bool {
+                let bit = Self::__bit(&v);
+                let present = self.0 & bit != 0;
+                self.0 &= !bit;
+                present
+            }
+
+            /// Returns `true` if the set contains the given variant.
+            pub fn contains(&self, v: #name) -> bool {
+                self.0 & Self::__bit(&v) != 0
+            }
+
+            /// Returns the number of variants in the set.
+            pub const fn len(&self) -> usize {
+                self.0.count_ones() as usize
+            }
+
+            /// Returns `true` if the set contains no variants.
+            pub const fn is_empty(&self) -> bool {
+                self.0 == 0
+            }
+
+            /// Returns the variants in either `self` or `other`.
+            pub const fn union(self, other: Self) -> Self {
+                #set(self.0 | other.0)
+            }
+
+            /// Returns the variants in both `self` and `other`.
+            pub const fn intersection(self, other: Self) -> Self {
+                #set(self.0 & other.0)
+            }
+
+            /// Returns the variants in `self` but not in `other`.
+            pub const fn difference(self, other: Self) -> Self {
+                #set(self.0 & !other.0)
+            }
+
+            /// Returns the variants in exactly one of `self` and `other`.
+            pub const fn symmetric_difference(self, other: Self) -> Self {
+                #set(self.0 ^ other.0)
+            }
+
+            /// Returns the variants not in `self`.
+            pub const fn complement(self) -> Self {
+                #set(!self.0 & #all)
+            }
+
+            /// Returns `true` if every variant in `self` is also in `other`.
+            pub const fn is_subset(&self, other: &Self) -> bool {
+                self.0 & !other.0 == 0
+            }
+
+            /// Returns an iterator over the variants in the set, in declaration order.
+            pub fn iter(&self) -> impl Iterator<Item = #name> {
+                let bits = self.0;
+                (0..#len)
+                    .filter(move |i| bits & (1 << i) != 0)
+                    .map(Self::__variant)
+            }
+        }
+
+        impl ::std::ops::Not for #set {
+            type Output = Self;
+
+            fn not(self) -> Self {
+                self.complement()
+            }
+        }
+
+        #( #op_impls )*
+
+        impl ::std::iter::FromIterator<#name> for #set {
+            fn from_iter<I: IntoIterator<Item = #name>>(iter: I) -> Self {
+                let mut set = Self::empty();
+                set.extend(iter);
+                set
+            }
+        }
+
+        impl ::std::iter::Extend<#name> for #set {
+            fn extend<I: IntoIterator<Item = #name>>(&mut self, iter: I) {
+                for v in iter {
+                    self.insert(v);
+                }
+            }
+        }
+
+        impl ::std::convert::From<#name> for #set {
+            fn from(v: #name) -> Self {
+                #set(Self::__bit(&v))
+            }
+        }
+    })
+}
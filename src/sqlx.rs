@@ -0,0 +1,90 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::attr::{Enum, ErrorList};
+use crate::from_str::FromStrImpl;
+use crate::name::name_table;
+
+pub fn derive(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let imp = FromStrImpl::for_enum(input)?;
+    let lookup = imp.lookup();
+    let name = input.name;
+
+    let mut encodes_string = false;
+    let arms: Vec<_> = name_table(input)?
+        .into_iter()
+        .zip(input.variants.iter())
+        .map(|((v, s), (_, attrs))| {
+            let vident = &v.ident;
+            match s {
+                Some(s) => quote!(#name::#vident => <&'q str as ::sqlx::Encode<'q, DB>>::encode(#s, buf)),
+
+                // The catch-all variant is stored as the string it was parsed from. It does not
+                // live as long as `'q`, so it must be copied.
+                None if attrs.other && v.fields.len() == 1 => {
+                    encodes_string = true;
+                    quote! {
+                        #name::#vident(ref s) => {
+                            let s = ::std::convert::AsRef::<str>::as_ref(s).to_owned();
+                            <::std::string::String as ::sqlx::Encode<'q, DB>>::encode(s, buf)
+                        }
+                    }
+                }
+
+                None => {
+                    let msg = format!("`{}::{}` is skipped and cannot be encoded", name, vident);
+                    quote!(#name::#vident { .. } => Err(#msg.into()))
+                }
+            }
+        })
+        .collect();
+
+    let string_bound = if encodes_string {
+        quote!(::std::string::String: ::sqlx::Encode<'q, DB>,)
+    } else {
+        TokenStream::new()
+    };
+
+    let msg = format!("invalid value {{:?}} for `{}`", name);
+
+    Ok(quote! {
+        impl<DB: ::sqlx::Database> ::sqlx::Type<DB> for #name
+            where str: ::sqlx::Type<DB>,
+        {
+            fn type_info() -> DB::TypeInfo {
+                <str as ::sqlx::Type<DB>>::type_info()
+            }
+
+            fn compatible(ty: &DB::TypeInfo) -> bool {
+                <str as ::sqlx::Type<DB>>::compatible(ty)
+            }
+        }
+
+        impl<'q, DB: ::sqlx::Database> ::sqlx::Encode<'q, DB> for #name
+            where &'q str: ::sqlx::Encode<'q, DB>,
+                  #string_bound
+        {
+            fn encode_by_ref(&self, buf: &mut <DB as ::sqlx::Database>::ArgumentBuffer<'q>)
+                -> Result<::sqlx::encode::IsNull, ::sqlx::error::BoxDynError>
+            {
+                match *self {
+                    #( #arms, )*
+                }
+            }
+        }
+
+        impl<'r, DB: ::sqlx::Database> ::sqlx::Decode<'r, DB> for #name
+            where &'r str: ::sqlx::Decode<'r, DB>,
+        {
+            fn decode(value: <DB as ::sqlx::Database>::ValueRef<'r>)
+                -> Result<Self, ::sqlx::error::BoxDynError>
+            {
+                let s = <&'r str as ::sqlx::Decode<'r, DB>>::decode(value)?;
+                match #lookup {
+                    Some(v) => Ok(v),
+                    None => Err(format!(#msg, s).into()),
+                }
+            }
+        }
+    })
+}
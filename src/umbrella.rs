@@ -0,0 +1,44 @@
+use proc_macro2::TokenStream;
+
+use crate::attr::{Enum, ErrorList};
+use crate::{conv, from_str, iter, name};
+
+type DeriveFn = fn(&Enum) -> Result<TokenStream, ErrorList>;
+
+/// The derives which can be selected with `#[enumeration(derive(...))]`, and the function which
+/// implements each one.
+const DERIVES: &[(&str, DeriveFn)] = &[
+    ("FromStr", from_str::derive),
+    ("Display", name::derive_display),
+    ("IterVariants", iter::derive),
+    ("TryFromRepr", conv::derive_try_from_repr),
+    ("ReprFrom", conv::derive_repr_from),
+];
+
+pub fn derive(input: &syn::DeriveInput) -> Result<TokenStream, ErrorList> {
+    let mut parsed = Enum::parse(input)?;
+    let derives = std::mem::take(&mut parsed.attrs.derives);
+
+    let mut errors = ErrorList::new();
+    let mut tokens = TokenStream::new();
+    for ident in &derives {
+        let derive = DERIVES.iter().find(|(name, _)| ident == name);
+        match derive {
+            // A variant can be skipped by every derive with `skip(Enum)`, or by just one of them.
+            Some((name, f)) => match f(parsed.for_derive(&["Enum", name])) {
+                Ok(t) => tokens.extend(t),
+                Err(mut e) => errors.append(&mut e),
+            },
+
+            None => errors.push_back(format_diag!(UnknownAttribute, "`{}` cannot be selected with \"derive\"", ident)
+                .with_span(ident.span())),
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    tokens.extend(parsed.attrs.aliases_file_dependency());
+    Ok(tokens)
+}
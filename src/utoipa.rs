@@ -0,0 +1,47 @@
+use proc_macro2::{Literal, TokenStream};
+use quote::quote;
+
+use crate::attr::{Enum, ErrorList};
+use crate::from_str::FromStrImpl;
+
+pub fn derive(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let imp = FromStrImpl::for_enum(input)?;
+    let name = &imp.enum_name;
+    let schema_name = name.to_string();
+
+    // Clients should send the canonical names, so aliases are only listed as examples. A
+    // catch-all variant accepts any string, so no values can be listed at all.
+    let (values, examples): (Option<&[String]>, Vec<_>) = if imp.has_other() {
+        (None, imp.accepted())
+    } else {
+        (Some(&imp.names), imp.aliases().collect())
+    };
+
+    let values = match values {
+        Some(names) => {
+            let len = Literal::usize_unsuffixed(names.len());
+            quote!(Some::<[&str; #len]>([#( #names ),*]))
+        }
+        None => quote!(None::<[&str; 0]>),
+    };
+
+    let num_examples = Literal::usize_unsuffixed(examples.len());
+
+    Ok(quote! {
+        impl ::utoipa::PartialSchema for #name {
+            fn schema() -> ::utoipa::openapi::RefOr<::utoipa::openapi::schema::Schema> {
+                ::utoipa::openapi::ObjectBuilder::new()
+                    .schema_type(::utoipa::openapi::schema::Type::String)
+                    .enum_values(#values)
+                    .examples::<[&str; #num_examples], &str>([#( #examples ),*])
+                    .into()
+            }
+        }
+
+        impl ::utoipa::ToSchema for #name {
+            fn name() -> ::std::borrow::Cow<'static, str> {
+                ::std::borrow::Cow::Borrowed(#schema_name)
+            }
+        }
+    })
+}
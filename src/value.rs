@@ -0,0 +1,88 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Lit;
+
+use crate::attr::{Enum, ErrorList};
+
+/// Returns the type of a literal, or `None` if it cannot be inferred from the literal alone.
+fn lit_type(lit: &Lit) -> Option<TokenStream> {
+    let ty = match lit {
+        Lit::Str(_) => quote!(&'static str),
+        Lit::ByteStr(_) => quote!(&'static [u8]),
+        Lit::Byte(_) => quote!(u8),
+        Lit::Char(_) => quote!(char),
+        Lit::Bool(_) => quote!(bool),
+        Lit::Int(i) if !i.suffix().is_empty() => {
+            let suffix = syn::Ident::new(i.suffix(), i.span());
+            quote!(#suffix)
+        }
+        Lit::Float(f) if !f.suffix().is_empty() => {
+            let suffix = syn::Ident::new(f.suffix(), f.span());
+            quote!(#suffix)
+        }
+        _ => return None,
+    };
+
+    Some(ty)
+}
+
+pub fn derive(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let Enum { name, variants, .. } = input;
+
+    let mut errors = ErrorList::new();
+    let mut ty: Option<(TokenStream, &syn::Ident)> = None;
+    let mut arms = vec![];
+    for (v, attrs) in variants.iter() {
+        let vident = &v.ident;
+        let lit = match &attrs.value {
+            Some(lit) => lit,
+            None => {
+                errors.push_back(format_diag!(MissingValue, "Variant `{}` has no \"value\"", vident)
+                    .with_span(vident.span()));
+                continue;
+            }
+        };
+
+        let lit_ty = match lit_type(lit) {
+            Some(t) => t,
+            None => {
+                errors.push_back(format_diag!(MalformedAttribute,
+                                              "Numeric \"value\" must have a type suffix (e.g. `404u16`)")
+                    .with_span(lit.span()));
+                continue;
+            }
+        };
+
+        match &ty {
+            Some((expected, first)) if expected.to_string() != lit_ty.to_string() => {
+                errors.push_back(format_diag!(MismatchedValueTypes,
+                                              "Value of `{}` is a `{}`, but value of `{}` is a `{}`",
+                                              vident, lit_ty, first, expected)
+                    .with_span(lit.span()));
+                continue;
+            }
+
+            Some(_) => {}
+            None => ty = Some((lit_ty, vident)),
+        }
+
+        arms.push(quote!(#name::#vident { .. } => #lit));
+    }
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    // An empty enum has no values, so the type is irrelevant.
+    let ty = ty.map(|(ty, _)| ty).unwrap_or_else(|| quote!(()));
+
+    Ok(quote! {
+        impl #name {
+            const fn value(&self) -> #ty {
+                match *self {
+                    #( #arms, )*
+                }
+            }
+        }
+    })
+}
@@ -0,0 +1,68 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+use crate::attr::{Enum, ErrorList, RenameRule};
+
+pub fn derive(input: &Enum) -> Result<TokenStream, ErrorList> {
+    let Enum { name, variants, .. } = input;
+
+    let vis = input.vis;
+    let visitor = format_ident!("{}Visitor", name);
+
+    let mut methods = vec![];
+    let mut arms = vec![];
+    for (v, _) in variants.iter() {
+        let vident = &v.ident;
+        let method = format_ident!("visit_{}", RenameRule::snake_case().apply_to_variant(&vident.to_string()));
+
+        // Fields are passed by reference, named after the field or `_0`, `_1`, ... for tuple
+        // variants.
+        let members: Vec<_> = v.fields.iter()
+            .enumerate()
+            .map(|(i, f)| match &f.ident {
+                Some(ident) => syn::Member::Named(ident.clone()),
+                None => syn::Member::Unnamed(i.into()),
+            })
+            .collect();
+        let params: Vec<_> = v.fields.iter()
+            .enumerate()
+            .map(|(i, f)| f.ident.clone().unwrap_or_else(|| format_ident!("_{}", i)))
+            .collect();
+        let tys = v.fields.iter().map(|f| &f.ty);
+
+        let doc = format!("Visits [`{}::{}`].", name, vident);
+        methods.push(quote! {
+            #[doc = #doc]
+            fn #method(&mut self, #( #params: &#tys ),*) -> Self::Output;
+        });
+
+        // Bind fields positionally so a field cannot shadow `visitor`.
+        let bindings: Vec<_> = (0..members.len()).map(|i| format_ident!("_{}", i)).collect();
+        arms.push(quote! {
+            #name::#vident { #( #members: ref #bindings ),* } => visitor.#method(#( #bindings ),*)
+        });
+    }
+
+    let doc = format!("A visitor with one method for each variant of [`{}`].", name);
+
+    Ok(quote! {
+        // Fields are passed as `&T` even when a more general type (e.g. `&[T]`) would do.
+        #[doc = #doc]
+        #[allow(clippy::ptr_arg)]
+        #vis trait #visitor {
+            /// The value returned by each method.
+            type Output;
+
+            #( #methods )*
+        }
+
+        impl #name {
+            /// Calls the method of `visitor` which corresponds to this variant.
+            pub fn accept<V: #visitor>(&self, visitor: &mut V) -> V::Output {
+                match *self {
+                    #( #arms, )*
+                }
+            }
+        }
+    })
+}
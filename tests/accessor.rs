@@ -0,0 +1,46 @@
+use enum_utils::EnumTryAs;
+
+#[derive(Debug, PartialEq, EnumTryAs)]
+enum Payload {
+    Empty,
+    Bytes(Vec<u8>),
+    Pair(u8, char),
+    Named { id: u32 },
+}
+
+#[test]
+fn try_as() {
+    let mut p = Payload::Bytes(vec![1]);
+    assert_eq!(p.try_as_bytes(), Some(&vec![1]));
+    assert_eq!(p.try_as_pair(), None);
+
+    p.try_as_bytes_mut().unwrap().push(2);
+    assert_eq!(p.try_into_bytes(), Ok(vec![1, 2]));
+
+    let mut p = Payload::Pair(1, 'a');
+    if let Some((n, _)) = p.try_as_pair_mut() {
+        *n += 1;
+    }
+    assert_eq!(p.try_as_pair(), Some((&2, &'a')));
+
+    assert_eq!(Payload::Named { id: 7 }.try_into_named(), Ok(7));
+    assert_eq!(Payload::Empty.try_into_named(), Err(Payload::Empty));
+}
+
+#[derive(Debug, EnumTryAs)]
+enum Only {
+    Value(String),
+}
+
+#[test]
+fn try_as_single_variant() {
+    const fn len(o: &Only) -> usize {
+        match o.try_as_value() {
+            Some(s) => s.len(),
+            None => 0,
+        }
+    }
+
+    assert_eq!(len(&Only::Value("abc".to_owned())), 3);
+    assert_eq!(Only::Value(String::new()).try_into_value().unwrap(), "");
+}
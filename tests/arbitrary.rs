@@ -0,0 +1,143 @@
+#![allow(unused)]
+
+#[cfg(feature = "proptest")]
+mod proptest_arbitrary {
+    use proptest::prelude::*;
+
+    #[derive(Debug, Clone, PartialEq, enum_utils::ProptestArbitrary)]
+    enum Event {
+        Start,
+        Key(char),
+        Move { x: i16, y: i16 },
+        #[enumeration(skip)]
+        Internal(std::rc::Rc<()>),
+    }
+
+    proptest! {
+        #[test]
+        fn never_skipped(event in any::<Event>()) {
+            prop_assert!(!matches!(event, Event::Internal(_)));
+        }
+    }
+
+    #[derive(Debug, Clone, enum_utils::ProptestArbitrary)]
+    enum Single {
+        Only(u8),
+    }
+
+    proptest! {
+        #[test]
+        fn single(_v in any::<Single>()) {}
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary_bytes {
+    use arbitrary::{Arbitrary, Unstructured};
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, enum_utils::Arbitrary)]
+    #[repr(u8)]
+    enum Contiguous {
+        A = 3,
+        B,
+        C,
+    }
+
+    #[derive(Debug, PartialEq, Eq, enum_utils::Arbitrary)]
+    enum Sparse {
+        A = 1,
+        #[enumeration(skip)]
+        B = 2,
+        C = 4,
+    }
+
+    #[derive(Debug, PartialEq, Eq, enum_utils::Arbitrary)]
+    enum Data {
+        Unit,
+        Tuple(u8, bool),
+        Named { x: u8 },
+    }
+
+    #[test]
+    fn fieldless() {
+        let data = [0u8; 64];
+        let mut u = Unstructured::new(&data);
+        assert_eq!(Contiguous::arbitrary(&mut u), Ok(Contiguous::A));
+
+        for b in 0..=255u8 {
+            let data = [b; 8];
+            let v = Contiguous::arbitrary(&mut Unstructured::new(&data)).unwrap();
+            assert!([Contiguous::A, Contiguous::B, Contiguous::C].contains(&v));
+
+            let v = Sparse::arbitrary(&mut Unstructured::new(&data)).unwrap();
+            assert_ne!(v, Sparse::B);
+        }
+    }
+
+    #[derive(Debug, PartialEq, Eq, enum_utils::Arbitrary)]
+    #[enumeration(debug_checks)]
+    #[repr(u16)]
+    enum Checked {
+        A = 500,
+        B,
+        C,
+    }
+
+    #[test]
+    fn debug_checks() {
+        for b in 0..=255u8 {
+            let data = [b; 8];
+            let v = Checked::arbitrary(&mut Unstructured::new(&data)).unwrap();
+            assert!([Checked::A, Checked::B, Checked::C].contains(&v));
+        }
+    }
+
+    #[test]
+    fn fields() {
+        let mut u = Unstructured::new(&[1, 7, 1, 2, 9]);
+        assert_eq!(Data::arbitrary(&mut u), Ok(Data::Tuple(7, true)));
+        assert_eq!(Data::arbitrary(&mut u), Ok(Data::Named { x: 9 }));
+    }
+}
+
+#[cfg(feature = "quickcheck")]
+mod quickcheck_arbitrary {
+    use quickcheck::{Arbitrary, Gen};
+
+    #[derive(Debug, Clone, PartialEq, enum_utils::QuickcheckArbitrary)]
+    enum Token {
+        Eof,
+        Num(u32),
+        #[enumeration(skip)]
+        Error,
+        Ident { name: String },
+        Semi,
+    }
+
+    #[test]
+    fn arbitrary() {
+        let mut g = Gen::new(10);
+        for _ in 0..100 {
+            assert_ne!(Token::arbitrary(&mut g), Token::Error);
+        }
+    }
+
+    #[test]
+    fn shrink() {
+        assert_eq!(Token::Eof.shrink().count(), 0);
+        assert_eq!(Token::Num(3).shrink().collect::<Vec<_>>(), vec![Token::Eof]);
+        assert_eq!(Token::Semi.shrink().collect::<Vec<_>>(), vec![Token::Eof]);
+        assert_eq!(Token::Error.shrink().count(), 0);
+    }
+
+    #[derive(Debug, Clone, PartialEq, enum_utils::QuickcheckArbitrary)]
+    enum NoSkip {
+        A,
+        B,
+    }
+
+    #[test]
+    fn shrink_no_skip() {
+        assert_eq!(NoSkip::B.shrink().collect::<Vec<_>>(), vec![NoSkip::A]);
+    }
+}
@@ -0,0 +1,26 @@
+#![cfg(feature = "bytemuck")]
+
+use bytemuck::checked;
+
+const SEVEN: i16 = 7;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, enum_utils::CheckedBitPattern)]
+#[repr(i16)]
+enum Tag {
+    Neg = -300,
+    Zero = 0,
+    Seven = SEVEN,
+}
+
+#[test]
+fn checked_bit_pattern() {
+    let raw: [i16; 3] = [7, -300, 0];
+    let tags: &[Tag] = checked::cast_slice(&raw);
+    assert_eq!(tags, &[Tag::Seven, Tag::Neg, Tag::Zero]);
+
+    assert_eq!(checked::try_cast::<i16, Tag>(1), Err(checked::CheckedCastError::InvalidBitPattern));
+    assert_eq!(checked::try_pod_read_unaligned::<Tag>(&(-300i16).to_ne_bytes()), Ok(Tag::Neg));
+
+    let back: &[i16] = bytemuck::cast_slice(tags);
+    assert_eq!(back, &raw);
+}
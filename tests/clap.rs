@@ -0,0 +1,32 @@
+#![cfg(feature = "clap")]
+
+use clap::Parser;
+
+#[derive(Debug, Clone, PartialEq, enum_utils::ValueEnum)]
+#[enumeration(rename_all = "kebab-case")]
+enum Backend {
+    /// The default backend.
+    Native,
+    #[enumeration(alias = "wasm")]
+    WebAssembly,
+    #[enumeration(skip)]
+    #[allow(unused)]
+    Custom(String),
+}
+
+#[derive(Parser)]
+struct Args {
+    #[arg(long, value_enum)]
+    backend: Backend,
+}
+
+#[test]
+fn parse() {
+    let args = Args::try_parse_from(["prog", "--backend", "web-assembly"]).unwrap();
+    assert_eq!(args.backend, Backend::WebAssembly);
+
+    let args = Args::try_parse_from(["prog", "--backend", "wasm"]).unwrap();
+    assert_eq!(args.backend, Backend::WebAssembly);
+
+    assert!(Args::try_parse_from(["prog", "--backend", "custom"]).is_err());
+}
@@ -0,0 +1,222 @@
+use std::convert::TryFrom;
+
+use enum_utils::{FromBool, FromEnum, ProtoEnum, ReprFrom, TryFromChar, TryFromRepr};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ReprFrom, TryFromRepr)]
+#[repr(i8)]
+enum Negative {
+    MinusTwo = -2,
+    MinusOne,
+    Zero,
+    Max = 127,
+}
+
+#[test]
+fn negative() {
+    use self::Negative::*;
+
+    assert_eq!(Ok(MinusTwo), Negative::try_from(-2i8));
+    assert_eq!(Ok(MinusOne), Negative::try_from(-1i8));
+    assert_eq!(Ok(Zero), Negative::try_from(0i8));
+    assert_eq!(Ok(Max), Negative::try_from(127i8));
+    assert_eq!(Err(()), Negative::try_from(1i8));
+    assert_eq!(-2i8, MinusTwo.into());
+}
+
+const BASE: u16 = 0x100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ReprFrom, TryFromRepr)]
+#[repr(u16)]
+enum NonLiteral {
+    A = BASE,
+    B = BASE << 1,
+}
+
+#[test]
+fn non_literal() {
+    use self::NonLiteral::*;
+
+    assert_eq!(Ok(A), NonLiteral::try_from(0x100u16));
+    assert_eq!(Ok(B), NonLiteral::try_from(0x200u16));
+    assert_eq!(Err(()), NonLiteral::try_from(0u16));
+    assert_eq!(0x200u16, B.into());
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, enum_utils::TryFromFloat)]
+#[repr(i64)]
+#[allow(unused)]
+enum Inexact {
+    Small = -3,
+    /// 2^53 + 1 is not representable by an `f64`.
+    Large = 9_007_199_254_740_993,
+    /// 2^63 - 1 rounds up to 2^63 when converted to an `f64`.
+    Max = 9_223_372_036_854_775_807,
+}
+
+#[test]
+fn float() {
+    use self::Inexact::*;
+
+    assert_eq!(Ok(Small), Inexact::try_from(-3.0f64));
+    assert_eq!(Ok(Small), Inexact::try_from(-3.0f32));
+    assert_eq!(Err(()), Inexact::try_from(-3.5f64));
+    assert_eq!(Err(()), Inexact::try_from(9_007_199_254_740_992.0f64));
+    assert_eq!(Err(()), Inexact::try_from(9_223_372_036_854_775_808.0f64));
+    assert_eq!(Err(()), Inexact::try_from(f64::INFINITY));
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ReprFrom, TryFromRepr)]
+#[enumeration(assert_discriminants(Low = -1, High = 0x100))]
+#[repr(i16)]
+enum Asserted {
+    Low = -1,
+    High = 0x100,
+}
+
+#[test]
+fn assert_discriminants() {
+    assert_eq!(Ok(Asserted::Low), Asserted::try_from(-1i16));
+    assert_eq!(0x100i16, Asserted::High.into());
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, TryFromChar)]
+enum Piece {
+    #[enumeration(char = 'K')]
+    King,
+    #[enumeration(char = 'N')]
+    Knight,
+    #[enumeration(char = '♛')]
+    Queen,
+    #[enumeration(skip)]
+    Empty,
+}
+
+#[test]
+fn char() {
+    assert_eq!(Ok(Piece::Knight), Piece::try_from('N'));
+    assert_eq!(Ok(Piece::Queen), Piece::try_from('♛'));
+    assert_eq!(Err(()), Piece::try_from('k'));
+    assert_eq!('K', char::from(Piece::King));
+}
+
+#[test]
+#[should_panic(expected = "`Piece::Empty` is skipped")]
+fn char_skipped() {
+    let _ = char::from(Piece::Empty);
+}
+
+mod wire {
+    #[allow(unused)]
+    pub enum Color {
+        Red,
+        Green,
+        Blue,
+    }
+}
+
+mod legacy {
+    #[allow(unused)]
+    pub enum Color {
+        Blue,
+        Red,
+        Green,
+    }
+}
+
+#[derive(Debug, PartialEq, FromEnum)]
+#[enumeration(from = "wire::Color", from = "legacy::Color")]
+enum Color {
+    Red,
+    #[enumeration(from_variant = "Green")]
+    Lime,
+    Blue,
+    #[enumeration(skip)]
+    #[allow(unused)]
+    Custom(u32),
+}
+
+#[test]
+fn from_enum() {
+    assert_eq!(Color::Red, wire::Color::Red.into());
+    assert_eq!(Color::Lime, wire::Color::Green.into());
+    assert_eq!(Color::Blue, wire::Color::Blue.into());
+    assert_eq!(Color::Lime, legacy::Color::Green.into());
+}
+
+#[derive(Debug, PartialEq, FromEnum)]
+#[enumeration(try_from = "Color")]
+enum Primary {
+    Red,
+    #[enumeration(from_variant = "Lime")]
+    Green,
+    Blue,
+}
+
+#[derive(Debug, PartialEq, FromEnum)]
+#[enumeration(try_from = "wire::Color")]
+enum Total {
+    Red,
+    Green,
+    Blue,
+}
+
+#[test]
+fn try_from_enum() {
+    assert_eq!(Ok(Primary::Green), Primary::try_from(Color::Lime));
+    assert_eq!(Ok(Primary::Blue), Primary::try_from(Color::Blue));
+    assert_eq!(Err(Color::Custom(7)), Primary::try_from(Color::Custom(7)));
+
+    assert!(matches!(Total::try_from(wire::Color::Red), Ok(Total::Red)));
+}
+
+#[derive(Debug, PartialEq, ProtoEnum)]
+#[repr(i64)]
+enum Wide {
+    Min = i32::MIN as i64,
+    Zero = 0,
+    Max = 0x7fff_ffff,
+}
+
+#[test]
+fn proto_enum() {
+    assert_eq!(Wide::from_i32(i32::MIN), Some(Wide::Min));
+    assert_eq!(Wide::from_i32(0), Some(Wide::Zero));
+    assert_eq!(Wide::from_i32(1), None);
+    assert_eq!(Wide::Max.as_i32(), i32::MAX);
+
+    const MIN: i32 = Wide::Min.as_i32();
+    assert_eq!(MIN, i32::MIN);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, FromBool)]
+enum Switch {
+    Off,
+    On,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, FromBool)]
+enum Answer {
+    #[enumeration(true_variant)]
+    Yes,
+    #[enumeration(false_variant)]
+    No,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, FromBool)]
+enum Flipped {
+    Keep,
+    #[enumeration(false_variant)]
+    Drop,
+}
+
+#[test]
+fn from_bool() {
+    assert_eq!(Switch::from(false), Switch::Off);
+    assert!(bool::from(Switch::On));
+
+    assert_eq!(Answer::from(true), Answer::Yes);
+    assert!(!bool::from(Answer::No));
+
+    assert_eq!(Flipped::from(true), Flipped::Keep);
+    assert!(!bool::from(Flipped::Drop));
+}
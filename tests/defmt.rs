@@ -0,0 +1,24 @@
+#![cfg(feature = "defmt")]
+
+use defmt::export::{fetch_bytes, fetch_string_index, make_formatter};
+use defmt::Format;
+
+#[derive(enum_utils::Format)]
+enum State {
+    Idle,
+    #[enumeration(rename = "{busy}")]
+    Busy,
+    #[enumeration(skip)]
+    #[allow(unused)]
+    Stopped(u32),
+}
+
+#[test]
+fn interned() {
+    // Interning is mocked on the host, so each `write!` emits the next index of the interner.
+    for v in &[State::Idle, State::Busy, State::Stopped(0)] {
+        let index = fetch_string_index();
+        v.format(make_formatter());
+        assert_eq!(fetch_bytes(), index.to_le_bytes());
+    }
+}
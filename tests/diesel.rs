@@ -0,0 +1,60 @@
+#![cfg(feature = "diesel")]
+
+use diesel::deserialize::FromSqlRow;
+use diesel::expression::AsExpression;
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Text};
+
+#[derive(Debug, PartialEq, AsExpression, FromSqlRow, enum_utils::DieselSql)]
+#[diesel(sql_type = Text)]
+#[enumeration(case_insensitive)]
+enum Kind {
+    #[enumeration(rename = "file")]
+    File,
+    #[enumeration(rename = "dir", alias = "directory")]
+    Dir,
+    #[enumeration(other)]
+    Other(String),
+}
+
+#[derive(Debug, PartialEq, AsExpression, FromSqlRow, enum_utils::DieselSql)]
+#[diesel(sql_type = BigInt)]
+#[enumeration(sql_repr)]
+#[repr(i64)]
+enum Big {
+    Small = -1,
+    Large = 1 << 40,
+}
+
+fn conn() -> SqliteConnection {
+    SqliteConnection::establish(":memory:").unwrap()
+}
+
+#[test]
+fn text() {
+    let mut conn = conn();
+
+    let kind = diesel::select(Kind::Dir.into_sql::<Text>()).get_result::<Kind>(&mut conn).unwrap();
+    assert_eq!(kind, Kind::Dir);
+
+    let kind = diesel::select("DIRECTORY".into_sql::<Text>()).get_result::<Kind>(&mut conn).unwrap();
+    assert_eq!(kind, Kind::Dir);
+
+    let raw = diesel::select(Kind::Other("link".to_owned()).into_sql::<Text>())
+        .get_result::<String>(&mut conn)
+        .unwrap();
+    assert_eq!(raw, "link");
+}
+
+#[test]
+fn repr() {
+    let mut conn = conn();
+
+    let big = diesel::select(Big::Large.into_sql::<BigInt>()).get_result::<Big>(&mut conn).unwrap();
+    assert_eq!(big, Big::Large);
+
+    let raw = diesel::select(Big::Small.into_sql::<BigInt>()).get_result::<i64>(&mut conn).unwrap();
+    assert_eq!(raw, -1);
+
+    assert!(diesel::select(2i64.into_sql::<BigInt>()).get_result::<Big>(&mut conn).is_err());
+}
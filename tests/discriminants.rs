@@ -0,0 +1,49 @@
+#[allow(unused)]
+#[derive(enum_utils::EnumDiscriminants)]
+#[enumeration(kind_derive(Debug, Clone, Copy, PartialEq, Eq))]
+enum Shape {
+    Circle(f64),
+    Rect { w: f64, h: f64 },
+    Empty,
+}
+
+#[test]
+fn kind() {
+    assert_eq!(Shape::Circle(1.0).kind(), ShapeKind::Circle);
+    assert_eq!(Shape::Rect { w: 1.0, h: 2.0 }.kind(), ShapeKind::Rect);
+}
+
+#[derive(enum_utils::EnumDiscriminants)]
+#[enumeration(
+    case_insensitive,
+    kind_name = "Op",
+    kind_derive(Debug, PartialEq, enum_utils::FromStr),
+)]
+enum Instr {
+    #[allow(unused)]
+    Push(i64),
+    #[enumeration(alias = "drop")]
+    Pop,
+}
+
+#[test]
+fn mirror_from_str() {
+    assert_eq!("PUSH".parse(), Ok(Op::Push));
+    assert_eq!("Drop".parse(), Ok(Op::Pop));
+    assert_eq!(Instr::Pop.kind(), Op::Pop);
+}
+
+#[derive(enum_utils::EnumDiscriminants)]
+#[enumeration(rename_all = "snake_case", kind_derive(Debug, serde::Serialize))]
+enum Event {
+    #[allow(unused)]
+    Click(u32),
+    #[enumeration(rename = "key")]
+    KeyPress,
+}
+
+#[test]
+fn foreign_derive() {
+    // The mirror has no derive from this crate, so the `#[enumeration]` attributes are not copied.
+    assert_eq!(serde_json::to_string(&Event::KeyPress.kind()).unwrap(), "\"KeyPress\"");
+}
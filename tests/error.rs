@@ -0,0 +1,58 @@
+use std::error::Error as _;
+use std::fmt;
+
+#[derive(Debug)]
+struct Inner;
+
+impl fmt::Display for Inner {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("inner")
+    }
+}
+
+impl std::error::Error for Inner {}
+
+#[derive(Debug, enum_utils::Error)]
+#[enumeration(rename_all = "snake_case")]
+enum Kind {
+    /// Request timed
+    /// out.
+    TimedOut,
+    Refused,
+    #[enumeration(message = "bad input")]
+    Invalid(#[enumeration(source)] Inner),
+    #[enumeration(format = "wrapped: {code}")]
+    Wrapped {
+        code: u16,
+        #[enumeration(source)]
+        cause: Inner,
+    },
+}
+
+#[test]
+fn error() {
+    assert_eq!(Kind::TimedOut.to_string(), "Request timed out.");
+    assert_eq!(Kind::Refused.to_string(), "refused");
+    assert_eq!(format!("{:>10}", Kind::Refused), "   refused");
+    assert!(Kind::Refused.source().is_none());
+
+    let err = Kind::Invalid(Inner);
+    assert_eq!(err.to_string(), "bad input");
+    assert_eq!(err.source().unwrap().to_string(), "inner");
+
+    let err = Kind::Wrapped { code: 7, cause: Inner };
+    assert_eq!(err.to_string(), "wrapped: 7");
+    assert!(err.source().unwrap().is::<Inner>());
+}
+
+#[derive(Debug, enum_utils::Error)]
+enum AllSources {
+    A(#[enumeration(source)] Inner),
+}
+
+#[test]
+fn error_all_sources() {
+    let err: Box<dyn std::error::Error> = Box::new(AllSources::A(Inner));
+    assert_eq!(err.to_string(), "A");
+    assert!(err.source().is_some());
+}
@@ -0,0 +1,57 @@
+use enum_utils::{FromStr, ForEachVariant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromStr, ForEachVariant)]
+#[enumeration(rename_all = "kebab-case")]
+enum HttpVersion {
+    Http10,
+    #[enumeration(rename = "HTTP/1.1")]
+    Http11,
+    #[enumeration(skip)]
+    #[allow(unused)]
+    Other(u8),
+}
+
+macro_rules! round_trip {
+    ($v:ident, $name:literal) => {
+        assert_eq!($name.parse(), Ok(HttpVersion::$v));
+    };
+}
+
+#[test]
+fn for_each_statements() {
+    for_each_http_version!(round_trip);
+
+    let mut count = 0;
+    macro_rules! count {
+        ($v:ident, $name:literal) => { count += 1; };
+    }
+
+    for_each_http_version!(count);
+    assert_eq!(count, 2);
+}
+
+trait Named {
+    const NAME: &'static str;
+}
+
+macro_rules! marker {
+    ($v:ident, $name:literal) => {
+        pub struct $v;
+
+        impl Named for $v {
+            const NAME: &'static str = $name;
+        }
+    };
+}
+
+mod markers {
+    use super::Named;
+
+    for_each_http_version!(marker);
+}
+
+#[test]
+fn for_each_items() {
+    assert_eq!(<markers::Http10 as Named>::NAME, "http10");
+    assert_eq!(<markers::Http11 as Named>::NAME, "HTTP/1.1");
+}
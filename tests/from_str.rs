@@ -0,0 +1,741 @@
+use enum_utils::{FromStr, IterVariants};
+
+#[derive(Debug, Clone, PartialEq, Eq, FromStr, IterVariants)]
+enum OtherUnit {
+    A,
+    B,
+    #[enumeration(other)]
+    Unknown,
+}
+
+#[test]
+fn other_unit() {
+    assert_eq!("A".parse(), Ok(OtherUnit::A));
+    assert_eq!("Unknown".parse(), Ok(OtherUnit::Unknown));
+    assert_eq!("".parse(), Ok(OtherUnit::Unknown));
+    assert_eq!(OtherUnit::iter().collect::<Vec<_>>(), vec![OtherUnit::A, OtherUnit::B]);
+}
+
+#[derive(Debug, PartialEq, Eq, FromStr)]
+enum OtherBoxed {
+    A,
+    #[enumeration(other)]
+    Unknown(Box<str>),
+}
+
+#[test]
+fn other_boxed() {
+    assert_eq!("A".parse(), Ok(OtherBoxed::A));
+    assert_eq!("a".parse(), Ok(OtherBoxed::Unknown("a".into())));
+}
+
+
+#[derive(Debug, PartialEq, Eq, FromStr)]
+#[enumeration(case_insensitive)]
+enum Tail {
+    Stop,
+    #[enumeration(parse_tail, separator = ":")]
+    Timeout(u32),
+    #[enumeration(parse_tail, rename = "Timeout:ms")]
+    TimeoutMs(u64),
+    #[enumeration(parse_tail)]
+    Retry(u8),
+    #[enumeration(other)]
+    Unknown(String),
+}
+
+#[test]
+fn parse_tail() {
+    assert_eq!("stop".parse(), Ok(Tail::Stop));
+    assert_eq!("TIMEOUT:30".parse(), Ok(Tail::Timeout(30)));
+    assert_eq!("timeout:ms250".parse(), Ok(Tail::TimeoutMs(250)));
+    assert_eq!("retry3".parse(), Ok(Tail::Retry(3)));
+    assert_eq!("retry300".parse(), Ok(Tail::Unknown("retry300".to_owned())));
+    assert_eq!("timeout:".parse(), Ok(Tail::Unknown("timeout:".to_owned())));
+    assert_eq!("é".parse(), Ok(Tail::Unknown("é".to_owned())));
+}
+
+#[derive(Debug, PartialEq, Eq, FromStr)]
+#[enumeration(from_bytes, case_insensitive)]
+enum Bytes {
+    Get,
+    #[enumeration(parse_tail)]
+    Retry(u8),
+    #[enumeration(other)]
+    Unknown(String),
+}
+
+#[test]
+fn from_bytes() {
+    use std::convert::TryFrom;
+
+    assert_eq!(Bytes::try_from(&b"GET"[..]), Ok(Bytes::Get));
+    assert_eq!(Bytes::try_from(&b"retry5"[..]), Ok(Bytes::Retry(5)));
+    assert_eq!(Bytes::try_from(&b"put"[..]), Ok(Bytes::Unknown("put".to_owned())));
+    assert_eq!(Bytes::try_from(&b"\xffget"[..]), Err(()));
+}
+
+#[derive(Debug, PartialEq, Eq, FromStr)]
+#[enumeration(try_from_str, case_insensitive)]
+enum Fallible {
+    Yes,
+    No,
+}
+
+#[test]
+fn try_from_str() {
+    use std::convert::TryFrom;
+
+    fn convert<T: TryFrom<String>>(s: &str) -> Option<T> {
+        T::try_from(s.to_owned()).ok()
+    }
+
+    assert_eq!(Fallible::try_from("YES"), Ok(Fallible::Yes));
+    assert_eq!(convert("no"), Some(Fallible::No));
+    assert_eq!(convert::<Fallible>("maybe"), None);
+}
+
+#[derive(Debug, PartialEq, Eq, FromStr)]
+#[enumeration(canonical_name, rename_all = "kebab-case")]
+enum Canonical {
+    #[enumeration(alias = "bg", alias = "background")]
+    BackgroundColor,
+    #[enumeration(rename = "fg", alias = "foreground-color")]
+    ForegroundColor,
+    #[enumeration(parse_tail)]
+    Opacity(u8),
+}
+
+#[test]
+fn canonical_name() {
+    assert_eq!(Canonical::canonical_name("bg"), Some("background-color"));
+    assert_eq!(Canonical::canonical_name("background-color"), Some("background-color"));
+    assert_eq!(Canonical::canonical_name("foreground-color"), Some("fg"));
+    assert_eq!(Canonical::canonical_name("BG"), None);
+    assert_eq!(Canonical::canonical_name("opacity50"), None);
+    assert_eq!("opacity50".parse(), Ok(Canonical::Opacity(50)));
+}
+
+#[derive(Debug, PartialEq, Eq, FromStr)]
+#[enumeration(parse_error, try_from_str, case_insensitive)]
+enum Rich {
+    Alpha,
+    #[enumeration(alias = "b")]
+    Beta,
+    Gamma,
+}
+
+#[test]
+fn parse_error() {
+    use std::convert::TryFrom;
+    use enum_utils_traits::ParseError;
+
+    assert_eq!("BETA".parse(), Ok(Rich::Beta));
+
+    let err: ParseError = "delta".parse::<Rich>().unwrap_err();
+    assert_eq!(err.input(), "delta");
+    assert_eq!(err.expected(), &["Alpha", "Beta", "Gamma"]);
+    assert_eq!(err.to_string(), "unknown variant `delta`, expected one of `Alpha`, `Beta`, or `Gamma`");
+    assert_eq!(Rich::try_from(String::from("Delta")).unwrap_err().input(), "Delta");
+
+    let _: Box<dyn std::error::Error> = Box::new(err);
+}
+
+#[test]
+fn parse_error_suggestion() {
+    let suggest = |s: &str| s.parse::<Rich>().unwrap_err().suggestion();
+
+    assert_eq!(suggest("gama"), Some("Gamma"));
+    assert_eq!(suggest("BTA"), Some("Beta"));
+    assert_eq!(suggest("alpah"), None);
+    assert_eq!(suggest(""), None);
+    assert_eq!("gamm".parse::<Rich>().unwrap_err().to_string(), "unknown variant `gamm`, did you mean `Gamma`?");
+}
+
+#[derive(Debug, PartialEq, Eq, FromStr, enum_utils::PartialEqStr)]
+#[enumeration(case_insensitive, canonical_name)]
+enum MixedCase {
+    Get,
+    #[enumeration(case_sensitive, alias = "X-Custom")]
+    XCustom,
+    #[enumeration(case_sensitive, rename = "get")]
+    LowerGet,
+    #[enumeration(parse_tail, case_sensitive)]
+    Id(u32),
+}
+
+#[test]
+fn variant_case() {
+    assert_eq!("GET".parse(), Ok(MixedCase::Get));
+    assert_eq!("get".parse(), Ok(MixedCase::LowerGet));
+    assert_eq!("XCustom".parse(), Ok(MixedCase::XCustom));
+    assert_eq!("X-Custom".parse(), Ok(MixedCase::XCustom));
+    assert_eq!("x-custom".parse::<MixedCase>(), Err(()));
+    assert_eq!("Id5".parse(), Ok(MixedCase::Id(5)));
+    assert_eq!("ID5".parse::<MixedCase>(), Err(()));
+
+    assert_eq!(MixedCase::canonical_name("gEt"), Some("Get"));
+    assert_eq!(MixedCase::canonical_name("xcustom"), None);
+
+    assert_eq!(MixedCase::Get, "gEt");
+    assert_ne!(MixedCase::XCustom, "xcustom");
+}
+
+#[derive(Debug, PartialEq, Eq, FromStr, enum_utils::PartialEqStr)]
+#[enumeration(case_insensitive = "unicode", from_bytes)]
+enum Greek {
+    #[enumeration(rename = "σοφος")]
+    Sophos,
+    #[enumeration(rename = "Ωmega", alias = "omega")]
+    Omega,
+    #[enumeration(case_sensitive, rename = "ω")]
+    LowerOmega,
+    #[enumeration(parse_tail, rename = "Δ")]
+    Delta(u8),
+    #[enumeration(case_insensitive = "ascii")]
+    Plain,
+}
+
+#[test]
+fn unicode_case() {
+    use std::convert::TryFrom;
+
+    assert_eq!("ΣΟΦΟΣ".parse(), Ok(Greek::Sophos));
+    assert_eq!("σοφος".parse(), Ok(Greek::Sophos));
+    assert_eq!("ΣΟΦΟς".parse(), Ok(Greek::Sophos));
+    assert_eq!("ωMEGA".parse(), Ok(Greek::Omega));
+    assert_eq!("OMEGA".parse(), Ok(Greek::Omega));
+    assert_eq!("ω".parse(), Ok(Greek::LowerOmega));
+    assert_eq!("Ω".parse::<Greek>(), Err(()));
+    assert_eq!("δ7".parse(), Ok(Greek::Delta(7)));
+    assert_eq!("PLAIN".parse(), Ok(Greek::Plain));
+    assert_eq!(Greek::try_from("ΣΟΦΟΣ".as_bytes()), Ok(Greek::Sophos));
+    assert_eq!(Greek::try_from(&b"\xff"[..]), Err(()));
+
+    assert_eq!(Greek::Omega, "ΩMEGA");
+    assert_eq!(Greek::Plain, "pLaIn");
+    assert_ne!(Greek::LowerOmega, "Ω");
+}
+
+#[derive(Debug, PartialEq, Eq, FromStr, enum_utils::VariantNames)]
+#[enumeration(prefix = "x-", alias_all = "kebab-case", alias_all = "SCREAMING_SNAKE_CASE", canonical_name)]
+enum AliasAll {
+    MaxAge,
+    #[enumeration(no_prefix)]
+    NoCache,
+}
+
+#[test]
+fn alias_all() {
+    assert_eq!(AliasAll::NAMES, &["x-MaxAge", "NoCache"]);
+    assert_eq!("x-MaxAge".parse(), Ok(AliasAll::MaxAge));
+    assert_eq!("x-max-age".parse(), Ok(AliasAll::MaxAge));
+    assert_eq!("x-MAX_AGE".parse(), Ok(AliasAll::MaxAge));
+    assert_eq!("no-cache".parse(), Ok(AliasAll::NoCache));
+    assert_eq!("max-age".parse::<AliasAll>(), Err(()));
+    assert_eq!(AliasAll::canonical_name("NO_CACHE"), Some("NoCache"));
+}
+
+#[derive(Debug, PartialEq, Eq, FromStr)]
+#[enumeration(trim, from_bytes, canonical_name)]
+enum Trimmed {
+    On,
+    Off,
+    #[enumeration(other)]
+    Unknown(String),
+}
+
+#[test]
+fn trim() {
+    use std::convert::TryFrom;
+
+    assert_eq!("  On ".parse(), Ok(Trimmed::On));
+    assert_eq!("\r\nOff\t".parse(), Ok(Trimmed::Off));
+    assert_eq!(" maybe ".parse(), Ok(Trimmed::Unknown("maybe".to_owned())));
+    assert_eq!("   ".parse(), Ok(Trimmed::Unknown(String::new())));
+    assert_eq!(Trimmed::try_from(&b" Off "[..]), Ok(Trimmed::Off));
+    assert_eq!(Trimmed::try_from(&b" \xff "[..]), Err(()));
+    assert_eq!(Trimmed::canonical_name(" On"), Some("On"));
+}
+
+#[derive(Debug, PartialEq, Eq, FromStr, enum_utils::PartialEqStr)]
+#[enumeration(dash_underscore_insensitive, rename_all = "snake_case", canonical_name)]
+enum Dashed {
+    LogLevel,
+    #[enumeration(case_insensitive)]
+    MaxSize,
+    #[enumeration(case_insensitive = "unicode", rename = "größe-max")]
+    Groesse,
+    #[enumeration(parse_tail, separator = "-")]
+    Retry(u8),
+}
+
+#[test]
+fn dash_underscore_insensitive() {
+    assert_eq!("log-level".parse(), Ok(Dashed::LogLevel));
+    assert_eq!("log_level".parse(), Ok(Dashed::LogLevel));
+    assert_eq!("LOG-LEVEL".parse::<Dashed>(), Err(()));
+    assert_eq!("MAX-SIZE".parse(), Ok(Dashed::MaxSize));
+    assert_eq!("GRÖSSE_max".parse::<Dashed>(), Err(()));
+    assert_eq!("GRÖßE_MAX".parse(), Ok(Dashed::Groesse));
+    assert_eq!("retry_3".parse(), Ok(Dashed::Retry(3)));
+    assert_eq!(Dashed::canonical_name("max-size"), Some("max_size"));
+
+    assert_eq!(Dashed::LogLevel, "log-level");
+    assert_ne!(Dashed::LogLevel, "Log-level");
+    assert_eq!(Dashed::MaxSize, "Max-Size");
+    assert_eq!(Dashed::Groesse, "GRÖßE_MAX");
+}
+
+mod parse_fn {
+    use enum_utils::FromStr;
+
+    #[derive(Debug, PartialEq, Eq, FromStr)]
+    #[enumeration(parse_fn = "pub parse_unit", from_bytes, case_insensitive)]
+    pub enum Unit {
+        Ms,
+        #[enumeration(alias = "sec")]
+        S,
+        #[enumeration(parse_tail)]
+        Custom(u8),
+    }
+}
+
+#[test]
+fn parse_fn() {
+    use std::convert::TryFrom;
+    use parse_fn::Unit;
+
+    assert_eq!(Unit::parse_unit(b"MS"), Some(Unit::Ms));
+    assert_eq!(Unit::parse_unit(b"sec"), Some(Unit::S));
+    assert_eq!(Unit::parse_unit(b"custom7"), Some(Unit::Custom(7)));
+    assert_eq!(Unit::parse_unit(b"min"), None);
+    assert_eq!(Unit::try_from(&b"s"[..]), Ok(Unit::S));
+}
+
+#[derive(Debug, PartialEq, Eq, FromStr)]
+#[enumeration(from_prefix, rename_all = "lowercase")]
+enum Keyword {
+    In,
+    Int,
+    #[enumeration(alias = "integer")]
+    Integral,
+    #[enumeration(case_insensitive = "unicode", rename = "σ")]
+    Sigma,
+}
+
+#[test]
+fn from_prefix() {
+    assert_eq!(Keyword::from_prefix(b"int x"), Some((Keyword::Int, 3)));
+    assert_eq!(Keyword::from_prefix(b"in"), Some((Keyword::In, 2)));
+    assert_eq!(Keyword::from_prefix(b"integers"), Some((Keyword::Integral, 7)));
+    assert_eq!(Keyword::from_prefix(b"integral"), Some((Keyword::Integral, 8)));
+    assert_eq!(Keyword::from_prefix("Σ1".as_bytes()), Some((Keyword::Sigma, 2)));
+    assert_eq!(Keyword::from_prefix(b"i"), None);
+    assert_eq!(Keyword::from_prefix(b""), None);
+}
+
+#[derive(Debug, PartialEq, Eq, FromStr)]
+#[enumeration(numeric_aliases, canonical_name)]
+#[repr(u8)]
+enum Signal {
+    Hup = 1,
+    Int,
+    #[enumeration(skip)]
+    #[allow(unused)]
+    Quit,
+    Kill = 9,
+}
+
+#[test]
+fn numeric_aliases() {
+    assert_eq!("1".parse(), Ok(Signal::Hup));
+    assert_eq!("2".parse(), Ok(Signal::Int));
+    assert_eq!("9".parse(), Ok(Signal::Kill));
+    assert_eq!("Kill".parse(), Ok(Signal::Kill));
+    assert_eq!("3".parse::<Signal>(), Err(()));
+    assert_eq!(Signal::canonical_name("9"), Some("Kill"));
+}
+
+#[derive(Debug, PartialEq, Eq, FromStr)]
+#[enumeration(parser = "match", case_insensitive)]
+enum MatchParsed {
+    Get,
+    Put,
+    #[enumeration(case_sensitive, alias = "del")]
+    Delete,
+}
+
+#[derive(Debug, PartialEq, Eq, FromStr)]
+#[enumeration(parser = "table", dash_underscore_insensitive, rename_all = "kebab-case")]
+enum TableParsed {
+    Get,
+    Put,
+    DryRun,
+    #[enumeration(alias = "del")]
+    Delete,
+}
+
+#[test]
+fn parser() {
+    assert_eq!("get".parse(), Ok(MatchParsed::Get));
+    assert_eq!("PUT".parse(), Ok(MatchParsed::Put));
+    assert_eq!("del".parse(), Ok(MatchParsed::Delete));
+    assert_eq!("Delete".parse(), Ok(MatchParsed::Delete));
+    assert_eq!("DEL".parse::<MatchParsed>(), Err(()));
+    assert_eq!("ge".parse::<MatchParsed>(), Err(()));
+
+    assert_eq!("get".parse(), Ok(TableParsed::Get));
+    assert_eq!("put".parse(), Ok(TableParsed::Put));
+    assert_eq!("dry_run".parse(), Ok(TableParsed::DryRun));
+    assert_eq!("del".parse(), Ok(TableParsed::Delete));
+    assert_eq!("delete".parse(), Ok(TableParsed::Delete));
+    assert_eq!("Get".parse::<TableParsed>(), Err(()));
+    assert_eq!("pet".parse::<TableParsed>(), Err(()));
+}
+
+#[derive(Debug, PartialEq, Eq, FromStr)]
+#[enumeration(qualified, canonical_name)]
+enum Direction {
+    North,
+    #[enumeration(alias = "Down")]
+    South,
+}
+
+#[derive(Debug, PartialEq, Eq, FromStr)]
+#[enumeration(qualified = "only", case_insensitive)]
+enum Level {
+    Low,
+    High,
+}
+
+#[test]
+fn qualified() {
+    assert_eq!("Direction::North".parse(), Ok(Direction::North));
+    assert_eq!("North".parse(), Ok(Direction::North));
+    assert_eq!("Direction::Down".parse(), Ok(Direction::South));
+    assert_eq!("Down".parse(), Ok(Direction::South));
+    assert_eq!("Level::North".parse::<Direction>(), Err(()));
+    assert_eq!(Direction::canonical_name("Direction::South"), Some("South"));
+
+    assert_eq!("Level::Low".parse(), Ok(Level::Low));
+    assert_eq!("level::HIGH".parse(), Ok(Level::High));
+    assert_eq!("Low".parse::<Level>(), Err(()));
+}
+
+#[derive(Debug, PartialEq, Eq, FromStr)]
+#[enumeration(case_insensitive, dash_underscore_insensitive, rename_all = "snake_case")]
+enum Opcode {
+    Add,
+    And,
+    Branch,
+    Call,
+    Div,
+    Eq,
+    Fence,
+    Get,
+    Halt,
+    Inc,
+    #[enumeration(rename = "")]
+    Nop,
+    #[enumeration(rename = "_pop")]
+    Pop,
+}
+
+#[test]
+fn first_byte_dispatch() {
+    assert_eq!("add".parse(), Ok(Opcode::Add));
+    assert_eq!("AND".parse(), Ok(Opcode::And));
+    assert_eq!("halt".parse(), Ok(Opcode::Halt));
+    assert_eq!("".parse(), Ok(Opcode::Nop));
+    assert_eq!("-POP".parse(), Ok(Opcode::Pop));
+    assert_eq!("_pop".parse(), Ok(Opcode::Pop));
+    assert_eq!("pop".parse::<Opcode>(), Err(()));
+    assert_eq!("jump".parse::<Opcode>(), Err(()));
+    assert_eq!("ad".parse::<Opcode>(), Err(()));
+}
+
+#[derive(Debug, PartialEq, Eq, FromStr)]
+#[enumeration(parse_detailed, trim)]
+enum Shell {
+    #[enumeration(alias = "sh", alias = "bourne")]
+    Bash,
+    #[enumeration(case_insensitive = "unicode", rename = "Ζsh")]
+    Zsh,
+}
+
+#[test]
+fn parse_detailed() {
+    assert_eq!(Shell::parse_detailed("Bash"), Some((Shell::Bash, "Bash", false)));
+    assert_eq!(Shell::parse_detailed(" sh\n"), Some((Shell::Bash, "sh", false)));
+    assert_eq!(Shell::parse_detailed("bourne"), Some((Shell::Bash, "bourne", false)));
+    assert_eq!(Shell::parse_detailed("ζSH"), Some((Shell::Zsh, "Ζsh", false)));
+    assert_eq!(Shell::parse_detailed("fish"), None);
+}
+
+#[derive(Debug, PartialEq, Eq, FromStr)]
+#[enumeration(no_panic, from_bytes, from_prefix, trim)]
+enum Unit {
+    #[enumeration(rename = "")]
+    None,
+    #[enumeration(rename = "m")]
+    Meter,
+    #[enumeration(rename = "mm")]
+    Millimeter,
+    #[enumeration(other)]
+    Unknown,
+}
+
+#[test]
+fn no_panic() {
+    use std::convert::TryFrom;
+
+    assert_eq!("".parse(), Ok(Unit::None));
+    assert_eq!(" mm ".parse(), Ok(Unit::Millimeter));
+    assert_eq!("km".parse(), Ok(Unit::Unknown));
+    assert_eq!(Unit::try_from(&b"   "[..]), Ok(Unit::None));
+    assert_eq!(Unit::from_prefix(b"mms"), Some((Unit::Millimeter, 2)));
+    assert_eq!(Unit::from_prefix(b"s"), Some((Unit::None, 0)));
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromStr, IterVariants)]
+enum Phase {
+    Alpha,
+    #[enumeration(skip(FromStr))]
+    Internal,
+    #[enumeration(skip(IterVariants, Display))]
+    Legacy,
+    #[enumeration(skip)]
+    #[allow(unused)]
+    Never,
+}
+
+#[test]
+fn scoped_skip() {
+    assert_eq!("Alpha".parse(), Ok(Phase::Alpha));
+    assert_eq!("Internal".parse::<Phase>(), Err(()));
+    assert_eq!("Legacy".parse(), Ok(Phase::Legacy));
+    assert_eq!("Never".parse::<Phase>(), Err(()));
+    assert_eq!(Phase::iter().collect::<Vec<_>>(), vec![Phase::Alpha, Phase::Internal]);
+}
+
+#[derive(Debug, PartialEq, Eq, FromStr)]
+#[enumeration(from_name, parse_error, trim)]
+enum Answer {
+    Yes,
+    #[enumeration(alias = "nope")]
+    No,
+    #[enumeration(parse_tail)]
+    Maybe(u8),
+}
+
+#[test]
+fn from_name() {
+    assert_eq!(Answer::from_name(" Yes"), Some(Answer::Yes));
+    assert_eq!(Answer::from_name("nope"), Some(Answer::No));
+    assert_eq!(Answer::from_name("Maybe50"), Some(Answer::Maybe(50)));
+    assert_eq!(Answer::from_name("yes"), None);
+    assert_eq!("No".parse::<Answer>(), Ok(Answer::No));
+    assert_eq!("yes".parse::<Answer>().unwrap_err().input(), "yes");
+}
+
+#[derive(Debug, PartialEq, Eq, FromStr)]
+#[enumeration(from_bytes, from_prefix, case_insensitive)]
+enum Frame {
+    #[enumeration(alias_bytes = b"\xfe\xff", alias_bytes = b"\x80A")]
+    Begin,
+    #[enumeration(alias_bytes = b"end")]
+    End,
+}
+
+#[test]
+fn alias_bytes() {
+    use std::convert::TryFrom;
+
+    assert_eq!(Frame::try_from(&b"\xfe\xff"[..]), Ok(Frame::Begin));
+    assert_eq!(Frame::try_from(&b"\x80A"[..]), Ok(Frame::Begin));
+    assert_eq!(Frame::try_from(&b"\x80a"[..]), Err(()));
+    assert_eq!(Frame::try_from(&b"BEGIN"[..]), Ok(Frame::Begin));
+    assert_eq!(Frame::try_from(&b"END"[..]), Ok(Frame::End));
+    assert_eq!("end".parse(), Ok(Frame::End));
+    assert_eq!(Frame::from_prefix(b"\xfe\xff\x00"), Some((Frame::Begin, 2)));
+}
+
+#[derive(Debug, PartialEq, Eq, FromStr)]
+#[enumeration(parse_detailed, qualified, case_insensitive)]
+enum Mode {
+    #[enumeration(alias = "fast", deprecated)]
+    #[enumeration(alias = "quick")]
+    Release,
+    #[enumeration(alias = "FAST", case_sensitive)]
+    Turbo,
+    Debug,
+}
+
+#[test]
+fn deprecated_aliases() {
+    assert_eq!("Fast".parse(), Ok(Mode::Release));
+    assert_eq!("FAST".parse(), Ok(Mode::Turbo));
+    assert!(Mode::is_deprecated_name("fast"));
+    assert!(Mode::is_deprecated_name("Mode::Fast"));
+    assert!(!Mode::is_deprecated_name("FAST"));
+    assert!(!Mode::is_deprecated_name("quick"));
+    assert!(!Mode::is_deprecated_name("release"));
+    assert!(!Mode::is_deprecated_name("slow"));
+    assert_eq!(Mode::parse_detailed("fast"), Some((Mode::Release, "fast", true)));
+    assert_eq!(Mode::parse_detailed("Mode::fast"), Some((Mode::Release, "Mode::fast", true)));
+    assert_eq!(Mode::parse_detailed("Quick"), Some((Mode::Release, "quick", false)));
+}
+
+#[derive(Debug, PartialEq, Eq, FromStr)]
+#[enumeration(from_cstr, from_bytes, case_insensitive)]
+enum Channel {
+    Stdout,
+    #[enumeration(alias_bytes = b"\xff")]
+    Stderr,
+}
+
+#[derive(Debug, PartialEq, Eq, FromStr)]
+#[enumeration(from_cstr_ptr = "from_ptr", parse_fn = "parse_bytes")]
+enum Stream {
+    In,
+    Out,
+}
+
+#[test]
+fn from_cstr() {
+    use std::convert::TryFrom;
+    use std::ffi::CStr;
+
+    let cstr = |b: &'static [u8]| CStr::from_bytes_with_nul(b).unwrap();
+    assert_eq!(Channel::try_from(cstr(b"STDOUT\0")), Ok(Channel::Stdout));
+    assert_eq!(Channel::try_from(cstr(b"\xff\0")), Ok(Channel::Stderr));
+    assert_eq!(Channel::try_from(cstr(b"stdin\0")), Err(()));
+
+    assert_eq!(Stream::try_from(cstr(b"In\0")), Ok(Stream::In));
+    assert_eq!(unsafe { Stream::from_ptr(cstr(b"Out\0").as_ptr()) }, Some(Stream::Out));
+    assert_eq!(unsafe { Stream::from_ptr(std::ptr::null()) }, None);
+    assert_eq!(Stream::parse_bytes(b"Out"), Some(Stream::Out));
+}
+
+#[derive(Debug, PartialEq, Eq, FromStr)]
+#[enumeration(case_insensitive, assume_lowercase, dash_underscore_insensitive)]
+enum Header {
+    #[enumeration(rename = "Content-Type")]
+    ContentType,
+    #[enumeration(rename = "ETag", case_sensitive)]
+    ETag,
+}
+
+#[test]
+fn assume_lowercase() {
+    assert_eq!("content-type".parse(), Ok(Header::ContentType));
+    assert_eq!("content_type".parse(), Ok(Header::ContentType));
+    assert_eq!("ETag".parse(), Ok(Header::ETag));
+    assert_eq!("etag".parse::<Header>(), Err(()));
+}
+
+#[derive(Debug, PartialEq, Eq, FromStr)]
+#[enumeration(inline = "always", from_bytes, try_from_str, canonical_name)]
+enum Inlined {
+    On,
+    #[enumeration(alias = "no")]
+    Off,
+}
+
+#[test]
+fn inline() {
+    use std::convert::TryFrom;
+
+    assert_eq!("On".parse(), Ok(Inlined::On));
+    assert_eq!(Inlined::try_from(&b"no"[..]), Ok(Inlined::Off));
+    assert_eq!(Inlined::try_from("Off"), Ok(Inlined::Off));
+    assert_eq!(Inlined::canonical_name("no"), Some("Off"));
+}
+
+#[derive(Debug, PartialEq, Eq, FromStr)]
+#[enumeration(parse_error, expecting = "a compass direction")]
+enum Compass {
+    North,
+    South,
+}
+
+#[test]
+fn expecting() {
+    let err = "up".parse::<Compass>().unwrap_err();
+    assert_eq!(err.expecting(), Some("a compass direction"));
+    assert_eq!(err.to_string(), "unknown variant `up`, expected a compass direction");
+    assert_eq!("Nort".parse::<Compass>().unwrap_err().to_string(), "unknown variant `Nort`, did you mean `North`?");
+}
+
+#[derive(Debug, PartialEq, Eq, FromStr)]
+#[enumeration(aliases_file = "tests/data/http_methods.txt", canonical_name)]
+enum HttpMethod {
+    Get,
+    #[enumeration(alias = "p")]
+    Post,
+    Delete,
+}
+
+#[test]
+fn aliases_file() {
+    assert_eq!("Get".parse(), Ok(HttpMethod::Get));
+    assert_eq!("GET".parse(), Ok(HttpMethod::Get));
+    assert_eq!("post".parse(), Ok(HttpMethod::Post));
+    assert_eq!("p".parse(), Ok(HttpMethod::Post));
+    assert_eq!("del".parse(), Ok(HttpMethod::Delete));
+    assert_eq!("#".parse::<HttpMethod>(), Err(()));
+    assert_eq!("`remove`".parse::<HttpMethod>(), Err(()));
+    assert_eq!(HttpMethod::canonical_name("DELETE"), Some("Delete"));
+}
+
+#[derive(Debug, PartialEq, Eq, FromStr)]
+#[enumeration(rename_all = "snake_case_acronyms", alias_all = "SCREAMING_SNAKE_CASE_ACRONYMS")]
+enum Acronyms {
+    ParseHTTPResponse,
+    IOError,
+    Http2Server,
+    HTTP2Client,
+    Plain,
+}
+
+#[test]
+fn rename_acronyms() {
+    assert_eq!("parse_http_response".parse(), Ok(Acronyms::ParseHTTPResponse));
+    assert_eq!("io_error".parse(), Ok(Acronyms::IOError));
+    assert_eq!("http2_server".parse(), Ok(Acronyms::Http2Server));
+    assert_eq!("http2_client".parse(), Ok(Acronyms::HTTP2Client));
+    assert_eq!("plain".parse(), Ok(Acronyms::Plain));
+    assert_eq!("PARSE_HTTP_RESPONSE".parse(), Ok(Acronyms::ParseHTTPResponse));
+    assert_eq!("parse_h_t_t_p_response".parse::<Acronyms>(), Err(()));
+}
+
+#[derive(Debug, PartialEq, Eq, FromStr)]
+#[enumeration(rename_all = "lowercase", trim)]
+enum Weekday {
+    #[enumeration(locale(en = "Monday", de = "Montag", fr = "lundi"))]
+    Monday,
+    #[enumeration(locale(en = "Tuesday", de = "Dienstag"), case_insensitive)]
+    Tuesday,
+    #[enumeration(skip, locale(en = "Holiday"))]
+    #[allow(unused)]
+    Holiday,
+}
+
+#[test]
+fn locale() {
+    assert_eq!(Weekday::Monday.name_in("fr"), "lundi");
+    assert_eq!(Weekday::Tuesday.name_in("fr"), "tuesday");
+    assert_eq!(Weekday::Tuesday.name_in("xx"), "tuesday");
+    assert_eq!(Weekday::Holiday.name_in("en"), "Holiday");
+    assert_eq!(Weekday::Holiday.name_in("de"), "Holiday");
+
+    assert_eq!(Weekday::parse_in("de", " Montag "), Some(Weekday::Monday));
+    assert_eq!(Weekday::parse_in("de", "montag"), None);
+    assert_eq!(Weekday::parse_in("de", "DIENSTAG"), Some(Weekday::Tuesday));
+    assert_eq!(Weekday::parse_in("en", "Montag"), None);
+    assert_eq!(Weekday::parse_in("en", "Holiday"), None);
+    assert_eq!(Weekday::parse_in("fr", "lundi"), Some(Weekday::Monday));
+    assert_eq!(Weekday::parse_in("it", "lunedì"), None);
+    assert_eq!("Montag".parse::<Weekday>(), Err(()));
+}
@@ -0,0 +1,44 @@
+#![cfg(feature = "async-graphql")]
+
+use async_graphql::{EmptyMutation, EmptySubscription, Object, Schema};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, enum_utils::FromStr, enum_utils::GraphQLEnum)]
+#[enumeration(rename_all = "SCREAMING_SNAKE_CASE")]
+enum Color {
+    /// Pure red.
+    Red,
+    #[enumeration(rename = "GRN", alias = "GREEN")]
+    Green,
+    #[enumeration(skip)]
+    #[allow(unused)]
+    Invisible,
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn echo(&self, color: Color) -> Color {
+        color
+    }
+}
+
+#[tokio::test]
+async fn graphql_enum() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+
+    let sdl = schema.sdl();
+    assert!(sdl.contains("enum Color {"));
+    assert!(sdl.contains("\"\"\"\n\tPure red.\n\t\"\"\"\n\tRED"));
+    assert!(sdl.contains("\tGRN\n"));
+    assert!(!sdl.contains("GREEN"));
+    assert!(!sdl.contains("INVISIBLE"));
+
+    let res = schema.execute("{ echo(color: GRN) }").await;
+    assert!(res.errors.is_empty(), "{:?}", res.errors);
+    assert_eq!(res.data.to_string(), "{echo: GRN}");
+    assert_eq!("GRN".parse(), Ok(Color::Green));
+
+    let res = schema.execute("{ echo(color: GREEN) }").await;
+    assert!(!res.errors.is_empty());
+}
@@ -0,0 +1,30 @@
+use enum_utils::VariantId;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, VariantId)]
+#[enumeration(rename_all = "lowercase")]
+enum Packet {
+    A,
+    #[enumeration(rename = "hello")]
+    Greeting,
+    #[enumeration(skip)]
+    Unknown,
+}
+
+#[test]
+fn id() {
+    // The 32-bit FNV-1a hashes of "a" and "hello".
+    assert_eq!(Packet::A.id(), 0xe40c_292c);
+    assert_eq!(Packet::Greeting.id(), 0x4f9f_2cab);
+
+    assert_eq!(Packet::from_id(0xe40c_292c), Some(Packet::A));
+    assert_eq!(Packet::from_id(0), None);
+
+    const GREETING: Option<Packet> = Packet::from_id(0x4f9f_2cab);
+    assert_eq!(GREETING, Some(Packet::Greeting));
+}
+
+#[test]
+#[should_panic(expected = "`Packet::Unknown` is skipped")]
+fn skipped() {
+    Packet::Unknown.id();
+}
@@ -1,4 +1,4 @@
-use enum_utils::IterVariants;
+use enum_utils::{EnumCount, IterVariants};
 
 #[derive(Debug, IterVariants, PartialEq, Eq)]
 #[repr(u32)]
@@ -54,7 +54,7 @@ fn skip_c_like() {
 }
 
 #[derive(Debug, IterVariants, PartialEq, Eq)]
-#[repr(C, u16)]
+#[repr(u16)]
 #[repr(align(2))]
 enum MultiRepr {
     A,
@@ -68,3 +68,127 @@ fn multi_repr() {
     assert_eq!(vec![A, B],
                MultiRepr::iter().collect::<Vec<_>>());
 }
+
+#[test]
+fn adjacent() {
+    use self::SkipCLike::*;
+
+    assert_eq!(vec![(A, C)],
+               SkipCLike::iter_adjacent().collect::<Vec<_>>());
+    assert_eq!(Vec::<(Zst, Zst)>::new(),
+               Zst::iter_adjacent().collect::<Vec<_>>());
+    assert_eq!(Vec::<(Empty, Empty)>::new(),
+               Empty::iter_adjacent().collect::<Vec<_>>());
+}
+
+#[derive(Debug, IterVariants, PartialEq, Eq)]
+#[enumeration(debug_checks)]
+#[repr(i8)]
+#[allow(unused)]
+enum DebugChecks {
+    #[enumeration(skip)]
+    A = -1,
+    B,
+    C,
+    D,
+}
+
+#[test]
+fn debug_checks() {
+    use self::DebugChecks::*;
+
+    assert_eq!(vec![B, C, D],
+               DebugChecks::iter().collect::<Vec<_>>());
+}
+
+#[derive(Debug, Clone, EnumCount, IterVariants)]
+#[allow(unused)]
+enum CountSkip {
+    A,
+    #[enumeration(skip)]
+    B,
+    C,
+}
+
+#[derive(Debug, EnumCount)]
+enum CountEmpty {}
+
+#[test]
+fn count() {
+    assert_eq!(CountSkip::COUNT, 2);
+    assert_eq!(CountSkip::COUNT, CountSkip::iter().count());
+    assert_eq!(CountEmpty::COUNT, 0);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, enum_utils::Ordinal, enum_utils::EnumCount)]
+enum Ordinal {
+    A,
+    #[enumeration(skip)]
+    #[allow(unused)]
+    B(u32),
+    C,
+    D,
+}
+
+#[test]
+fn ordinal() {
+    assert_eq!(Ordinal::COUNT, 3);
+    for i in 0..Ordinal::COUNT {
+        assert_eq!(Ordinal::from_ordinal(i).unwrap().ordinal(), i);
+    }
+
+    assert_eq!(Ordinal::C.ordinal(), 1);
+    assert_eq!(Ordinal::from_ordinal(3), None);
+}
+
+#[test]
+#[should_panic(expected = "`Ordinal::B` is skipped and has no ordinal")]
+fn ordinal_skipped() {
+    Ordinal::B(0).ordinal();
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, enum_utils::Successor)]
+#[enumeration(wrapping)]
+enum Wrapping {
+    A,
+    #[enumeration(skip)]
+    B,
+    C,
+}
+
+#[test]
+fn successor() {
+    assert_eq!(Wrapping::A.next(), Some(Wrapping::C));
+    assert_eq!(Wrapping::C.next(), Some(Wrapping::A));
+    assert_eq!(Wrapping::A.prev(), Some(Wrapping::C));
+    assert_eq!(Wrapping::B.next(), None);
+    assert_eq!(Wrapping::B.prev(), None);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, enum_utils::Successor)]
+#[enumeration(wrapping)]
+enum Single {
+    A,
+}
+
+#[test]
+fn successor_single() {
+    assert_eq!(Single::A.next(), Some(Single::A));
+    assert_eq!(Single::A.prev(), Some(Single::A));
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, enum_utils::EnumBounds)]
+#[repr(u16)]
+enum Bounds {
+    Middle = 10,
+    #[enumeration(skip)]
+    Last = 500,
+    First = 1,
+}
+
+#[test]
+fn bounds() {
+    assert_eq!(Bounds::MIN, Bounds::First);
+    assert_eq!(Bounds::MAX, Bounds::Last);
+    assert_ne!(Bounds::Middle, Bounds::MIN);
+}
@@ -0,0 +1,29 @@
+use std::convert::TryFrom;
+
+include!("data/large_enum.rs");
+
+#[test]
+fn parse() {
+    assert_eq!("v0".parse(), Ok(Large::V0));
+    assert_eq!("v12".parse(), Ok(Large::V12));
+    assert_eq!("v120".parse(), Ok(Large::V120));
+    assert_eq!("v1199".parse(), Ok(Large::V1199));
+    assert_eq!("v1200".parse::<Large>(), Err(()));
+    assert_eq!("v".parse::<Large>(), Err(()));
+    assert_eq!("V1".parse::<Large>(), Err(()));
+}
+
+#[test]
+fn round_trip() {
+    assert_eq!(Large::COUNT, 1200);
+    assert_eq!(Large::iter().count(), Large::COUNT);
+
+    for (i, v) in Large::iter().enumerate() {
+        assert_eq!(v.to_string(), format!("v{}", i));
+        assert_eq!(v.name().parse(), Ok(v));
+        assert_eq!(u16::from(v), i as u16);
+        assert_eq!(Large::try_from(i as u16), Ok(v));
+    }
+
+    assert_eq!(Large::try_from(1200u16), Err(()));
+}
@@ -0,0 +1,62 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq, enum_utils::EnumMap)]
+enum Key {
+    A,
+    #[enumeration(skip)]
+    #[allow(unused)]
+    B(u8),
+    C,
+}
+
+#[test]
+fn index() {
+    let mut map = KeyMap::from_array(["a", "c"]);
+    assert_eq!(map[Key::A], "a");
+    assert_eq!(map[Key::C], "c");
+
+    map[Key::C] = "d";
+    assert_eq!(map.iter().collect::<Vec<_>>(), vec![(Key::A, &"a"), (Key::C, &"d")]);
+}
+
+#[test]
+#[should_panic(expected = "`Key::B` is skipped and cannot be used as a key")]
+fn index_skipped() {
+    let map = KeyMap::<u8>::default();
+    let _ = map[Key::B(0)];
+}
+
+#[test]
+fn iter_mut() {
+    let mut map = KeyMap::from_fn(|_| 1);
+    for (_, v) in map.iter_mut() {
+        *v *= 2;
+    }
+
+    assert_eq!(map.values().sum::<i32>(), 4);
+}
+
+#[derive(enum_utils::EnumMap)]
+enum Empty {}
+
+#[test]
+fn empty() {
+    assert_eq!(EmptyMap::<u8>::default().values().count(), 0);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, enum_utils::EnumIndex, enum_utils::EnumCount)]
+enum Slot {
+    First,
+    #[enumeration(skip)]
+    #[allow(unused)]
+    Hidden,
+    Second,
+}
+
+#[test]
+fn array_index() {
+    let mut table = [""; Slot::COUNT];
+    table[Slot::First] = "a";
+    table[Slot::Second] = "b";
+    assert_eq!(table, ["a", "b"]);
+    assert_eq!(table[Slot::Second], "b");
+    assert_eq!(table[0], "a");
+}
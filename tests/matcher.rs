@@ -0,0 +1,43 @@
+use enum_utils::Matcher;
+use enum_utils_traits::MatchState;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Matcher)]
+#[enumeration(rename_all = "kebab-case", dash_underscore_insensitive)]
+enum Header {
+    Host,
+    #[enumeration(case_insensitive)]
+    ContentType,
+    #[enumeration(rename = "Content-Type")]
+    ExactContentType,
+    #[enumeration(alias = "ho")]
+    Short,
+}
+
+#[derive(Matcher)]
+enum Empty {}
+
+fn feed(matcher: &mut HeaderMatcher, s: &[u8]) -> Vec<MatchState<Header>> {
+    s.iter().map(|&b| matcher.push(b)).collect()
+}
+
+#[test]
+fn matcher() {
+    use MatchState::*;
+
+    let mut m = HeaderMatcher::default();
+    assert_eq!(feed(&mut m, b"host"), [Incomplete, Matched(Header::Short), Incomplete, Matched(Header::Host)]);
+    assert_eq!(m.push(b'x'), Failed);
+    assert_eq!(m.push(b'h'), Failed);
+
+    m.reset();
+    assert_eq!(feed(&mut m, b"Content_Type").last(), Some(&Matched(Header::ExactContentType)));
+
+    m.reset();
+    assert_eq!(feed(&mut m, b"CONTENT-type").last(), Some(&Matched(Header::ContentType)));
+
+    m.reset();
+    assert_eq!(feed(&mut m, b"HO"), [Failed, Failed]);
+
+    let mut e = EmptyMatcher::new();
+    assert!(matches!(e.push(b'a'), Failed));
+}
@@ -0,0 +1,256 @@
+use enum_utils::{AsRefStr, AsStr, Display, PartialEqStr, VariantNames};
+
+#[derive(Debug, AsStr, Display, PartialEqStr, VariantNames)]
+enum Empty {}
+
+#[derive(Debug, Display)]
+#[enumeration(rename_all = "SCREAMING_SNAKE_CASE")]
+enum Padded {
+    Short,
+    LongerName,
+}
+
+#[test]
+fn display_padding() {
+    assert_eq!(format!("{:>8}|", Padded::Short), "   SHORT|");
+    assert_eq!(format!("{:<8}|", Padded::LongerName), "LONGER_NAME|");
+}
+
+#[derive(Debug, Display)]
+#[enumeration(rename_all = "lowercase")]
+enum SkippedDisplay {
+    Shown,
+    #[enumeration(skip)]
+    Hidden,
+}
+
+#[test]
+fn display_skipped() {
+    assert_eq!(SkippedDisplay::Shown.to_string(), "shown");
+    assert_eq!(SkippedDisplay::Hidden.to_string(), "Hidden");
+    assert_eq!(format!("{:>7}", SkippedDisplay::Hidden), " Hidden");
+}
+
+#[derive(Debug, Display, enum_utils::FromStr)]
+enum Templated {
+    #[enumeration(format = "{{literal}}")]
+    Braces,
+    #[enumeration(format = "pair({1}, {0:>3})", skip)]
+    Pair(u8, char),
+    #[enumeration(format = "tail:{0}", parse_tail, separator = ":", rename = "tail")]
+    Tail(u16),
+    #[enumeration(format = "{name}", skip)]
+    Named { name: &'static str, unused: bool },
+}
+
+#[test]
+fn display_format() {
+    assert_eq!(Templated::Braces.to_string(), "{literal}");
+    assert_eq!(Templated::Pair(7, 'x').to_string(), "pair(x,   7)");
+    assert_eq!(Templated::Named { name: "n", unused: false }.to_string(), "n");
+
+    let s = Templated::Tail(42).to_string();
+    assert_eq!(s, "tail:42");
+    assert!(matches!(s.parse(), Ok(Templated::Tail(42))));
+}
+
+#[derive(Debug, PartialEq, AsStr, Display, enum_utils::FromStr)]
+#[enumeration(rename_all(serialize = "SCREAMING_SNAKE_CASE", deserialize = "kebab-case"))]
+enum Split {
+    MaxAge,
+    #[enumeration(rename = "ttl")]
+    TimeToLive,
+}
+
+#[derive(Debug, PartialEq, Display, enum_utils::FromStr)]
+#[enumeration(rename_all(serialize = "lowercase"))]
+enum SerializeOnly {
+    NoCache,
+}
+
+#[test]
+fn split_rename_all() {
+    assert_eq!(Split::MaxAge.to_string(), "MAX_AGE");
+    assert_eq!(Split::MaxAge.name(), "MAX_AGE");
+    assert_eq!("max-age".parse(), Ok(Split::MaxAge));
+    assert_eq!("MAX_AGE".parse::<Split>(), Err(()));
+    assert_eq!(Split::TimeToLive.to_string(), "ttl");
+    assert_eq!("ttl".parse(), Ok(Split::TimeToLive));
+
+    assert_eq!(SerializeOnly::NoCache.to_string(), "nocache");
+    assert_eq!("NoCache".parse(), Ok(SerializeOnly::NoCache));
+}
+
+#[derive(Debug, PartialEq, AsStr, Display, enum_utils::FromStr)]
+#[enumeration(rename_all = "lowercase", prefix = "x-")]
+enum DisplayAs {
+    #[enumeration(rename = "gz", alias = "gzip", display = "gzip")]
+    Gzip,
+    Brotli,
+}
+
+#[test]
+fn display_attr() {
+    assert_eq!(DisplayAs::Gzip.to_string(), "gzip");
+    assert_eq!(DisplayAs::Gzip.name(), "gzip");
+    assert_eq!(DisplayAs::Brotli.to_string(), "x-brotli");
+    assert_eq!("x-gz".parse(), Ok(DisplayAs::Gzip));
+    assert_eq!("gzip".parse(), Ok(DisplayAs::Gzip));
+}
+
+#[test]
+fn display_empty() {
+    let _: Option<Empty> = None;
+    assert!(Empty::NAMES.is_empty());
+}
+
+#[derive(enum_utils::EnumMessage)]
+#[enumeration(rename_all = "kebab-case")]
+enum Status {
+    ///   Everything is fine.
+    Ok,
+
+    /// Something went wrong.
+    #[enumeration(message = "Overridden")]
+    #[allow(unused)]
+    Failed(String),
+
+    NotStarted,
+}
+
+#[test]
+fn message() {
+    assert_eq!(Status::Ok.message(), "Everything is fine.");
+    assert_eq!(Status::Failed(String::new()).message(), "Overridden");
+    assert_eq!(Status::NotStarted.message(), "not-started");
+}
+
+#[derive(Debug, PartialEqStr)]
+#[enumeration(rename_all = "kebab-case")]
+enum Method {
+    Get,
+    #[enumeration(rename = "M-SEARCH", alias = "msearch")]
+    MSearch,
+    #[enumeration(skip)]
+    #[allow(unused)]
+    Extension(String),
+}
+
+#[test]
+fn partial_eq_str() {
+    assert_eq!(Method::Get, "get");
+    assert_eq!(Method::MSearch, "M-SEARCH");
+    assert_ne!(Method::MSearch, "m-search");
+    assert_ne!(Method::MSearch, "msearch");
+    assert_ne!(Method::Extension("get".to_owned()), "get");
+    assert!(Method::Get == *"get");
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, AsRefStr)]
+#[enumeration(rename_all = "snake_case")]
+enum Header {
+    ContentType,
+    #[enumeration(skip)]
+    Custom(u8),
+}
+
+impl std::hash::Hash for Header {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.as_ref().hash(state)
+    }
+}
+
+#[test]
+fn as_ref_str() {
+    use std::collections::HashSet;
+
+    assert_eq!(Header::ContentType.as_ref(), "content_type");
+    assert_eq!(<&str>::from(Header::Custom(1)), "Custom");
+
+    let set: HashSet<Header> = [Header::ContentType].iter().cloned().collect();
+    assert!(set.contains("content_type"));
+    assert!(!set.contains("Custom"));
+}
+
+#[derive(Debug, AsStr, AsRefStr, Display, PartialEqStr, VariantNames)]
+#[enumeration(rename_all = "snake_case")]
+#[allow(unused)]
+enum Event {
+    KeyPress(char),
+    Resize { width: u16, height: u16 },
+    Quit,
+}
+
+#[test]
+fn names_ignore_fields() {
+    let resize = Event::Resize { width: 80, height: 24 };
+    assert_eq!(resize.to_string(), "resize");
+    assert_eq!(resize.name(), "resize");
+    assert_eq!(Event::KeyPress('q').as_ref(), "key_press");
+    assert_eq!(Event::KeyPress('q'), "key_press");
+    assert_eq!(Event::Quit.to_string(), "quit");
+    assert_eq!(Event::NAMES, &["key_press", "resize", "quit"]);
+}
+
+const BASE: i16 = 100;
+
+#[derive(enum_utils::NameOf)]
+#[enumeration(rename_all = "kebab-case", assert_discriminants(NotFound = 100, Gone = 101, Internal = -1))]
+#[repr(i16)]
+#[allow(unused)]
+enum Code {
+    NotFound = BASE,
+    Gone,
+    #[enumeration(skip)]
+    Internal = -1,
+}
+
+#[test]
+fn name_of() {
+    assert_eq!(Code::name_of(100), Some("not-found"));
+    assert_eq!(Code::name_of(101), Some("gone"));
+    assert_eq!(Code::name_of(-1), Some("Internal"));
+    assert_eq!(Code::name_of(0), None);
+
+    const GONE: Option<&str> = Code::name_of(101);
+    assert_eq!(GONE, Some("gone"));
+}
+
+#[derive(Debug, PartialEq, Display, VariantNames, enum_utils::FromStr)]
+#[enumeration(prefix = "net.", rename_all = "lowercase")]
+enum Namespaced {
+    #[enumeration(alias = "up")]
+    Connect,
+    #[enumeration(rename = "close")]
+    Disconnect,
+    #[enumeration(no_prefix)]
+    Ping,
+}
+
+#[test]
+fn prefix() {
+    assert_eq!(Namespaced::NAMES, &["net.connect", "net.close", "ping"]);
+    assert_eq!(Namespaced::Disconnect.to_string(), "net.close");
+    assert_eq!("net.connect".parse(), Ok(Namespaced::Connect));
+    assert_eq!("up".parse(), Ok(Namespaced::Connect));
+    assert_eq!("ping".parse(), Ok(Namespaced::Ping));
+    assert_eq!("connect".parse::<Namespaced>(), Err(()));
+}
+
+#[derive(Debug, PartialEq, Display, VariantNames, enum_utils::FromStr)]
+#[enumeration(prefix = "app.", suffix = ".v2", rename_all = "kebab-case")]
+enum Versioned {
+    MaxConnections,
+    #[enumeration(no_suffix)]
+    Timeout,
+    #[enumeration(no_prefix, no_suffix, rename = "legacy")]
+    Legacy,
+}
+
+#[test]
+fn suffix() {
+    assert_eq!(Versioned::NAMES, &["app.max-connections.v2", "app.timeout", "legacy"]);
+    assert_eq!(Versioned::MaxConnections.to_string(), "app.max-connections.v2");
+    assert_eq!("app.timeout".parse(), Ok(Versioned::Timeout));
+    assert_eq!("app.timeout.v2".parse::<Versioned>(), Err(()));
+}
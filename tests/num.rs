@@ -0,0 +1,65 @@
+#![cfg(feature = "num-traits")]
+
+use num_traits::{FromPrimitive, ToPrimitive};
+
+#[derive(Debug, PartialEq, enum_utils::FromPrimitive, enum_utils::ToPrimitive)]
+#[repr(i8)]
+enum Contiguous {
+    A = -2,
+    B,
+    C,
+    D,
+}
+
+#[derive(Debug, PartialEq, enum_utils::FromPrimitive, enum_utils::ToPrimitive)]
+#[repr(u32)]
+enum Sparse {
+    A = 1,
+    B = 1 << 20,
+    C = u32::MAX,
+}
+
+#[derive(Debug, PartialEq, enum_utils::FromPrimitive)]
+#[enumeration(debug_checks)]
+#[repr(u8)]
+enum Checked {
+    A = 7,
+    B,
+    C,
+}
+
+#[test]
+fn contiguous() {
+    assert_eq!(Contiguous::from_i64(-2), Some(Contiguous::A));
+    assert_eq!(Contiguous::from_i64(1), Some(Contiguous::D));
+    assert_eq!(Contiguous::from_i64(-3), None);
+    assert_eq!(Contiguous::from_i64(2), None);
+    assert_eq!(Contiguous::from_u64(0), Some(Contiguous::C));
+    assert_eq!(Contiguous::from_i64(254), None);
+
+    assert_eq!(Contiguous::A.to_i64(), Some(-2));
+    assert_eq!(Contiguous::A.to_u64(), None);
+    assert_eq!(Contiguous::B.to_i8(), Some(-1));
+    assert_eq!(Contiguous::D.to_u8(), Some(1));
+}
+
+#[test]
+fn sparse() {
+    assert_eq!(Sparse::from_u64(1 << 20), Some(Sparse::B));
+    assert_eq!(Sparse::from_u64(u32::MAX.into()), Some(Sparse::C));
+    assert_eq!(Sparse::from_u64(2), None);
+    assert_eq!(Sparse::from_i64(-1), None);
+    assert_eq!(Sparse::from_u64(1 << 40), None);
+
+    assert_eq!(Sparse::C.to_i64(), Some(u32::MAX.into()));
+    assert_eq!(Sparse::C.to_i32(), None);
+}
+
+#[test]
+fn debug_checks() {
+    assert_eq!(Checked::from_u8(6), None);
+    assert_eq!(Checked::from_u8(7), Some(Checked::A));
+    assert_eq!(Checked::from_i64(8), Some(Checked::B));
+    assert_eq!(Checked::from_u64(9), Some(Checked::C));
+    assert_eq!(Checked::from_u64(10), None);
+}
@@ -0,0 +1,20 @@
+use enum_utils::EnumOrd;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumOrd)]
+enum Priority {
+    #[enumeration(order = 10)]
+    Low,
+    #[enumeration(order = 30)]
+    High,
+    #[enumeration(order = 20)]
+    Medium,
+}
+
+#[test]
+fn order() {
+    let mut ps = vec![Priority::High, Priority::Low, Priority::Medium];
+    ps.sort();
+    assert_eq!(ps, [Priority::Low, Priority::Medium, Priority::High]);
+    assert!(Priority::Medium > Priority::Low);
+    assert_eq!(Priority::High.partial_cmp(&Priority::High), Some(std::cmp::Ordering::Equal));
+}
@@ -0,0 +1,43 @@
+#![cfg(feature = "postgres-types")]
+
+use postgres_types::{FromSql, IsNull, Kind, ToSql, Type};
+
+#[derive(Debug, PartialEq, enum_utils::PostgresEnum)]
+#[enumeration(rename_all = "snake_case")]
+enum JobState {
+    Queued,
+    InProgress,
+    #[enumeration(skip)]
+    #[allow(unused)]
+    Local,
+}
+
+fn enum_type(name: &str, labels: &[&str]) -> Type {
+    let labels = labels.iter().map(|&l| l.to_owned()).collect();
+    Type::new(name.to_owned(), 0, Kind::Enum(labels), "public".to_owned())
+}
+
+#[test]
+fn accepts() {
+    assert!(<JobState as FromSql>::accepts(&enum_type("JobState", &["queued", "in_progress"])));
+    assert!(<JobState as ToSql>::accepts(&enum_type("JobState", &["in_progress", "queued"])));
+
+    assert!(!<JobState as FromSql>::accepts(&enum_type("job_state", &["queued", "in_progress"])));
+    assert!(!<JobState as FromSql>::accepts(&enum_type("JobState", &["queued"])));
+    assert!(!<JobState as FromSql>::accepts(&enum_type("JobState", &["queued", "done"])));
+    assert!(!<JobState as FromSql>::accepts(&Type::TEXT));
+}
+
+#[test]
+fn round_trip() {
+    let ty = enum_type("JobState", &["queued", "in_progress"]);
+
+    let mut buf = Default::default();
+    assert!(matches!(JobState::InProgress.to_sql_checked(&ty, &mut buf), Ok(IsNull::No)));
+    assert_eq!(&buf[..], b"in_progress");
+    assert_eq!(JobState::from_sql(&ty, &buf).unwrap(), JobState::InProgress);
+
+    assert!(JobState::from_sql(&ty, b"local").is_err());
+    assert!(JobState::Local.to_sql_checked(&ty, &mut buf).is_err());
+    assert!(JobState::Queued.to_sql_checked(&Type::TEXT, &mut buf).is_err());
+}
@@ -0,0 +1,30 @@
+#[derive(enum_utils::EnumProperty)]
+enum Level {
+    #[enumeration(props(color = "green"))]
+    #[enumeration(props(code = "I"))]
+    Info,
+    #[enumeration(props(color = "yellow", code = "W"))]
+    Warn,
+    Error,
+}
+
+#[test]
+fn get_property() {
+    assert_eq!(Level::Info.get_property("color"), Some("green"));
+    assert_eq!(Level::Info.get_property("code"), Some("I"));
+    assert_eq!(Level::Warn.get_property("code"), Some("W"));
+    assert_eq!(Level::Error.get_property("color"), None);
+    assert_eq!(Level::Warn.get_property("colour"), None);
+    assert_eq!(Level::Warn.get_property(""), None);
+}
+
+#[derive(enum_utils::EnumProperty)]
+enum Bare {
+    #[allow(unused)]
+    A,
+}
+
+#[test]
+fn no_properties() {
+    assert_eq!(Bare::A.get_property("a"), None);
+}
@@ -0,0 +1,82 @@
+#![cfg(feature = "rand")]
+
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, enum_utils::Random, enum_utils::EnumMap)]
+#[repr(u8)]
+enum Contiguous {
+    A = 1,
+    B,
+    C,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, enum_utils::Random, enum_utils::EnumMap)]
+enum Sparse {
+    A = 10,
+    #[enumeration(skip)]
+    #[allow(unused)]
+    B = 20,
+    C = 30,
+}
+
+#[test]
+fn uniform() {
+    let mut rng = StdRng::seed_from_u64(0);
+
+    let mut counts = ContiguousMap::<u32>::default();
+    for _ in 0..300 {
+        counts[rng.gen::<Contiguous>()] += 1;
+    }
+    assert!(counts.values().all(|&n| n > 50));
+
+    let mut counts = SparseMap::<u32>::default();
+    for _ in 0..200 {
+        counts[rng.gen::<Sparse>()] += 1;
+    }
+    assert!(counts.values().all(|&n| n > 50));
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, enum_utils::Random)]
+#[enumeration(debug_checks)]
+#[repr(i8)]
+enum Checked {
+    A = -1,
+    B,
+    C,
+}
+
+#[test]
+fn debug_checks() {
+    let mut rng = StdRng::seed_from_u64(0);
+    for _ in 0..100 {
+        let v: Checked = rng.gen();
+        assert!(matches!(v, Checked::A | Checked::B | Checked::C));
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, enum_utils::Random, enum_utils::EnumMap)]
+enum Weighted {
+    #[enumeration(weight = 3)]
+    A,
+    B,
+    #[enumeration(weight = 0)]
+    C,
+    #[enumeration(skip, weight = 100)]
+    #[allow(unused)]
+    D,
+}
+
+#[test]
+fn weighted() {
+    let mut rng = StdRng::seed_from_u64(0);
+
+    let mut counts = WeightedMap::<u32>::default();
+    for _ in 0..4000 {
+        counts[Weighted::sample_weighted(&mut rng)] += 1;
+    }
+
+    assert_eq!(counts[Weighted::C], 0);
+    assert!((2800..3200).contains(&counts[Weighted::A]));
+    assert!((800..1200).contains(&counts[Weighted::B]));
+}
@@ -0,0 +1,57 @@
+#![cfg(feature = "rusqlite")]
+
+use rusqlite::types::{FromSqlError, Value};
+use rusqlite::{Connection, Error};
+
+#[derive(Debug, PartialEq, enum_utils::RusqliteSql)]
+#[enumeration(case_insensitive)]
+enum Kind {
+    #[enumeration(rename = "file")]
+    File,
+    #[enumeration(rename = "dir", alias = "directory")]
+    Dir,
+    #[enumeration(skip)]
+    #[allow(unused)]
+    Socket,
+    #[enumeration(other)]
+    Other(String),
+}
+
+#[derive(Debug, PartialEq, enum_utils::RusqliteSql)]
+#[enumeration(sql_repr)]
+#[repr(i8)]
+enum Level {
+    Low = -1,
+    High = 1,
+}
+
+fn select<T: rusqlite::ToSql, U: rusqlite::types::FromSql>(conn: &Connection, v: T) -> rusqlite::Result<U> {
+    conn.query_row("SELECT ?1", [v], |row| row.get(0))
+}
+
+#[test]
+fn text() {
+    let conn = Connection::open_in_memory().unwrap();
+
+    assert_eq!(select::<_, Kind>(&conn, Kind::Dir).unwrap(), Kind::Dir);
+    assert_eq!(select::<_, Kind>(&conn, "DIRECTORY").unwrap(), Kind::Dir);
+    assert_eq!(select::<_, Kind>(&conn, "fifo").unwrap(), Kind::Other("fifo".to_owned()));
+    assert_eq!(select::<_, String>(&conn, Kind::Other("fifo".to_owned())).unwrap(), "fifo");
+    assert_eq!(select::<_, String>(&conn, Kind::File).unwrap(), "file");
+
+    assert!(matches!(select::<_, String>(&conn, Kind::Socket), Err(Error::ToSqlConversionFailure(_))));
+    assert!(matches!(select::<_, Kind>(&conn, 1), Err(Error::InvalidColumnType(..))));
+}
+
+#[test]
+fn repr() {
+    let conn = Connection::open_in_memory().unwrap();
+
+    assert_eq!(select::<_, i64>(&conn, Level::Low).unwrap(), -1);
+    assert_eq!(select::<_, Level>(&conn, 1).unwrap(), Level::High);
+
+    let err = <Level as rusqlite::types::FromSql>::column_result((&Value::Integer(0)).into());
+    assert_eq!(err, Err(FromSqlError::InvalidType));
+    let err = <Level as rusqlite::types::FromSql>::column_result((&Value::Integer(1000)).into());
+    assert_eq!(err, Err(FromSqlError::OutOfRange(1000)));
+}
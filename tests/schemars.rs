@@ -0,0 +1,31 @@
+#![cfg(feature = "schemars")]
+#![allow(unused)]
+
+use serde_json::json;
+
+#[derive(enum_utils::JsonSchema)]
+enum Fruit {
+    #[enumeration(rename = "apple", alias = "pomme")]
+    Apple,
+    #[enumeration(skip)]
+    Rotten,
+    Banana,
+}
+
+#[derive(enum_utils::JsonSchema)]
+enum Open {
+    Known,
+    #[enumeration(other)]
+    Unknown(String),
+}
+
+#[test]
+fn schema() {
+    let schema = schemars::schema_for!(Fruit);
+    assert_eq!(schema.get("title"), Some(&json!("Fruit")));
+    assert_eq!(schema.get("enum"), Some(&json!(["apple", "Banana", "pomme"])));
+
+    let schema = schemars::schema_for!(Open);
+    assert_eq!(schema.get("type"), Some(&json!("string")));
+    assert_eq!(schema.get("enum"), None);
+}
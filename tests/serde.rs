@@ -0,0 +1,106 @@
+#![cfg(feature = "serde")]
+
+#[derive(Debug, PartialEq, enum_utils::Deserialize)]
+#[enumeration(rename_all = "kebab-case")]
+enum Mode {
+    ReadOnly,
+    #[enumeration(rename = "rw", alias = "read-write")]
+    ReadWrite,
+    #[enumeration(skip)]
+    #[allow(unused)]
+    Internal,
+}
+
+#[test]
+fn deserialize() {
+    assert_eq!(serde_json::from_str::<Mode>(r#""read-only""#).unwrap(), Mode::ReadOnly);
+    assert_eq!(serde_json::from_str::<Mode>(r#""rw""#).unwrap(), Mode::ReadWrite);
+    assert_eq!(serde_json::from_str::<Mode>(r#""read-write""#).unwrap(), Mode::ReadWrite);
+
+    let err = serde_json::from_str::<Mode>(r#""internal""#).unwrap_err();
+    assert_eq!(err.to_string(), "unknown variant `internal`, expected `read-only` or `rw` at line 1 column 10");
+
+    let err = serde_json::from_str::<Mode>("3").unwrap_err();
+    assert_eq!(err.to_string(), "invalid type: integer `3`, expected a variant of `Mode` at line 1 column 1");
+}
+
+#[derive(Debug, PartialEq, enum_utils::Deserialize)]
+enum WithOther {
+    Known,
+    #[enumeration(other)]
+    Unknown(String),
+}
+
+#[test]
+fn deserialize_other() {
+    assert_eq!(serde_json::from_str::<WithOther>(r#""Known""#).unwrap(), WithOther::Known);
+    assert_eq!(serde_json::from_str::<WithOther>(r#""?""#).unwrap(), WithOther::Unknown("?".to_owned()));
+}
+
+#[derive(Debug, PartialEq, enum_utils::Deserialize)]
+#[enumeration(expecting = "a compass direction")]
+enum Direction {
+    North,
+    South,
+}
+
+#[test]
+fn deserialize_expecting() {
+    assert_eq!(serde_json::from_str::<Direction>(r#""North""#).unwrap(), Direction::North);
+
+    let err = serde_json::from_str::<Direction>(r#""up""#).unwrap_err();
+    assert_eq!(err.to_string(), "invalid value: string \"up\", expected a compass direction at line 1 column 4");
+
+    let err = serde_json::from_str::<Direction>("3").unwrap_err();
+    assert_eq!(err.to_string(), "invalid type: integer `3`, expected a compass direction at line 1 column 1");
+}
+
+#[derive(Debug, PartialEq, enum_utils::Serialize)]
+#[enumeration(rename_all = "kebab-case")]
+enum Level {
+    #[enumeration(rename = "dbg")]
+    Debug,
+    WarnOnce,
+    #[enumeration(skip)]
+    #[allow(unused)]
+    Hidden(u8),
+}
+
+#[test]
+fn serialize() {
+    assert_eq!(serde_json::to_string(&Level::Debug).unwrap(), r#""dbg""#);
+    assert_eq!(serde_json::to_string(&Level::WarnOnce).unwrap(), r#""warn-once""#);
+
+    let err = serde_json::to_string(&Level::Hidden(1)).unwrap_err();
+    assert_eq!(err.to_string(), "`Level::Hidden` is skipped and cannot be serialized");
+}
+
+#[derive(Debug, PartialEq, enum_utils::Serialize, enum_utils::Deserialize)]
+#[enumeration(rename_all(serialize = "SCREAMING_SNAKE_CASE", deserialize = "snake_case"))]
+enum Split {
+    ReadOnly,
+}
+
+#[test]
+fn split_rename_all() {
+    assert_eq!(serde_json::to_string(&Split::ReadOnly).unwrap(), r#""READ_ONLY""#);
+    assert_eq!(serde_json::from_str::<Split>(r#""read_only""#).unwrap(), Split::ReadOnly);
+    assert!(serde_json::from_str::<Split>(r#""READ_ONLY""#).is_err());
+}
+
+const BASE: u8 = 10;
+
+#[derive(Debug, PartialEq, enum_utils::SerializeRepr, enum_utils::DeserializeRepr)]
+#[repr(u8)]
+enum NonLiteral {
+    A = BASE,
+    B = BASE * 2,
+}
+
+#[test]
+fn repr() {
+    assert_eq!(serde_json::to_string(&NonLiteral::B).unwrap(), "20");
+    assert_eq!(serde_json::from_str::<NonLiteral>("10").unwrap(), NonLiteral::A);
+    assert!(serde_json::from_str::<NonLiteral>("11").is_err());
+    assert!(serde_json::from_str::<NonLiteral>("256").is_err());
+}
@@ -0,0 +1,51 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq, enum_utils::EnumSet)]
+enum Color {
+    Red,
+    #[enumeration(skip)]
+    #[allow(unused)]
+    Custom(u32),
+    Green,
+    Blue,
+}
+
+#[test]
+fn operations() {
+    let mut set = ColorSet::empty();
+    assert!(set.is_empty());
+    assert!(set.insert(Color::Green));
+    assert!(set.contains(Color::Green));
+    assert!(!set.contains(Color::Red));
+
+    let all = ColorSet::all();
+    assert_eq!(all.len(), 3);
+    assert_eq!(all.bits(), 0b111);
+    assert_eq!(all - set, ColorSet::from(Color::Red) | Color::Blue.into());
+    assert_eq!(all ^ set, !set);
+    assert!(set.is_subset(&all));
+
+    assert!(set.remove(Color::Green));
+    assert!(!set.remove(Color::Green));
+    assert_eq!(ColorSet::from_bits_truncate(0xff), all);
+}
+
+#[test]
+fn iter() {
+    let set: ColorSet = vec![Color::Blue, Color::Red].into_iter().collect();
+    assert_eq!(set.iter().collect::<Vec<_>>(), vec![Color::Red, Color::Blue]);
+}
+
+#[test]
+#[should_panic(expected = "`Color::Custom` is skipped and cannot be a member of a set")]
+fn insert_skipped() {
+    ColorSet::empty().insert(Color::Custom(0));
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, enum_utils::EnumSet)]
+enum Eight { A, B, C, D, E, F, G, H }
+
+#[test]
+fn full_width() {
+    assert_eq!(EightSet::all().bits(), u8::MAX);
+    assert_eq!(EightSet::all().complement(), EightSet::empty());
+    assert_eq!(EightSet::from(Eight::H).iter().collect::<Vec<_>>(), vec![Eight::H]);
+}
@@ -0,0 +1,34 @@
+#![cfg(feature = "sqlx")]
+
+use sqlx::sqlite::SqlitePool;
+
+#[derive(Debug, PartialEq, enum_utils::SqlxType)]
+#[enumeration(rename_all = "snake_case", case_insensitive)]
+enum Status {
+    Active,
+    OnHold,
+    #[enumeration(other)]
+    Unknown(String),
+}
+
+#[tokio::test]
+async fn round_trip() -> sqlx::Result<()> {
+    let pool = SqlitePool::connect("sqlite::memory:").await?;
+
+    let status: Status = sqlx::query_scalar("SELECT ?")
+        .bind(Status::OnHold)
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!(status, Status::OnHold);
+
+    let status: Status = sqlx::query_scalar("SELECT 'ACTIVE'").fetch_one(&pool).await?;
+    assert_eq!(status, Status::Active);
+
+    let raw: String = sqlx::query_scalar("SELECT ?")
+        .bind(Status::Unknown("archived".to_owned()))
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!(raw, "archived");
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use enum_utils_traits::{EnumCount, FromRepr, IntoEnumIterator, VariantNames};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, enum_utils::IterVariants, enum_utils::EnumCount,
+         enum_utils::VariantNames, enum_utils::TryFromRepr)]
+#[enumeration(traits, debug_checks, rename_all = "lowercase")]
+#[repr(u8)]
+enum Contiguous {
+    A = 1,
+    B,
+    C,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, enum_utils::IterVariants, enum_utils::EnumCount)]
+#[enumeration(traits)]
+enum Sparse {
+    X,
+    #[enumeration(skip)]
+    #[allow(unused)]
+    Y(u8),
+    Z,
+}
+
+#[derive(Debug, Clone, enum_utils::IterVariants)]
+#[enumeration(traits)]
+enum Empty {}
+
+fn collect<E: IntoEnumIterator>() -> Vec<E> {
+    E::iter().collect()
+}
+
+#[test]
+fn generic() {
+    assert_eq!(collect::<Contiguous>(), [Contiguous::A, Contiguous::B, Contiguous::C]);
+    assert_eq!(collect::<Sparse>(), [Sparse::X, Sparse::Z]);
+    assert!(collect::<Empty>().is_empty());
+
+    assert_eq!(Contiguous::COUNT, 3);
+    assert_eq!(Sparse::COUNT, 2);
+    assert_eq!(Contiguous::NAMES, &["a", "b", "c"]);
+}
+
+#[test]
+fn from_repr() {
+    assert_eq!(Contiguous::from_repr(3), Some(Contiguous::C));
+    assert_eq!(Contiguous::from_repr(0), None);
+}
@@ -0,0 +1,64 @@
+use std::convert::TryFrom;
+
+#[derive(Debug, Clone, Copy, PartialEq, enum_utils::Enum)]
+#[enumeration(derive(FromStr, Display, IterVariants))]
+#[enumeration(rename_all = "snake_case")]
+enum Stage {
+    Parse,
+    TypeCheck,
+    #[enumeration(skip)]
+    #[allow(unused)]
+    Internal,
+    Codegen,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, enum_utils::Enum)]
+#[enumeration(derive(TryFromRepr, ReprFrom))]
+#[repr(i8)]
+enum Sign {
+    Minus = -1,
+    Plus = 1,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, enum_utils::Enum)]
+#[enumeration(derive(FromStr, IterVariants))]
+enum Scoped {
+    A,
+    #[enumeration(skip(FromStr))]
+    B,
+    #[enumeration(skip(Enum))]
+    #[allow(unused)]
+    C,
+    #[enumeration(skip(IterVariants))]
+    D,
+}
+
+#[derive(enum_utils::Enum)]
+#[allow(unused)]
+enum Nothing {
+    A,
+}
+
+#[test]
+fn names() {
+    assert_eq!("type_check".parse(), Ok(Stage::TypeCheck));
+    assert_eq!("internal".parse::<Stage>(), Err(()));
+    assert_eq!(Stage::Codegen.to_string(), "codegen");
+    assert_eq!(Stage::iter().collect::<Vec<_>>(), [Stage::Parse, Stage::TypeCheck, Stage::Codegen]);
+}
+
+#[test]
+fn repr() {
+    assert_eq!(Sign::try_from(-1), Ok(Sign::Minus));
+    assert_eq!(Sign::try_from(0), Err(()));
+    assert_eq!(i8::from(Sign::Plus), 1);
+}
+
+#[test]
+fn scoped_skip() {
+    assert_eq!("A".parse(), Ok(Scoped::A));
+    assert_eq!("B".parse::<Scoped>(), Err(()));
+    assert_eq!("C".parse::<Scoped>(), Err(()));
+    assert_eq!("D".parse(), Ok(Scoped::D));
+    assert_eq!(Scoped::iter().collect::<Vec<_>>(), [Scoped::A, Scoped::B]);
+}
@@ -0,0 +1,36 @@
+#![cfg(feature = "utoipa")]
+#![allow(unused)]
+
+use serde_json::json;
+use utoipa::{PartialSchema, ToSchema};
+
+#[derive(enum_utils::ToSchema)]
+enum Fruit {
+    #[enumeration(rename = "apple", alias = "pomme")]
+    Apple,
+    #[enumeration(skip)]
+    Rotten,
+    Banana,
+}
+
+#[derive(enum_utils::ToSchema)]
+enum Open {
+    Known,
+    #[enumeration(other)]
+    Unknown(String),
+}
+
+#[test]
+fn schema() {
+    assert_eq!(Fruit::name(), "Fruit");
+
+    let schema = serde_json::to_value(Fruit::schema()).unwrap();
+    assert_eq!(schema["type"], json!("string"));
+    assert_eq!(schema["enum"], json!(["apple", "Banana"]));
+    assert_eq!(schema["examples"], json!(["pomme"]));
+
+    let schema = serde_json::to_value(Open::schema()).unwrap();
+    assert_eq!(schema["type"], json!("string"));
+    assert_eq!(schema.get("enum"), None);
+    assert_eq!(schema["examples"], json!(["Known"]));
+}
@@ -0,0 +1,34 @@
+use enum_utils::EnumValue;
+
+#[derive(EnumValue)]
+#[allow(unused)]
+enum Element {
+    #[enumeration(value = 1.008f64)]
+    Hydrogen,
+    #[enumeration(value = 4.0026f64)]
+    Helium { isotope: u8 },
+}
+
+#[derive(EnumValue)]
+enum Flag {
+    #[enumeration(value = b'r')]
+    Read,
+    #[enumeration(value = b'w')]
+    Write,
+}
+
+#[derive(EnumValue)]
+enum Empty {}
+
+#[test]
+fn value() {
+    assert_eq!(Element::Hydrogen.value(), 1.008);
+    assert_eq!(Element::Helium { isotope: 3 }.value(), 4.0026);
+    assert_eq!(Flag::Read.value(), b'r');
+    assert_eq!(Flag::Write.value(), b'w');
+
+    const W: u8 = Flag::Write.value();
+    assert_eq!(W, b'w');
+
+    let _: Option<Empty> = None;
+}
@@ -0,0 +1,57 @@
+#[derive(enum_utils::Visitor)]
+enum Node {
+    Leaf,
+    Value(u32, &'static str),
+    Branch { visitor: Vec<Node> },
+}
+
+struct Count;
+
+impl NodeVisitor for Count {
+    type Output = usize;
+
+    fn visit_leaf(&mut self) -> usize {
+        1
+    }
+
+    fn visit_value(&mut self, _: &u32, _: &&'static str) -> usize {
+        1
+    }
+
+    fn visit_branch(&mut self, visitor: &Vec<Node>) -> usize {
+        1 + visitor.iter().map(|n| n.accept(self)).sum::<usize>()
+    }
+}
+
+struct Describe(Vec<String>);
+
+impl NodeVisitor for Describe {
+    type Output = ();
+
+    fn visit_leaf(&mut self) {
+        self.0.push("leaf".to_owned());
+    }
+
+    fn visit_value(&mut self, n: &u32, s: &&'static str) {
+        self.0.push(format!("{}={}", s, n));
+    }
+
+    fn visit_branch(&mut self, children: &Vec<Node>) {
+        for child in children {
+            child.accept(self);
+        }
+    }
+}
+
+#[test]
+fn visitor() {
+    let tree = Node::Branch {
+        visitor: vec![Node::Leaf, Node::Value(3, "x"), Node::Branch { visitor: vec![] }],
+    };
+
+    assert_eq!(tree.accept(&mut Count), 4);
+
+    let mut describe = Describe(vec![]);
+    tree.accept(&mut describe);
+    assert_eq!(describe.0, ["leaf", "x=3"]);
+}
@@ -0,0 +1,232 @@
+//! Traits for the APIs generated by the derives in [`enum-utils`].
+//!
+//! By default, those derives generate inherent methods and associated constants. If an enum is
+//! marked with `#[enumeration(traits)]`, they implement the traits in this crate instead, so that
+//! generic code can be written against them.
+//!
+//! ```
+//! use enum_utils_traits::{IntoEnumIterator, VariantNames};
+//!
+//! fn dump<E: IntoEnumIterator + core::fmt::Debug>() -> Vec<String> {
+//!     E::iter().map(|v| format!("{:?}", v)).collect()
+//! }
+//!
+//! #[derive(Debug, Clone, enum_utils::IterVariants, enum_utils::VariantNames)]
+//! #[enumeration(traits)]
+//! enum Direction {
+//!     North,
+//!     South,
+//! }
+//!
+//! assert_eq!(dump::<Direction>(), ["North", "South"]);
+//! assert_eq!(Direction::NAMES, &["North", "South"]);
+//! ```
+//!
+//! It also contains [`ParseError`], which is returned by `enum_utils::FromStr` for an enum marked
+//! with `#[enumeration(parse_error)]`, and [`MatchState`], which is returned by the matchers
+//! generated by `enum_utils::Matcher`.
+//!
+//! [`enum-utils`]: https://docs.rs/enum-utils
+
+#![no_std]
+
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+use alloc::string::String;
+use core::fmt;
+
+/// An enum whose unskipped variants can be iterated over in declaration order.
+///
+/// Implemented by `enum_utils::IterVariants`.
+pub trait IntoEnumIterator: Sized {
+    /// The iterator returned by [`iter`](IntoEnumIterator::iter).
+    type Iter: Iterator<Item = Self> + Clone;
+
+    /// Returns an iterator over each unskipped variant.
+    fn iter() -> Self::Iter;
+}
+
+/// An enum with a name for each unskipped variant.
+///
+/// Implemented by `enum_utils::VariantNames`.
+pub trait VariantNames {
+    /// The canonical name of each unskipped variant, in declaration order.
+    const NAMES: &'static [&'static str];
+}
+
+/// An enum with a fixed number of unskipped variants.
+///
+/// Implemented by `enum_utils::EnumCount`.
+pub trait EnumCount {
+    /// The number of unskipped variants.
+    const COUNT: usize;
+}
+
+/// A C-like enum which can be constructed from its primitive representation.
+///
+/// Implemented by `enum_utils::TryFromRepr`.
+pub trait FromRepr: Sized {
+    /// The type given in `#[repr(...)]`.
+    type Repr;
+
+    /// Returns the variant whose discriminant is `repr`, if there is one.
+    fn from_repr(repr: Self::Repr) -> Option<Self>;
+}
+
+/// The error returned when a string does not name any variant of an enum.
+///
+/// Returned by `enum_utils::FromStr` for an enum marked with `#[enumeration(parse_error)]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    input: String,
+    expected: &'static [&'static str],
+    expecting: Option<&'static str>,
+}
+
+impl ParseError {
+    /// Creates an error for `input`, which is not one of the `expected` names.
+    pub fn new(input: &str, expected: &'static [&'static str]) -> Self {
+        ParseError {
+            input: input.into(),
+            expected,
+            expecting: None,
+        }
+    }
+
+    /// Describes the expected input with `expecting` instead of listing every name.
+    ///
+    /// This is used by `enum_utils::FromStr` for an enum marked with
+    /// `#[enumeration(expecting = "...")]`.
+    pub fn with_expecting(mut self, expecting: &'static str) -> Self {
+        self.expecting = Some(expecting);
+        self
+    }
+
+    /// The string which could not be parsed.
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    /// The canonical name of each variant which can be parsed, in declaration order.
+    ///
+    /// Aliases are accepted as well, but are not listed.
+    pub fn expected(&self) -> &'static [&'static str] {
+        self.expected
+    }
+
+    /// A description of the expected input, if one was given with [`with_expecting`].
+    ///
+    /// [`with_expecting`]: #method.with_expecting
+    pub fn expecting(&self) -> Option<&'static str> {
+        self.expecting
+    }
+
+    /// The expected name which is closest to the input, if any is close enough to be a likely
+    /// typo.
+    ///
+    /// Names are compared by their edit distance, ignoring ASCII case. A name is only suggested if
+    /// it is at most one edit away for every three characters of input (and at least one edit).
+    /// Ties are broken in favor of the name which was declared first.
+    pub fn suggestion(&self) -> Option<&'static str> {
+        let max = core::cmp::max(self.input.chars().count() / 3, 1);
+
+        let mut best = None;
+        for &name in self.expected {
+            let dist = match edit_distance(&self.input, name, max) {
+                Some(d) => d,
+                None => continue,
+            };
+
+            match best {
+                Some((_, best_dist)) if best_dist <= dist => {}
+                _ => best = Some((name, dist)),
+            }
+        }
+
+        best.map(|(name, _)| name)
+    }
+}
+
+/// Returns the Levenshtein distance between `a` and `b`, ignoring ASCII case, or `None` if it is
+/// greater than `max`.
+fn edit_distance(a: &str, b: &str, max: usize) -> Option<usize> {
+    use alloc::vec::Vec;
+
+    let a: Vec<char> = a.chars().map(|c| c.to_ascii_lowercase()).collect();
+    let b: Vec<char> = b.chars().map(|c| c.to_ascii_lowercase()).collect();
+
+    let len_diff = if a.len() > b.len() { a.len() - b.len() } else { b.len() - a.len() };
+    if len_diff > max {
+        return None;
+    }
+
+    // Only the previous row of the matrix is needed to compute the next one.
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut cur = Vec::with_capacity(prev.len());
+    for (i, &ca) in a.iter().enumerate() {
+        cur.clear();
+        cur.push(i + 1);
+        for (j, &cb) in b.iter().enumerate() {
+            let subst = prev[j] + if ca == cb { 0 } else { 1 };
+            cur.push(subst.min(prev[j + 1] + 1).min(cur[j] + 1));
+        }
+
+        // The distance can never be smaller than the minimum of any row.
+        if cur.iter().all(|&d| d > max) {
+            return None;
+        }
+
+        core::mem::swap(&mut prev, &mut cur);
+    }
+
+    Some(prev[b.len()]).filter(|&d| d <= max)
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown variant `{}`", self.input)?;
+
+        if let Some(name) = self.suggestion() {
+            return write!(f, ", did you mean `{}`?", name);
+        }
+
+        if let Some(expecting) = self.expecting {
+            return write!(f, ", expected {}", expecting);
+        }
+
+        match self.expected {
+            [] => write!(f, ", there are no variants"),
+            [name] => write!(f, ", expected `{}`", name),
+            [a, b] => write!(f, ", expected `{}` or `{}`", a, b),
+            [names @ .., last] => {
+                write!(f, ", expected one of ")?;
+                for name in names {
+                    write!(f, "`{}`, ", name)?;
+                }
+                write!(f, "or `{}`", last)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+/// The state of a streaming matcher after it has been fed a byte.
+///
+/// Returned by the `push` method of the matchers generated by `enum_utils::Matcher`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchState<T> {
+    /// The bytes so far are the start of a name, but not a complete one.
+    Incomplete,
+
+    /// The bytes so far are the name of this variant. They may also be the start of a longer
+    /// name.
+    Matched(T),
+
+    /// The bytes so far are not the start of any name. This is permanent until the matcher is
+    /// reset.
+    Failed,
+}